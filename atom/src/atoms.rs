@@ -1,5 +1,6 @@
 pub mod chunk;
 pub mod object;
+pub mod path;
 pub mod scalar;
 pub mod sequence;
 pub mod string;
@@ -9,7 +10,7 @@ pub mod vector;
 use urid::*;
 
 /// An URID collection of all standard atom types, provided for convenience.
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct AtomURIDCollection {
     pub blank: URID<object::Blank>,
     pub double: URID<scalar::Double>,
@@ -22,6 +23,7 @@ pub struct AtomURIDCollection {
     pub chunk: URID<chunk::Chunk>,
     pub literal: URID<string::Literal>,
     pub object: URID<object::Object>,
+    pub path: URID<path::Path>,
     pub property: URID<object::Property>,
     pub string: URID<string::String>,
     pub tuple: URID<tuple::Tuple>,
@@ -42,6 +44,7 @@ impl URIDCollection for AtomURIDCollection {
             chunk: map.map_type()?,
             literal: map.map_type()?,
             object: map.map_type()?,
+            path: map.map_type()?,
             property: map.map_type()?,
             string: map.map_type()?,
             tuple: map.map_type()?,
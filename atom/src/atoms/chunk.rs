@@ -24,6 +24,8 @@
 //! }
 //! ```
 //!
+//! The writing handle is an [`AtomWriter`], which extends the chunk with every call to [`write_bytes`](crate::space::SpaceWriter::write_bytes) and updates the size in the atom header accordingly. A chunk can therefore be written incrementally, for example while its contents are computed, without collecting the bytes in an intermediate buffer first. If the space runs out, [`AtomWriteError::OutOfSpace`] is returned and the chunk keeps the bytes written so far.
+//!
 //! # Specification
 //!
 //! [http://lv2plug.in/ns/ext/atom/atom.html#Chunk](http://lv2plug.in/ns/ext/atom/atom.html#Chunk)
@@ -122,4 +124,54 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_chunk_incremental_writes() {
+        let map = HashURIDMapper::new();
+        let urids = crate::atoms::AtomURIDCollection::from_map(&map).unwrap();
+
+        let bytes: Vec<u8> = (0..30).collect();
+
+        let mut single_space = AlignedVec::<AtomHeader>::new_with_capacity(8);
+        let single_space = single_space.as_space_mut();
+        SpaceCursor::new(single_space.as_bytes_mut())
+            .write_atom(urids.chunk)
+            .unwrap()
+            .write_bytes(&bytes)
+            .unwrap();
+
+        let mut streamed_space = AlignedVec::<AtomHeader>::new_with_capacity(8);
+        let streamed_space = streamed_space.as_space_mut();
+        {
+            let mut space = SpaceCursor::new(streamed_space.as_bytes_mut());
+            let mut writer = space.write_atom(urids.chunk).unwrap();
+            for part in bytes.chunks(7) {
+                writer.write_bytes(part).unwrap();
+                assert_eq!(
+                    writer.atom_header().size_of_body(),
+                    writer.allocated_bytes().len() - 8
+                );
+            }
+        }
+
+        assert_eq!(single_space.as_bytes(), streamed_space.as_bytes());
+        let atom = unsafe { streamed_space.read().next_atom() }.unwrap();
+        assert_eq!(atom.read(urids.chunk).unwrap().as_bytes(), &bytes[..]);
+
+        // The space is exhausted.
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(3);
+        let raw_space = raw_space.as_space_mut();
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = space.write_atom(urids.chunk).unwrap();
+            writer.write_bytes(&bytes[..10]).unwrap();
+            assert!(matches!(
+                writer.write_bytes(&bytes[10..]),
+                Err(AtomWriteError::OutOfSpace { .. })
+            ));
+            assert_eq!(writer.atom_header().size_of_body(), 10);
+        }
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        assert_eq!(atom.read(urids.chunk).unwrap().as_bytes(), &bytes[..10]);
+    }
 }
@@ -69,10 +69,14 @@
 //!
 //! # Specification
 //! [http://lv2plug.in/ns/ext/atom/atom.html#Object](http://lv2plug.in/ns/ext/atom/atom.html#Object).
+use crate::atoms::scalar::AtomURID;
+use crate::atoms::AtomURIDCollection;
 use crate::space::SpaceReader;
 use crate::*;
 use core::convert::TryFrom;
 use core::iter::Iterator;
+use core::mem::size_of;
+use units::UnitURIDCollection;
 use urid::UriBound;
 use urid::URID;
 
@@ -96,6 +100,165 @@ pub struct ObjectHeader {
     pub otype: URID,
 }
 
+impl ObjectHeader {
+    /// Create a new object header from a typed object type URID.
+    ///
+    /// Unlike the struct fields, this constructor only accepts a [`TypeUrid`](struct.TypeUrid.html), which can't be mixed up with a property key.
+    #[inline]
+    pub fn new(id: Option<URID>, otype: TypeUrid) -> Self {
+        Self {
+            id,
+            otype: otype.get(),
+        }
+    }
+
+    /// Return the object type as a typed URID.
+    #[inline]
+    pub fn type_urid(&self) -> TypeUrid {
+        TypeUrid(self.otype)
+    }
+
+    /// Check whether the object has the given type.
+    ///
+    /// Reading an object returns its header alongside the property reader, which allows to dispatch on the type of the object before its properties are read:
+    ///
+    /// ```
+    /// use lv2_atom::prelude::*;
+    /// use lv2_atom::atoms::object::{ObjectHeader, TypeUrid};
+    /// use lv2_atom::space::SpaceWriter;
+    /// use urid::*;
+    ///
+    /// #[uri("urn:my-plugin:note")]
+    /// struct NoteMessage;
+    ///
+    /// #[uri("urn:my-plugin:tempo")]
+    /// struct TempoMessage;
+    ///
+    /// #[uri("urn:my-plugin:value")]
+    /// struct Value;
+    ///
+    /// #[derive(URIDCollection)]
+    /// struct MyURIDs {
+    ///     atom: AtomURIDCollection,
+    ///     note: URID<NoteMessage>,
+    ///     tempo: URID<TempoMessage>,
+    ///     value: URID<Value>,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Message {
+    ///     Note(i32),
+    ///     Tempo(f32),
+    /// }
+    ///
+    /// fn parse_message(atom: &UnidentifiedAtom, urids: &MyURIDs) -> Option<Message> {
+    ///     let (header, reader) = atom.read(urids.atom.object).ok()?;
+    ///     if header.has_type(urids.note) {
+    ///         let note = reader.get(urids.value, urids.atom.int)?.ok()?;
+    ///         Some(Message::Note(*note))
+    ///     } else if header.has_type(urids.tempo) {
+    ///         let tempo = reader.get(urids.value, urids.atom.float)?.ok()?;
+    ///         Some(Message::Tempo(*tempo))
+    ///     } else {
+    ///         None
+    ///     }
+    /// }
+    ///
+    /// let map = HashURIDMapper::new();
+    /// let urids: MyURIDs = map.populate_collection().unwrap();
+    ///
+    /// let mut note = OwnedAtom::new();
+    /// note.write()
+    ///     .write_atom(urids.atom.object)
+    ///     .unwrap()
+    ///     .write_header(ObjectHeader::new(None, TypeUrid::new(urids.note)))
+    ///     .unwrap()
+    ///     .new_property(urids.value, urids.atom.int)
+    ///     .unwrap()
+    ///     .set(60)
+    ///     .unwrap();
+    ///
+    /// let mut tempo = OwnedAtom::new();
+    /// tempo.write()
+    ///     .write_atom(urids.atom.object)
+    ///     .unwrap()
+    ///     .write_header(ObjectHeader::new(None, TypeUrid::new(urids.tempo)))
+    ///     .unwrap()
+    ///     .new_property(urids.value, urids.atom.float)
+    ///     .unwrap()
+    ///     .set(120.0)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(parse_message(note.atom().unwrap(), &urids), Some(Message::Note(60)));
+    /// assert_eq!(parse_message(tempo.atom().unwrap(), &urids), Some(Message::Tempo(120.0)));
+    /// ```
+    #[inline]
+    pub fn has_type<T: ?Sized>(&self, otype: URID<T>) -> bool {
+        self.otype == otype
+    }
+}
+
+/// The URID of an object type.
+///
+/// Object types and property keys are both plain URIDs, which makes them easy to confuse. This wrapper, as well as [`KeyUrid`](struct.KeyUrid.html), lets the compiler tell them apart:
+///
+/// ```compile_fail
+/// use lv2_atom::atoms::object::*;
+/// use urid::*;
+///
+/// let key = KeyUrid::new(URID::new(1).unwrap());
+/// // A key can not be used as an object type.
+/// let header = ObjectHeader::new(None, key);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypeUrid(URID);
+
+impl TypeUrid {
+    /// Mark the given URID as an object type.
+    #[inline]
+    pub fn new<T: ?Sized>(urid: URID<T>) -> Self {
+        Self(urid.into_general())
+    }
+
+    /// Return the wrapped URID.
+    #[inline]
+    pub fn get(self) -> URID {
+        self.0
+    }
+}
+
+/// The URID of a property key.
+///
+/// This is the counterpart to [`TypeUrid`](struct.TypeUrid.html):
+///
+/// ```compile_fail
+/// use lv2_atom::prelude::*;
+/// use lv2_atom::space::*;
+/// use lv2_atom::atoms::object::*;
+/// use urid::*;
+///
+/// fn write(writer: &mut ObjectWriter, urids: &AtomURIDCollection, otype: TypeUrid) {
+///     // An object type can not be used as a property key.
+///     writer.new_keyed_property(otype, urids.int);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyUrid(URID);
+
+impl KeyUrid {
+    /// Mark the given URID as a property key.
+    #[inline]
+    pub fn new<T: ?Sized>(urid: URID<T>) -> Self {
+        Self(urid.into_general())
+    }
+
+    /// Return the wrapped URID.
+    #[inline]
+    pub fn get(self) -> URID {
+        self.0
+    }
+}
+
 pub struct ObjectReaderHandle;
 impl<'a> AtomHandle<'a> for ObjectReaderHandle {
     type Handle = (ObjectHeader, ObjectReader<'a>);
@@ -149,7 +312,7 @@ impl Atom for Object {
             })?,
         };
 
-        let reader = ObjectReader { reader };
+        let reader = ObjectReader { reader, depth: 0 };
 
         Ok((header, reader))
     }
@@ -194,8 +357,70 @@ impl Atom for Blank {
 /// An iterator over all properties in an object.
 ///
 /// Each iteration item is the header of the property, as well as the space occupied by the value atom. You can use normal `read` methods on the returned space.
+///
+/// The properties are yielded in the order in which they are stored in the object's body. Objects are logically unordered, but keeping the serialization order allows objects to be round-tripped without changes.
+#[derive(Clone)]
 pub struct ObjectReader<'a> {
     reader: SpaceReader<'a>,
+    depth: usize,
+}
+
+/// The maximal nesting depth of objects read with [`ObjectReader::read_object`](struct.ObjectReader.html#method.read_object).
+///
+/// The top-level object has a depth of 0.
+pub const MAX_OBJECT_DEPTH: usize = 32;
+
+impl<'a> ObjectReader<'a> {
+    /// Read the value of the first property with the given key.
+    ///
+    /// The properties are scanned from the current position of the iterator, so properties it has already yielded aren't considered. The iterator itself is not advanced. Returns `None` if there is no remaining property with the given key.
+    ///
+    /// # Errors
+    ///
+    /// If a property with the given key exists, but its value isn't an atom of the given type or is malformed, `Some(Err(_))` is returned.
+    pub fn get<K: ?Sized, A: Atom>(
+        &self,
+        key: URID<K>,
+        atom_type: URID<A>,
+    ) -> Option<Result<<A::ReadHandle as AtomHandle<'a>>::Handle, AtomReadError>> {
+        self.clone()
+            .find(|(header, _)| header.key == key)
+            .map(|(_, atom)| atom.read(atom_type))
+    }
+
+    /// Read the value of a property of this object as an object.
+    ///
+    /// Both [`Object`](struct.Object.html)s and [`Blank`](struct.Blank.html)s are accepted. The returned reader is one level deeper than this one, which allows recursive walkers over nested objects, like those in patch messages, to stay within a bounded depth.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the value isn't an object or is malformed, or if the nested object would be deeper than [`MAX_OBJECT_DEPTH`](constant.MAX_OBJECT_DEPTH.html).
+    pub fn read_object(
+        &self,
+        value: &'a UnidentifiedAtom,
+        urids: &AtomURIDCollection,
+    ) -> Result<(ObjectHeader, ObjectReader<'a>), AtomReadError> {
+        if self.depth >= MAX_OBJECT_DEPTH {
+            return Err(AtomReadError::InvalidAtomValue {
+                reading_type_uri: Object::uri(),
+                error_message: "Objects are nested too deeply",
+            });
+        }
+
+        let (header, mut reader) = if value.header().urid() == urids.blank {
+            value.read(urids.blank)?
+        } else {
+            value.read(urids.object)?
+        };
+        reader.depth = self.depth + 1;
+
+        Ok((header, reader))
+    }
+
+    /// The nesting depth of this object, starting at 0 for objects that weren't read with [`read_object`](#method.read_object).
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
 }
 
 impl<'a> Iterator for ObjectReader<'a> {
@@ -209,6 +434,45 @@ impl<'a> Iterator for ObjectReader<'a> {
     }
 }
 
+/// Return the keys of all properties that differ between two objects.
+///
+/// A property differs if its value isn't byte-wise equal in both objects, including the type and size of the atom, or if it only exists in one of them. The changed and added keys are returned in the order of `new`, followed by the removed keys in the order of `old`. If a key occurs more than once in an object, only its first occurrence is compared, just like in [`ObjectReader::get`](struct.ObjectReader.html#method.get).
+///
+/// This can be used to only emit the properties that have changed, for example as `patch:Set` messages.
+pub fn object_diff(old: &ObjectReader, new: &ObjectReader) -> Vec<URID> {
+    fn first_value<'a>(reader: &ObjectReader<'a>, key: URID) -> Option<&'a UnidentifiedAtom> {
+        reader
+            .clone()
+            .find(|(header, _)| header.key == key)
+            .map(|(_, atom)| atom)
+    }
+
+    let mut keys: Vec<URID> = Vec::new();
+
+    for (header, new_value) in new.clone() {
+        if keys.contains(&header.key) {
+            continue;
+        }
+        let changed = match first_value(old, header.key) {
+            Some(old_value) => {
+                old_value.atom_space().as_bytes() != new_value.atom_space().as_bytes()
+            }
+            None => true,
+        };
+        if changed {
+            keys.push(header.key);
+        }
+    }
+
+    for (header, _) in old.clone() {
+        if !keys.contains(&header.key) && first_value(new, header.key).is_none() {
+            keys.push(header.key);
+        }
+    }
+
+    keys
+}
+
 /// Writing handle for object properties.
 ///
 /// This handle is a safeguard to assure that a object is always a series of properties.
@@ -253,6 +517,144 @@ impl<'a> ObjectWriter<'a> {
         Property::write_header(&mut self.frame, key, None::<URID<()>>)?;
         self.frame.write_atom(atom_type)
     }
+
+    /// Copies a property verbatim, e.g. from another object.
+    ///
+    /// This is useful to modify some properties of an object and pass the rest through: You read the input object, write the modified properties with [`new_property`](#method.new_property) and forward all other properties with this method.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if there is not enough space in the underlying buffer,
+    /// or if any other write error occurs.
+    pub fn forward_property(
+        &mut self,
+        header: &PropertyHeader,
+        value: &UnidentifiedAtom,
+    ) -> Result<(), AtomWriteError> {
+        Property::write_header(&mut self.frame, header.key, header.context)?;
+        self.frame.copy_atom(value)?;
+        Ok(())
+    }
+
+    /// Initializes a new property with a typed key.
+    ///
+    /// This method does the same as [`new_property`](#method.new_property), but only accepts a [`KeyUrid`](struct.KeyUrid.html) as the key.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if there is not enough space in the underlying buffer,
+    /// or if any other write error occurs.
+    #[inline]
+    pub fn new_keyed_property<A: Atom>(
+        &mut self,
+        key: KeyUrid,
+        atom_type: URID<A>,
+    ) -> Result<<A::WriteHandle as AtomHandle>::Handle, AtomWriteError> {
+        self.new_property(key.get(), atom_type)
+    }
+
+    /// Writes a `units:unit` property that annotates the object's value with a unit.
+    ///
+    /// Hosts and GUIs use this property to display values with the correct suffix, e.g. "dB" or "Hz". The value of the property is a URID atom that contains the URID of the given unit:
+    ///
+    /// ```
+    /// use lv2_atom::prelude::*;
+    /// use lv2_atom::atoms::object::{ObjectHeader, TypeUrid};
+    /// use lv2_atom::space::SpaceWriter;
+    /// use lv2_units::prelude::*;
+    /// use urid::*;
+    ///
+    /// let map = HashURIDMapper::new();
+    /// let urids: AtomURIDCollection = map.populate_collection().unwrap();
+    /// let units: UnitURIDCollection = map.populate_collection().unwrap();
+    /// let gain = map.map_str("urn:my-plugin:gain").unwrap();
+    /// let value = map.map_str("urn:my-plugin:value").unwrap();
+    ///
+    /// let mut owned = OwnedAtom::new();
+    /// let mut cursor = owned.write();
+    /// let mut writer = cursor
+    ///     .write_atom(urids.object)
+    ///     .unwrap()
+    ///     .write_header(ObjectHeader::new(None, TypeUrid::new(gain)))
+    ///     .unwrap();
+    /// writer.new_property(value, urids.float).unwrap().set(-6.0).unwrap();
+    /// writer.new_unit_property(&units, urids.urid, units.db).unwrap();
+    ///
+    /// let (_, reader) = owned.atom().unwrap().read(urids.object).unwrap();
+    /// let unit = reader.get(units.unit, urids.urid).unwrap().unwrap();
+    /// assert_eq!(*unit, units.db);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if there is not enough space in the underlying buffer,
+    /// or if any other write error occurs.
+    pub fn new_unit_property<U: ?Sized>(
+        &mut self,
+        units: &UnitURIDCollection,
+        urid_type: URID<AtomURID>,
+        unit: URID<U>,
+    ) -> Result<(), AtomWriteError> {
+        self.new_property(units.unit, urid_type)?
+            .set(unit.into_general())?;
+        Ok(())
+    }
+
+    /// Finish the object, with its properties sorted by their key URIDs.
+    ///
+    /// The properties of an LV2 object are unordered, but some consumers and golden-file tests expect a deterministic order. This method reorders the properties that have been written so far, keeping the relative order of properties with the same key.
+    ///
+    /// Since the properties are reordered in a temporary buffer, this method allocates and therefore isn't realtime-safe.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if there is not enough space in the underlying buffer to pad the reordered properties, or if the written properties are malformed.
+    pub fn finish_sorted(mut self) -> Result<(), AtomWriteError> {
+        let malformed = AtomWriteError::IllegalOperation {
+            writing_type_uri: Object::uri(),
+            error_message: "Malformed object properties",
+        };
+        let start = size_of::<sys::LV2_Atom_Object_Body>();
+        let property_header_size = size_of::<StrippedPropertyHeader>() + size_of::<AtomHeader>();
+
+        // SAFETY: The properties are only read here.
+        let body = unsafe { self.frame.body_bytes_mut() };
+        let old_len = body.len();
+
+        let mut properties = Vec::new();
+        let mut offset = start;
+        while offset < body.len() {
+            let header = body
+                .get(offset..offset + property_header_size)
+                .ok_or(malformed)?;
+            let key = u32::from_ne_bytes([header[0], header[1], header[2], header[3]]);
+            let size = u32::from_ne_bytes([header[8], header[9], header[10], header[11]]);
+            let end = offset + property_header_size + size as usize;
+            if end > body.len() {
+                return Err(malformed);
+            }
+            properties.push((key, offset..end));
+            offset = crate::util::padded_size::<AtomHeader>(end);
+        }
+        properties.sort_by_key(|(key, _)| *key);
+
+        let mut sorted = Vec::with_capacity(offset - start);
+        for (_, range) in properties {
+            sorted.extend_from_slice(&body[range]);
+            sorted.resize(crate::util::padded_size::<AtomHeader>(sorted.len()), 0);
+        }
+
+        // The last property might not have been padded before.
+        let new_len = start + sorted.len();
+        if new_len > old_len {
+            self.frame.allocate(new_len - old_len)?;
+        }
+
+        // SAFETY: The body is overwritten with the same, but reordered, properties.
+        let body = unsafe { self.frame.body_bytes_mut() };
+        body[start..new_len].copy_from_slice(&sorted);
+        Ok(())
+    }
 }
 
 /// An atom containing a key-value pair.
@@ -285,6 +687,14 @@ struct StrippedPropertyHeader {
     context: u32,
 }
 
+impl PropertyHeader {
+    /// Return the key of the property as a typed URID.
+    #[inline]
+    pub fn key_urid(&self) -> KeyUrid {
+        KeyUrid(self.key)
+    }
+}
+
 impl Property {
     /// Read the body of a property atom from a space.
     ///
@@ -332,13 +742,276 @@ impl Property {
 
 #[cfg(test)]
 mod tests {
-    use crate::atoms::object::{ObjectHeader, PropertyHeader};
+    use crate::atoms::object::{
+        object_diff, KeyUrid, ObjectHeader, ObjectReader, ObjectWriter, PropertyHeader, TypeUrid,
+        MAX_OBJECT_DEPTH,
+    };
     use crate::prelude::*;
+    use crate::space::error::AtomReadError;
     use crate::space::*;
     use crate::AtomHeader;
     use std::mem::size_of;
     use urid::*;
 
+    #[test]
+    fn test_forward_property() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let object_type = map.map_str("urn:my-type").unwrap();
+        let context = map.map_str("urn:my-context").unwrap();
+        let keys: Vec<URID> = ["urn:key-a", "urn:key-b", "urn:key-c"]
+            .iter()
+            .map(|uri| map.map_str(uri).unwrap())
+            .collect();
+
+        let mut input_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let input_space = input_space.as_space_mut();
+        let mut output_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let output_space = output_space.as_space_mut();
+
+        // writing the input
+        {
+            let mut cursor = SpaceCursor::new(input_space.as_bytes_mut());
+            let mut writer = cursor
+                .write_atom(urids.object)
+                .unwrap()
+                .write_header(ObjectHeader {
+                    id: None,
+                    otype: object_type,
+                })
+                .unwrap();
+            writer
+                .new_property(keys[0], urids.int)
+                .unwrap()
+                .set(1)
+                .unwrap();
+            writer
+                .new_property_with_context(keys[1], context, urids.int)
+                .unwrap()
+                .set(2)
+                .unwrap();
+            writer
+                .new_property(keys[2], urids.string)
+                .unwrap()
+                .append("three")
+                .unwrap();
+        }
+
+        // editing and forwarding
+        {
+            let (header, reader) = unsafe { input_space.read().next_atom() }
+                .unwrap()
+                .read(urids.object)
+                .unwrap();
+
+            let mut cursor = SpaceCursor::new(output_space.as_bytes_mut());
+            let mut writer = cursor
+                .write_atom(urids.object)
+                .unwrap()
+                .write_header(header)
+                .unwrap();
+            for (property, atom) in reader {
+                if property.key == keys[0] {
+                    let value = *atom.read(urids.int).unwrap();
+                    writer
+                        .new_property(property.key, urids.int)
+                        .unwrap()
+                        .set(value * 10)
+                        .unwrap();
+                } else {
+                    writer.forward_property(&property, atom).unwrap();
+                }
+            }
+        }
+
+        // reading the output
+        {
+            let (header, reader) = unsafe { output_space.read().next_atom() }
+                .unwrap()
+                .read(urids.object)
+                .unwrap();
+            assert_eq!(header.otype, object_type);
+
+            let properties: Vec<(PropertyHeader, &UnidentifiedAtom)> = reader.collect();
+            assert_eq!(properties.len(), 3);
+
+            assert_eq!(properties[0].0.key, keys[0]);
+            assert_eq!(*properties[0].1.read(urids.int).unwrap(), 10);
+
+            assert_eq!(properties[1].0.key, keys[1]);
+            assert_eq!(properties[1].0.context, Some(context));
+            assert_eq!(*properties[1].1.read(urids.int).unwrap(), 2);
+
+            assert_eq!(properties[2].0.key, keys[2]);
+            assert_eq!(properties[2].0.context, None);
+            assert_eq!(properties[2].1.read(urids.string).unwrap(), "three");
+        }
+    }
+
+    #[test]
+    fn test_typed_urids() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let otype = TypeUrid::new(map.map_str("urn:my-type").unwrap());
+        let key = KeyUrid::new(map.map_str("urn:my-key").unwrap());
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing
+        {
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = cursor
+                .write_atom(urids.object)
+                .unwrap()
+                .write_header(ObjectHeader::new(None, otype))
+                .unwrap();
+            writer
+                .new_keyed_property(key, urids.int)
+                .unwrap()
+                .set(42)
+                .unwrap();
+        }
+
+        // reading
+        {
+            let (header, mut iter) = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.object)
+                .unwrap();
+            assert_eq!(header.type_urid(), otype);
+            assert!(header.has_type(otype.get()));
+            assert!(!header.has_type(key.get()));
+            assert_eq!(header.id, None);
+
+            let (property, atom) = iter.next().unwrap();
+            assert_eq!(property.key_urid(), key);
+            assert_eq!(*atom.read(urids.int).unwrap(), 42);
+            assert!(iter.next().is_none());
+        }
+    }
+
+    #[test]
+    fn test_property_order() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let object_type = map.map_str("urn:my-type").unwrap();
+        // The keys are mapped in a different order than they are written.
+        let keys: Vec<URID> = ["urn:key-a", "urn:key-b", "urn:key-c", "urn:key-d"]
+            .iter()
+            .map(|uri| map.map_str(uri).unwrap())
+            .collect();
+        let order = [keys[2], keys[0], keys[3], keys[1]];
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing
+        {
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = cursor
+                .write_atom(urids.object)
+                .unwrap()
+                .write_header(ObjectHeader {
+                    id: None,
+                    otype: object_type,
+                })
+                .unwrap();
+            for (i, key) in order.iter().enumerate() {
+                writer
+                    .new_property(*key, urids.int)
+                    .unwrap()
+                    .set(i as i32)
+                    .unwrap();
+            }
+        }
+
+        // reading
+        {
+            let (_, iter) = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.object)
+                .unwrap();
+            let properties: Vec<(URID, i32)> = iter
+                .map(|(header, atom)| (header.key, *atom.read(urids.int).unwrap()))
+                .collect();
+            assert_eq!(
+                properties,
+                [(order[0], 0), (order[1], 1), (order[2], 2), (order[3], 3)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_sorted_object() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let object_type = map.map_str("urn:my-type").unwrap();
+        let keys: Vec<URID> = ["urn:key-a", "urn:key-b", "urn:key-c"]
+            .iter()
+            .map(|uri| map.map_str(uri).unwrap())
+            .collect();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing
+        {
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = cursor
+                .write_atom(urids.object)
+                .unwrap()
+                .write_header(ObjectHeader {
+                    id: None,
+                    otype: object_type,
+                })
+                .unwrap();
+            writer
+                .new_property(keys[2], urids.int)
+                .unwrap()
+                .set(3)
+                .unwrap();
+            writer
+                .new_property(keys[0], urids.int)
+                .unwrap()
+                .set(1)
+                .unwrap();
+            // The string is not padded, since it is the last property.
+            writer
+                .new_property(keys[1], urids.string)
+                .unwrap()
+                .append("b")
+                .unwrap();
+            writer.finish_sorted().unwrap();
+        }
+
+        // reading
+        {
+            let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+            assert_eq!(
+                atom.header().size_of_body(),
+                size_of::<sys::LV2_Atom_Object_Body>()
+                    + 3 * size_of::<sys::LV2_Atom_Property_Body>()
+                    + 3 * 8
+            );
+
+            let (_, iter) = atom.read(urids.object).unwrap();
+            let properties: Vec<(PropertyHeader, &UnidentifiedAtom)> = iter.collect();
+            assert_eq!(properties.len(), 3);
+
+            assert_eq!(properties[0].0.key, keys[0]);
+            assert_eq!(*properties[0].1.read(urids.int).unwrap(), 1);
+            assert_eq!(properties[1].0.key, keys[1]);
+            assert_eq!(properties[1].1.read(urids.string).unwrap(), "b");
+            assert_eq!(properties[2].0.key, keys[2]);
+            assert_eq!(*properties[2].1.read(urids.int).unwrap(), 3);
+        }
+    }
+
     #[test]
     #[allow(clippy::float_cmp)]
     fn test_object() {
@@ -463,5 +1136,243 @@ mod tests {
             assert_eq!(header.key, second_key);
             assert_eq!(*atom.read(urids.float).unwrap(), second_value);
         }
+
+        // getting single properties
+        {
+            let (_, iter) = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.object)
+                .unwrap();
+
+            // The first property is skipped since its key doesn't match.
+            assert_eq!(
+                *iter.get(second_key, urids.float).unwrap().unwrap(),
+                second_value
+            );
+            assert_eq!(
+                *iter.get(first_key, urids.int).unwrap().unwrap(),
+                first_value
+            );
+            // The key matches, but the type doesn't.
+            assert!(matches!(
+                iter.get(first_key, urids.float),
+                Some(Err(
+                    crate::space::error::AtomReadError::AtomUridMismatch { .. }
+                ))
+            ));
+            // There is no property with this key.
+            assert!(iter.get(object_type, urids.int).is_none());
+
+            // Properties the iterator has already yielded are skipped.
+            let mut iter = iter;
+            iter.next().unwrap();
+            assert!(iter.get(first_key, urids.int).is_none());
+            assert!(iter.get(second_key, urids.float).is_some());
+        }
+    }
+
+    #[test]
+    fn test_nested_object() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let outer_type = map.map_str("urn:outer").unwrap();
+        let inner_type = map.map_str("urn:inner").unwrap();
+        let child_key = map.map_str("urn:child").unwrap();
+        let value_key = map.map_str("urn:value").unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing
+        {
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut outer = cursor
+                .write_atom(urids.object)
+                .unwrap()
+                .write_header(ObjectHeader {
+                    id: None,
+                    otype: outer_type,
+                })
+                .unwrap();
+            let mut inner = outer
+                .new_property(child_key, urids.blank)
+                .unwrap()
+                .write_header(ObjectHeader {
+                    id: None,
+                    otype: inner_type,
+                })
+                .unwrap();
+            inner
+                .new_property(value_key, urids.int)
+                .unwrap()
+                .set(42)
+                .unwrap();
+        }
+
+        // reading
+        {
+            let (header, outer) = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.object)
+                .unwrap();
+            assert_eq!(header.otype, outer_type);
+            assert_eq!(outer.depth(), 0);
+
+            let (property, value) = outer.clone().next().unwrap();
+            assert_eq!(property.key, child_key);
+            let (header, inner) = outer.read_object(value, &urids).unwrap();
+            assert_eq!(header.otype, inner_type);
+            assert_eq!(inner.depth(), 1);
+
+            let (property, value) = inner.clone().next().unwrap();
+            assert_eq!(property.key, value_key);
+            assert_eq!(*value.read(urids.int).unwrap(), 42);
+
+            // The integer isn't an object.
+            assert!(matches!(
+                inner.read_object(value, &urids),
+                Err(AtomReadError::AtomUridMismatch { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_object_depth_limit() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let object_type = map.map_str("urn:my-type").unwrap();
+        let child_key = map.map_str("urn:child").unwrap();
+        let depth = MAX_OBJECT_DEPTH + 4;
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(8 * depth);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing a chain of objects that is deeper than allowed
+        {
+            fn write_chain(
+                writer: &mut ObjectWriter,
+                urids: &AtomURIDCollection,
+                child_key: URID,
+                object_type: URID,
+                depth: usize,
+            ) {
+                if depth == 0 {
+                    return;
+                }
+                let mut child = writer
+                    .new_property(child_key, urids.object)
+                    .unwrap()
+                    .write_header(ObjectHeader {
+                        id: None,
+                        otype: object_type,
+                    })
+                    .unwrap();
+                write_chain(&mut child, urids, child_key, object_type, depth - 1);
+            }
+
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = cursor
+                .write_atom(urids.object)
+                .unwrap()
+                .write_header(ObjectHeader {
+                    id: None,
+                    otype: object_type,
+                })
+                .unwrap();
+            write_chain(&mut writer, &urids, child_key, object_type, depth - 1);
+        }
+
+        // reading it recursively
+        {
+            fn read_chain(
+                reader: ObjectReader,
+                urids: &AtomURIDCollection,
+            ) -> Result<usize, AtomReadError> {
+                match reader.clone().next() {
+                    Some((_, value)) => read_chain(reader.read_object(value, urids)?.1, urids),
+                    None => Ok(reader.depth()),
+                }
+            }
+
+            let (_, reader) = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.object)
+                .unwrap();
+            assert_eq!(
+                read_chain(reader, &urids),
+                Err(AtomReadError::InvalidAtomValue {
+                    reading_type_uri: Object::uri(),
+                    error_message: "Objects are nested too deeply",
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_object_diff() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let object_type = map.map_str("urn:my-type").unwrap();
+        let keys: Vec<URID> = ["urn:key-a", "urn:key-b", "urn:key-c", "urn:key-d"]
+            .iter()
+            .map(|uri| map.map_str(uri).unwrap())
+            .collect();
+
+        let write_object =
+            |space: &mut AtomSpace, values: &[(URID, i32)], removed: Option<URID>| {
+                let mut cursor = SpaceCursor::new(space.as_bytes_mut());
+                let mut writer = cursor
+                    .write_atom(urids.object)
+                    .unwrap()
+                    .write_header(ObjectHeader {
+                        id: None,
+                        otype: object_type,
+                    })
+                    .unwrap();
+                for (key, value) in values {
+                    writer
+                        .new_property(*key, urids.int)
+                        .unwrap()
+                        .set(*value)
+                        .unwrap();
+                }
+                if let Some(key) = removed {
+                    writer
+                        .new_property(key, urids.string)
+                        .unwrap()
+                        .append("removed")
+                        .unwrap();
+                }
+            };
+
+        let mut old_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        write_object(
+            old_space.as_space_mut(),
+            &[(keys[0], 1), (keys[1], 2)],
+            Some(keys[3]),
+        );
+        let mut new_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        write_object(
+            new_space.as_space_mut(),
+            &[(keys[0], 1), (keys[1], 3), (keys[2], 4)],
+            None,
+        );
+
+        let (_, old) = unsafe { old_space.as_space().read().next_atom() }
+            .unwrap()
+            .read(urids.object)
+            .unwrap();
+        let (_, new) = unsafe { new_space.as_space().read().next_atom() }
+            .unwrap()
+            .read(urids.object)
+            .unwrap();
+
+        // The second property changed, the third one was added and the fourth one was removed.
+        assert_eq!(vec![keys[1], keys[2], keys[3]], object_diff(&old, &new));
+        assert_eq!(vec![keys[1], keys[3], keys[2]], object_diff(&new, &old));
+        assert!(object_diff(&old, &old).is_empty());
     }
 }
@@ -0,0 +1,166 @@
+//! Path atoms.
+//!
+//! A path atom contains the null-terminated, UTF-8 encoded path of a file. It has the same layout as a [`String`](../string/struct.String.html), but tells the host that the atom refers to a file, which is important for the state extension.
+//!
+//! Reading a path atom returns a [`std::path::Path`](https://doc.rust-lang.org/std/path/struct.Path.html) and writing is done with a writing handle that sets the path.
+//!
+//! # Example
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_atom::prelude::*;
+//! use lv2_atom::atoms::path::PathWriter;
+//!
+//! #[derive(PortCollection)]
+//! struct MyPorts {
+//!     input: InputPort<AtomPort>,
+//!     output: OutputPort<AtomPort>,
+//! }
+//!
+//! fn run(ports: &mut MyPorts, urids: &AtomURIDCollection) {
+//!     let input: &std::path::Path = ports.input.read(urids.path).unwrap();
+//!     let writer: PathWriter = ports.output.write(urids.path).unwrap();
+//!     writer.set(&input.with_extension("bak")).unwrap();
+//! }
+//! ```
+//!
+//! # Specification
+//!
+//! [http://lv2plug.in/ns/ext/atom/atom.html#Path](http://lv2plug.in/ns/ext/atom/atom.html#Path)
+use crate::space::error::{AtomReadError, AtomWriteError};
+use crate::space::*;
+use crate::{Atom, AtomHandle};
+use std::ffi::CStr;
+use urid::*;
+
+/// An atom containing the UTF-8 encoded path of a file.
+///
+/// [See also the module documentation.](index.html)
+pub struct Path;
+
+unsafe impl UriBound for Path {
+    const URI: &'static [u8] = sys::LV2_ATOM__Path;
+}
+
+pub struct PathReadHandle;
+
+impl<'a> AtomHandle<'a> for PathReadHandle {
+    type Handle = &'a std::path::Path;
+}
+
+pub struct PathWriteHandle;
+
+impl<'a> AtomHandle<'a> for PathWriteHandle {
+    type Handle = PathWriter<'a>;
+}
+
+impl Atom for Path {
+    type ReadHandle = PathReadHandle;
+    type WriteHandle = PathWriteHandle;
+
+    unsafe fn read(
+        body: &AtomSpace,
+    ) -> Result<<Self::ReadHandle as AtomHandle<'_>>::Handle, AtomReadError> {
+        let c_str = CStr::from_bytes_with_nul(body.as_bytes()).map_err(|_| {
+            AtomReadError::InvalidAtomValue {
+                reading_type_uri: Self::uri(),
+                error_message: "Path value is not null-terminated",
+            }
+        })?;
+
+        let str = c_str
+            .to_str()
+            .map_err(|_| AtomReadError::InvalidAtomValue {
+                reading_type_uri: Self::uri(),
+                error_message: "Path contents are invalid UTF-8",
+            })?;
+
+        Ok(std::path::Path::new(str))
+    }
+
+    fn write(
+        frame: AtomWriter,
+    ) -> Result<<Self::WriteHandle as AtomHandle>::Handle, AtomWriteError> {
+        Ok(PathWriter {
+            writer: frame.terminated(0),
+        })
+    }
+}
+
+/// Handle to set the path of a path atom.
+pub struct PathWriter<'a> {
+    writer: Terminated<AtomWriter<'a>>,
+}
+
+impl<'a> PathWriter<'a> {
+    /// Write the path to the atom.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the path is not valid UTF-8, if there is not enough
+    /// space in the underlying buffer, or if any other write error occurs.
+    pub fn set(mut self, path: &std::path::Path) -> Result<(), AtomWriteError> {
+        let path = path.to_str().ok_or(AtomWriteError::IllegalOperation {
+            writing_type_uri: Path::uri(),
+            error_message: "Path is not valid UTF-8",
+        })?;
+        self.writer.write_bytes(path.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::space::*;
+    use crate::AtomHeader;
+    use urid::*;
+
+    #[test]
+    fn test_path() {
+        let map = HashURIDMapper::new();
+        let urids = crate::atoms::AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let writer = space.write_atom(urids.path).unwrap();
+            writer.set(std::path::Path::new("/tmp/sample.wav")).unwrap();
+        }
+
+        // verifying
+        {
+            let mut reader = raw_space.read();
+            let string: &sys::LV2_Atom_String = unsafe { reader.next_value() }.unwrap();
+            assert_eq!(string.atom.type_, urids.path);
+            assert_eq!(string.atom.size as usize, "/tmp/sample.wav".len() + 1);
+        }
+
+        // reading
+        {
+            let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+            let path = atom.read(urids.path).unwrap();
+            assert_eq!(std::path::Path::new("/tmp/sample.wav"), path);
+        }
+    }
+
+    #[test]
+    fn test_invalid_path() {
+        let map = HashURIDMapper::new();
+        let urids = crate::atoms::AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        // A path atom with a body that isn't valid UTF-8.
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = AtomWriter::write_new(&mut space, urids.path).unwrap();
+            writer.write_bytes(&[b'/', 0xff, 0xfe, 0]).unwrap();
+        }
+
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        assert!(atom.read(urids.path).is_err());
+    }
+}
@@ -230,4 +230,23 @@ mod tests {
         test_scalar::<Bool>(1);
         test_scalar::<AtomURID>(URID::try_from(1).unwrap());
     }
+
+    #[test]
+    fn test_try_read_scalar() {
+        let map = HashURIDMapper::new();
+        let urids = crate::atoms::AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            space.write_atom(urids.int).unwrap().set(42).unwrap();
+        }
+
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        let i: &i32 = Int::try_read(atom, urids.int).unwrap();
+        assert_eq!(*i, 42);
+        assert!(Long::try_read(atom, urids.long).is_err());
+    }
 }
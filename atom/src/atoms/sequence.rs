@@ -72,7 +72,7 @@ use crate::*;
 use std::marker::PhantomData;
 use sys::LV2_Atom_Event__bindgen_ty_1 as RawTimeStamp;
 pub use unit::*;
-use units::units::Frame;
+use units::units::{Beat, Frame};
 
 #[repr(C, align(8))]
 #[derive(Copy, Clone)]
@@ -97,6 +97,33 @@ impl<'a> AtomHandle<'a> for SequenceWriteHandle {
     type Handle = SequenceHeaderWriter<'a>;
 }
 
+/// The URIDs to read and write sequences with time stamps of the unit `U`.
+///
+/// This collection is used by the [`SequencePort`](../../port/struct.SequencePort.html), which reads and writes sequences without repeating the URID of the sequence and the unit every time.
+pub struct SequenceURIDs<U: SequenceUnit> {
+    /// The URID of the sequence atom type.
+    pub sequence: URID<Sequence>,
+    /// The URID of the time stamp unit.
+    pub unit: URID<U>,
+}
+
+impl<U: SequenceUnit> Clone for SequenceURIDs<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U: SequenceUnit> Copy for SequenceURIDs<U> {}
+
+impl<U: SequenceUnit> URIDCollection for SequenceURIDs<U> {
+    fn from_map<M: Map + ?Sized>(map: &M) -> Option<Self> {
+        Some(Self {
+            sequence: map.map_type()?,
+            unit: map.map_type()?,
+        })
+    }
+}
+
 /// A type-state for the Sequence Reader, that reads the header of a sequence.
 #[derive(Clone)]
 pub struct SequenceHeaderReader<'a> {
@@ -105,6 +132,13 @@ pub struct SequenceHeaderReader<'a> {
 }
 
 impl<'a> SequenceHeaderReader<'a> {
+    /// Returns the URID of the time stamp unit that is declared in the header of the sequence.
+    ///
+    /// This can be used to choose the right unit for [`with_unit`](#method.with_unit). A unit URID of zero means that the unit is unspecified, in which case `None` is returned and the time stamps are measured in frames.
+    pub fn sequence_unit(&self) -> Option<URID> {
+        URID::new(self.header.unit)
+    }
+
     /// Tries to read the sequence as having timestamps of the given type.
     ///
     /// # Errors
@@ -130,6 +164,36 @@ impl<'a> SequenceHeaderReader<'a> {
             })
         }
     }
+
+    /// Reads the sequence with the time stamp unit that is declared in its header.
+    ///
+    /// In contrast to [`with_unit`](#method.with_unit), the unit doesn't have to be known in advance: The returned iterator yields [`TimeStamp`s](enum.TimeStamp.html) decoded with the unit actually stored in the sequence. A unit URID of zero is treated as frames.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an `InvalidUrid` error if the sequence's unit is neither the given frame URID nor the given beat URID.
+    pub fn read_unit_agnostic(
+        self,
+        frame_urid: URID<Frame>,
+        beat_urid: URID<Beat>,
+    ) -> Result<UnitAgnosticSequenceIterator<'a>, AtomReadError> {
+        let unit_type = if self.header.unit == 0 || self.header.unit == frame_urid {
+            SequenceUnitType::Frame
+        } else if self.header.unit == beat_urid {
+            SequenceUnitType::Beat
+        } else {
+            return Err(AtomReadError::InvalidUrid {
+                expected_uri: Frame::uri(),
+                expected_urid: frame_urid.into_general(),
+                found_urid: self.header.unit,
+            });
+        };
+
+        Ok(UnitAgnosticSequenceIterator {
+            reader: self.reader,
+            unit_type,
+        })
+    }
 }
 
 /// A type-state for the Sequence Writer, that writes the header of a sequence.
@@ -204,11 +268,53 @@ impl Atom for Sequence {
 }
 
 /// An iterator over all events in a sequence.
+///
+/// A borrowed iterator can be iterated too: This iterates over a copy of the iterator and leaves the original untouched, which allows scanning a sequence multiple times, e.g. once to count the events and once to process them.
 pub struct SequenceIterator<'a, U: SequenceUnit> {
     reader: SpaceReader<'a>,
     unit_type: PhantomData<U>,
 }
 
+impl<'a, U: SequenceUnit> SequenceIterator<'a, U> {
+    /// Turn this iterator into one that yields [`TimeStamp`s](enum.TimeStamp.html) instead of raw values.
+    ///
+    /// The unit that was passed to [`with_unit`](struct.SequenceHeaderReader.html#method.with_unit) is stamped onto every time stamp, which is useful if the events are passed to code that handles both units.
+    pub fn time_stamped(self) -> impl Iterator<Item = (TimeStamp, &'a UnidentifiedAtom)> {
+        self.map(|(stamp, atom)| (U::time_stamp(stamp), atom))
+    }
+
+    /// Return the time stamp of the next event without consuming it.
+    ///
+    /// If the iteration hasn't started yet, this is the time stamp of the first event, which is useful to make scheduling decisions before handling the events. Returns `None` if there are no more events.
+    pub fn peek_first_timestamp(&self) -> Option<TimeStamp> {
+        let mut reader = self.reader.clone();
+        // SAFETY: The validity of the space's contents is guaranteed by this type.
+        let raw_stamp: &RawTimeStamp = unsafe { reader.next_value() }.ok()?;
+        // The event is only complete if it also contains an atom.
+        unsafe { reader.next_atom() }.ok()?;
+        // SAFETY: The validity of the unit type is guaranteed by this type.
+        Some(U::time_stamp(unsafe { U::convert_from_raw(*raw_stamp) }))
+    }
+}
+
+impl<'a, U: SequenceUnit> Clone for SequenceIterator<'a, U> {
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+            unit_type: PhantomData,
+        }
+    }
+}
+
+impl<'a, U: SequenceUnit> IntoIterator for &SequenceIterator<'a, U> {
+    type Item = (U::Value, &'a UnidentifiedAtom);
+    type IntoIter = SequenceIterator<'a, U>;
+
+    fn into_iter(self) -> SequenceIterator<'a, U> {
+        self.clone()
+    }
+}
+
 impl<'a, U: SequenceUnit> Iterator for SequenceIterator<'a, U> {
     type Item = (U::Value, &'a UnidentifiedAtom);
 
@@ -230,7 +336,55 @@ impl<'a, U: SequenceUnit> Iterator for SequenceIterator<'a, U> {
     }
 }
 
+/// An iterator over all events in a sequence, regardless of the sequence's time stamp unit.
+///
+/// It is created by [`read_unit_agnostic`](struct.SequenceHeaderReader.html#method.read_unit_agnostic).
+pub struct UnitAgnosticSequenceIterator<'a> {
+    reader: SpaceReader<'a>,
+    unit_type: SequenceUnitType,
+}
+
+impl<'a> UnitAgnosticSequenceIterator<'a> {
+    /// Return the unit type of the time stamps in this sequence.
+    pub fn unit_type(&self) -> SequenceUnitType {
+        self.unit_type
+    }
+}
+
+impl<'a> Iterator for UnitAgnosticSequenceIterator<'a> {
+    type Item = (TimeStamp, &'a UnidentifiedAtom);
+
+    fn next(&mut self) -> Option<(TimeStamp, &'a UnidentifiedAtom)> {
+        let unit_type = self.unit_type;
+        self.reader
+            .try_read(|reader| {
+                // SAFETY: The validity of the space's contents is guaranteed by this type.
+                let raw_stamp: &RawTimeStamp = unsafe { reader.next_value()? };
+
+                // SAFETY: The unit type was read from the sequence header.
+                let stamp = unsafe {
+                    match unit_type {
+                        SequenceUnitType::Frame => {
+                            Frame::time_stamp(Frame::convert_from_raw(*raw_stamp))
+                        }
+                        SequenceUnitType::Beat => {
+                            Beat::time_stamp(Beat::convert_from_raw(*raw_stamp))
+                        }
+                    }
+                };
+
+                // SAFETY: The validity of the space's contents is guaranteed by this type.
+                let atom = unsafe { reader.next_atom()? };
+
+                Ok((stamp, atom))
+            })
+            .ok()
+    }
+}
+
 /// The writing handle for sequences.
+///
+/// The sequence header is written when this handle is created. Therefore, a sequence that is dropped without any events is a valid, empty sequence.
 pub struct SequenceWriter<'a, U: SequenceUnit> {
     writer: AtomWriter<'a>,
     last_stamp: Option<U::Value>,
@@ -399,4 +553,355 @@ mod tests {
             assert!(reader.next().is_none());
         }
     }
+
+    #[test]
+    fn test_peek_first_timestamp() {
+        let map = HashURIDMapper::new();
+        let urids: TestURIDCollection = TestURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+        let mut empty_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let empty_space = empty_space.as_space_mut();
+
+        // writing
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = space
+                .write_atom(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.frame)
+                .unwrap();
+            writer
+                .new_event(10, urids.atom.int)
+                .unwrap()
+                .set(1)
+                .unwrap();
+            writer
+                .new_event(20, urids.atom.int)
+                .unwrap()
+                .set(2)
+                .unwrap();
+
+            let mut space = SpaceCursor::new(empty_space.as_bytes_mut());
+            space
+                .write_atom(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.frame)
+                .unwrap();
+        }
+
+        // reading
+        {
+            let header_reader = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.sequence)
+                .unwrap();
+            assert_eq!(
+                header_reader.sequence_unit(),
+                Some(urids.units.frame.into_general())
+            );
+            let mut reader = header_reader.with_unit(urids.units.frame).unwrap();
+            assert_eq!(reader.peek_first_timestamp(), Some(TimeStamp::Frames(10)));
+            // Peeking does not consume the event.
+            assert_eq!(reader.next().unwrap().0, 10);
+            assert_eq!(reader.peek_first_timestamp(), Some(TimeStamp::Frames(20)));
+
+            let reader = unsafe { empty_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.frame)
+                .unwrap();
+            assert_eq!(reader.peek_first_timestamp(), None);
+        }
+    }
+
+    #[test]
+    fn test_empty_sequence() {
+        let map = HashURIDMapper::new();
+        let urids: TestURIDCollection = TestURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            space
+                .write_atom(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.frame)
+                .unwrap();
+        }
+
+        // verifying
+        {
+            let sequence: &sys::LV2_Atom_Sequence =
+                unsafe { raw_space.read().next_value() }.unwrap();
+            assert_eq!(sequence.atom.type_, urids.atom.sequence);
+            assert_eq!(
+                sequence.atom.size as usize,
+                size_of::<sys::LV2_Atom_Sequence_Body>()
+            );
+            assert_eq!(sequence.body.unit, urids.units.frame);
+            assert_eq!(sequence.body.pad, 0);
+        }
+
+        // reading
+        {
+            let mut reader = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.frame)
+                .unwrap();
+            assert!(reader.next().is_none());
+        }
+    }
+
+    #[test]
+    fn test_time_stamp_units() {
+        let map = HashURIDMapper::new();
+        let urids: TestURIDCollection = TestURIDCollection::from_map(&map).unwrap();
+
+        let mut frame_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let frame_space = frame_space.as_space_mut();
+        let mut beat_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let beat_space = beat_space.as_space_mut();
+
+        // writing
+        {
+            let mut space = SpaceCursor::new(frame_space.as_bytes_mut());
+            let mut writer = space
+                .write_atom(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.frame)
+                .unwrap();
+            writer.new_event(3, urids.atom.int).unwrap().set(1).unwrap();
+
+            let mut space = SpaceCursor::new(beat_space.as_bytes_mut());
+            let mut writer = space
+                .write_atom(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.beat)
+                .unwrap();
+            writer
+                .new_event(1.5, urids.atom.int)
+                .unwrap()
+                .set(2)
+                .unwrap();
+        }
+
+        // reading
+        {
+            let (stamp, _) = unsafe { frame_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.frame)
+                .unwrap()
+                .time_stamped()
+                .next()
+                .unwrap();
+            assert_eq!(stamp, TimeStamp::Frames(3));
+            assert_eq!(stamp.unit_type(), SequenceUnitType::Frame);
+            assert_eq!(stamp.as_frames(), Some(3));
+            assert_eq!(stamp.as_beats(), None);
+
+            let (stamp, _) = unsafe { beat_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.beat)
+                .unwrap()
+                .time_stamped()
+                .next()
+                .unwrap();
+            assert_eq!(stamp, TimeStamp::Beats(1.5));
+            assert_eq!(stamp.unit_type(), SequenceUnitType::Beat);
+            assert_eq!(stamp.as_beats(), Some(1.5));
+            assert_eq!(stamp.as_frames(), None);
+
+            // A beat sequence can't be read with frame time stamps and vice versa.
+            assert!(unsafe { beat_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.frame)
+                .is_err());
+            assert!(unsafe { frame_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.beat)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_borrowed_iteration() {
+        let map = HashURIDMapper::new();
+        let urids: TestURIDCollection = TestURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let raw_space = raw_space.as_space_mut();
+
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = space
+                .write_atom(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.frame)
+                .unwrap();
+            for i in 0..3 {
+                writer
+                    .new_event(i, urids.atom.int)
+                    .unwrap()
+                    .set(i as i32)
+                    .unwrap();
+            }
+        }
+
+        let sequence = unsafe { raw_space.read().next_atom() }
+            .unwrap()
+            .read(urids.atom.sequence)
+            .unwrap()
+            .with_unit(urids.units.frame)
+            .unwrap();
+
+        let mut count = 0;
+        for _ in &sequence {
+            count += 1;
+        }
+        assert_eq!(count, 3);
+        assert_eq!((&sequence).into_iter().count(), 3);
+
+        let stamps: Vec<i64> = sequence.map(|(stamp, _)| stamp).collect();
+        assert_eq!(stamps, [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_unit_agnostic_reading() {
+        let map = HashURIDMapper::new();
+        let urids: TestURIDCollection = TestURIDCollection::from_map(&map).unwrap();
+
+        let mut beat_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let beat_space = beat_space.as_space_mut();
+        let mut frame_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let frame_space = frame_space.as_space_mut();
+        let mut other_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let other_space = other_space.as_space_mut();
+
+        // writing
+        {
+            let mut space = SpaceCursor::new(beat_space.as_bytes_mut());
+            let mut writer = space
+                .write_atom(urids.atom.sequence)
+                .unwrap()
+                .with_unit(urids.units.beat)
+                .unwrap();
+            writer
+                .new_event(0.5, urids.atom.int)
+                .unwrap()
+                .set(1)
+                .unwrap();
+            writer
+                .new_event(2.25, urids.atom.int)
+                .unwrap()
+                .set(2)
+                .unwrap();
+
+            let mut space = SpaceCursor::new(frame_space.as_bytes_mut());
+            let mut writer = space
+                .write_atom(urids.atom.sequence)
+                .unwrap()
+                .with_frame_unit()
+                .unwrap();
+            writer.new_event(7, urids.atom.int).unwrap().set(3).unwrap();
+
+            let mut space = SpaceCursor::new(other_space.as_bytes_mut());
+            space
+                .write_atom(urids.atom.sequence)
+                .unwrap()
+                .with_unit_raw::<Frame>(urids.atom.int.get())
+                .unwrap();
+        }
+
+        // reading
+        {
+            let mut iter = unsafe { beat_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.sequence)
+                .unwrap()
+                .read_unit_agnostic(urids.units.frame, urids.units.beat)
+                .unwrap();
+            assert_eq!(iter.unit_type(), SequenceUnitType::Beat);
+
+            let (stamp, atom) = iter.next().unwrap();
+            assert_eq!(stamp, TimeStamp::Beats(0.5));
+            assert_eq!(*atom.read(urids.atom.int).unwrap(), 1);
+
+            let (stamp, atom) = iter.next().unwrap();
+            assert_eq!(stamp, TimeStamp::Beats(2.25));
+            assert_eq!(*atom.read(urids.atom.int).unwrap(), 2);
+
+            assert!(iter.next().is_none());
+
+            let mut iter = unsafe { frame_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.sequence)
+                .unwrap()
+                .read_unit_agnostic(urids.units.frame, urids.units.beat)
+                .unwrap();
+            assert_eq!(iter.unit_type(), SequenceUnitType::Frame);
+
+            let (stamp, atom) = iter.next().unwrap();
+            assert_eq!(stamp, TimeStamp::Frames(7));
+            assert_eq!(*atom.read(urids.atom.int).unwrap(), 3);
+
+            assert!(iter.next().is_none());
+
+            assert!(unsafe { other_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.sequence)
+                .unwrap()
+                .read_unit_agnostic(urids.units.frame, urids.units.beat)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_raw_beat_time_stamp() {
+        let map = HashURIDMapper::new();
+        let urids: TestURIDCollection = TestURIDCollection::from_map(&map).unwrap();
+
+        // A sequence measured in beats, containing one integer event at beat 2.75.
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&32u32.to_ne_bytes());
+        bytes.extend_from_slice(&urids.atom.sequence.get().to_ne_bytes());
+        bytes.extend_from_slice(&urids.units.beat.get().to_ne_bytes());
+        bytes.extend_from_slice(&0u32.to_ne_bytes());
+        bytes.extend_from_slice(&2.75f64.to_ne_bytes());
+        bytes.extend_from_slice(&4u32.to_ne_bytes());
+        bytes.extend_from_slice(&urids.atom.int.get().to_ne_bytes());
+        bytes.extend_from_slice(&42i32.to_ne_bytes());
+        bytes.extend_from_slice(&0u32.to_ne_bytes());
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(bytes.len());
+        let raw_space = raw_space.as_space_mut();
+        raw_space.as_bytes_mut()[..bytes.len()].copy_from_slice(&bytes);
+
+        let mut reader = unsafe { raw_space.read().next_atom() }
+            .unwrap()
+            .read(urids.atom.sequence)
+            .unwrap()
+            .with_unit(urids.units.beat)
+            .unwrap();
+
+        let (stamp, atom) = reader.next().unwrap();
+        assert_eq!(stamp, 2.75);
+        assert_eq!(*atom.read(urids.atom.int).unwrap(), 42);
+        assert!(reader.next().is_none());
+    }
 }
@@ -8,11 +8,51 @@ pub enum SequenceUnitType {
     Frame,
 }
 
+/// A time stamp of a sequence event that knows its unit.
+///
+/// Sequences either measure time in audio frames or in beats. A `TimeStamp` keeps track of the unit it was decoded with, so that it can't be mistaken for a time stamp in the other unit.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+pub enum TimeStamp {
+    /// A time stamp in audio frames.
+    Frames(i64),
+    /// A time stamp in beats.
+    Beats(f64),
+}
+
+impl TimeStamp {
+    /// Return the unit type of the time stamp.
+    pub fn unit_type(self) -> SequenceUnitType {
+        match self {
+            TimeStamp::Frames(_) => SequenceUnitType::Frame,
+            TimeStamp::Beats(_) => SequenceUnitType::Beat,
+        }
+    }
+
+    /// Return the time stamp in frames, or `None` if it is measured in beats.
+    pub fn as_frames(self) -> Option<i64> {
+        match self {
+            TimeStamp::Frames(frames) => Some(frames),
+            TimeStamp::Beats(_) => None,
+        }
+    }
+
+    /// Return the time stamp in beats, or `None` if it is measured in frames.
+    pub fn as_beats(self) -> Option<f64> {
+        match self {
+            TimeStamp::Frames(_) => None,
+            TimeStamp::Beats(beats) => Some(beats),
+        }
+    }
+}
+
 pub trait SequenceUnit: UriBound + private::Sealed {
     type Value: Copy + PartialEq + PartialOrd + 'static;
 
     const TYPE: SequenceUnitType;
 
+    /// Create a time stamp of this unit from the given value.
+    fn time_stamp(value: Self::Value) -> TimeStamp;
+
     #[doc(hidden)]
     unsafe fn convert_from_raw(raw: sys::LV2_Atom_Event__bindgen_ty_1) -> Self::Value;
 
@@ -24,6 +64,11 @@ impl SequenceUnit for Beat {
     type Value = f64;
     const TYPE: SequenceUnitType = SequenceUnitType::Beat;
 
+    #[inline]
+    fn time_stamp(value: f64) -> TimeStamp {
+        TimeStamp::Beats(value)
+    }
+
     #[inline]
     unsafe fn convert_from_raw(raw: LV2_Atom_Event__bindgen_ty_1) -> Self::Value {
         raw.beats
@@ -39,6 +84,11 @@ impl SequenceUnit for Frame {
     type Value = i64;
     const TYPE: SequenceUnitType = SequenceUnitType::Frame;
 
+    #[inline]
+    fn time_stamp(value: i64) -> TimeStamp {
+        TimeStamp::Frames(value)
+    }
+
     #[inline]
     unsafe fn convert_from_raw(raw: LV2_Atom_Event__bindgen_ty_1) -> Self::Value {
         raw.frames
@@ -30,7 +30,7 @@
 use crate::prelude::*;
 use crate::space::error::{AtomReadError, AtomWriteError};
 use crate::space::*;
-use crate::AtomHandle;
+use crate::{AtomHandle, AtomHeader};
 use std::ffi::CStr;
 use urid::*;
 
@@ -45,12 +45,42 @@ unsafe impl UriBound for Literal {
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 /// The type or language URID of a literal.
+///
+/// A literal either carries a language tag, like `"hello"@en`, or a datatype, like `"42"^^xsd:int`, but never both. When a literal is read, the variant tells which of them it carries.
 pub enum LiteralInfo {
     Language(URID),
     Datatype(URID),
 }
 
 impl LiteralInfo {
+    /// Create the info of a literal with the given language tag.
+    ///
+    /// The URID is the mapped URI of the language, for example `http://lexvo.org/id/iso639-1/en` for English.
+    pub fn language<T: ?Sized>(urid: URID<T>) -> Self {
+        LiteralInfo::Language(urid.into_general())
+    }
+
+    /// Create the info of a literal with the given datatype.
+    pub fn datatype<T: ?Sized>(urid: URID<T>) -> Self {
+        LiteralInfo::Datatype(urid.into_general())
+    }
+
+    /// Return the URID of the language tag, or `None` if the literal has a datatype.
+    pub fn language_urid(&self) -> Option<URID> {
+        match self {
+            LiteralInfo::Language(urid) => Some(*urid),
+            LiteralInfo::Datatype(_) => None,
+        }
+    }
+
+    /// Return the URID of the datatype, or `None` if the literal has a language tag.
+    pub fn datatype_urid(&self) -> Option<URID> {
+        match self {
+            LiteralInfo::Language(_) => None,
+            LiteralInfo::Datatype(urid) => Some(*urid),
+        }
+    }
+
     fn try_from_raw(header: &sys::LV2_Atom_Literal_Body) -> Result<Self, &'static str> {
         match (URID::new(header.lang), URID::new(header.datatype)) {
             (Some(urid), _) => Ok(LiteralInfo::Language(urid)),
@@ -218,8 +248,28 @@ impl<'a> StringWriter<'a> {
     }
 }
 
+/// Return the size of a [`String`](struct.String.html) atom containing the given string, in bytes.
+///
+/// The size includes the atom header, the null terminator and the padding to the next multiple of 8 bytes, which is the amount of space a writer needs to write the atom. This can be used to check whether a string fits into a buffer before it is written.
+pub fn string_atom_size(string: &str) -> usize {
+    crate::util::padded_size::<AtomHeader>(::core::mem::size_of::<AtomHeader>() + string.len() + 1)
+}
+
+/// Return the size of a [`Literal`](struct.Literal.html) atom containing the given string, in bytes.
+///
+/// Like [`string_atom_size`](fn.string_atom_size.html), the size includes the atom header, the literal's info header, the null terminator and the padding.
+pub fn literal_atom_size(string: &str) -> usize {
+    crate::util::padded_size::<AtomHeader>(
+        ::core::mem::size_of::<AtomHeader>()
+            + ::core::mem::size_of::<sys::LV2_Atom_Literal_Body>()
+            + string.len()
+            + 1,
+    )
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::atoms::string::{literal_atom_size, string_atom_size};
     use crate::prelude::*;
     use crate::space::*;
     use crate::AtomHeader;
@@ -303,6 +353,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_literal_info() {
+        let map = HashURIDMapper::new();
+        let urids: TestURIDs = TestURIDs::from_map(&map).unwrap();
+
+        for info in [
+            LiteralInfo::language(urids.german),
+            LiteralInfo::datatype(urids.atom.int),
+        ] {
+            let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(8);
+            let raw_space = raw_space.as_space_mut();
+            SpaceCursor::new(raw_space.as_bytes_mut())
+                .write_atom(urids.atom.literal)
+                .unwrap()
+                .write_info(info)
+                .unwrap()
+                .append("42")
+                .unwrap();
+
+            let (read_info, text) = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.atom.literal)
+                .unwrap();
+            assert_eq!(read_info, info);
+            assert_eq!(text, "42");
+        }
+
+        let language = LiteralInfo::language(urids.german);
+        assert_eq!(language, LiteralInfo::Language(urids.german.into_general()));
+        assert_eq!(language.language_urid(), Some(urids.german.into_general()));
+        assert_eq!(language.datatype_urid(), None);
+
+        let datatype = LiteralInfo::datatype(urids.atom.int);
+        assert_eq!(
+            datatype,
+            LiteralInfo::Datatype(urids.atom.int.into_general())
+        );
+        assert_eq!(datatype.language_urid(), None);
+        assert_eq!(
+            datatype.datatype_urid(),
+            Some(urids.atom.int.into_general())
+        );
+    }
+
     #[test]
     fn test_string() {
         let map = HashURIDMapper::new();
@@ -341,4 +435,63 @@ mod tests {
             assert_eq!(string, SAMPLE0.to_owned() + SAMPLE1);
         }
     }
+
+    #[test]
+    fn test_try_read_string() {
+        let map = HashURIDMapper::new();
+        let urids = crate::atoms::AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            space
+                .write_atom(urids.string)
+                .unwrap()
+                .append(SAMPLE0)
+                .unwrap();
+        }
+
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        let string: &str = String::try_read(atom, urids.string).unwrap();
+        assert_eq!(string, SAMPLE0);
+        assert!(Literal::try_read(atom, urids.literal).is_err());
+    }
+
+    #[test]
+    fn test_atom_size() {
+        let map = HashURIDMapper::new();
+        let urids: TestURIDs = TestURIDs::from_map(&map).unwrap();
+
+        for (string, string_size, literal_size) in
+            [("", 16, 24), ("1234567", 16, 24), ("12345678", 24, 32)].iter()
+        {
+            assert_eq!(string_atom_size(string), *string_size);
+            assert_eq!(literal_atom_size(string), *literal_size);
+
+            // The atoms exactly fit into buffers of the computed size.
+            let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(*string_size / 8);
+            {
+                let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+                let mut writer = space.write_atom(urids.atom.string).unwrap();
+                writer.append(string).unwrap();
+            }
+            let atom = unsafe { raw_space.as_space().read().next_atom() }.unwrap();
+            assert_eq!(atom.read(urids.atom.string).unwrap(), *string);
+
+            let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(*literal_size / 8);
+            {
+                let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+                let mut writer = space
+                    .write_atom(urids.atom.literal)
+                    .unwrap()
+                    .write_info(LiteralInfo::Language(urids.german.into_general()))
+                    .unwrap();
+                writer.append(string).unwrap();
+            }
+            let atom = unsafe { raw_space.as_space().read().next_atom() }.unwrap();
+            assert_eq!(atom.read(urids.atom.literal).unwrap().1, *string);
+        }
+    }
 }
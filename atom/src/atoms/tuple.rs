@@ -80,6 +80,37 @@ pub struct TupleIterator<'a> {
     reader: SpaceReader<'a>,
 }
 
+impl<'a> TupleIterator<'a> {
+    /// Read the next atom of the tuple as an atom of the given type.
+    ///
+    /// This is useful to decode tuples with a fixed shape, element by element:
+    ///
+    /// ```
+    /// # use lv2_atom::prelude::*;
+    /// # use lv2_atom::atoms::tuple::TupleIterator;
+    /// # use lv2_atom::space::error::AtomReadError;
+    /// fn decode(mut tuple: TupleIterator, urids: &AtomURIDCollection) -> Result<(i32, f32), AtomReadError> {
+    ///     let a = *tuple.next_as(urids.int)?;
+    ///     let b = *tuple.next_as(urids.float)?;
+    ///     Ok((a, b))
+    /// }
+    /// ```
+    ///
+    /// The iterator advances even if the atom can't be read as the given type.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if there is no atom left in the tuple, if the next atom isn't of the given type, or if any other read error occurs.
+    pub fn next_as<A: Atom>(
+        &mut self,
+        urid: URID<A>,
+    ) -> Result<<A::ReadHandle as AtomHandle<'a>>::Handle, AtomReadError> {
+        // SAFETY: the validity of the given space is guaranteed by this type.
+        let atom = unsafe { self.reader.next_atom() }?;
+        atom.read(urid)
+    }
+}
+
 impl<'a> Iterator for TupleIterator<'a> {
     type Item = &'a UnidentifiedAtom;
 
@@ -112,6 +143,7 @@ impl<'a> TupleWriter<'a> {
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
+    use crate::space::error::AtomReadError;
     use crate::space::*;
     use crate::AtomHeader;
     use std::mem::size_of;
@@ -187,5 +219,34 @@ mod tests {
             );
             assert_eq!(*items[1].read(urids.int).unwrap(), 42);
         }
+
+        // reading with expected types
+        {
+            let mut tuple = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.tuple)
+                .unwrap();
+            assert_eq!(
+                tuple
+                    .next_as(urids.vector)
+                    .unwrap()
+                    .of_type(urids.int)
+                    .unwrap(),
+                [17; 9]
+            );
+            assert!(matches!(
+                tuple.next_as(urids.float),
+                Err(AtomReadError::AtomUridMismatch { .. })
+            ));
+            assert!(tuple.next_as(urids.int).is_err());
+
+            let mut tuple = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.tuple)
+                .unwrap();
+            tuple.next_as(urids.vector).unwrap();
+            assert_eq!(*tuple.next_as(urids.int).unwrap(), 42);
+            assert!(tuple.next_as(urids.int).is_err());
+        }
     }
 }
@@ -101,6 +101,21 @@ impl<'a> VectorReader<'a> {
         Ok(unsafe { self.reader.as_slice() }?)
     }
 
+    /// Attempts to read the vector as containing a given atom type, and returns an iterator over its elements.
+    ///
+    /// This is a shorthand for [`of_type`](#method.of_type) for the common case of looping over the elements.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the type or size of the atoms contained do not match the
+    /// vector being currently read, e.g. when trying to read a vector of floats as integers.
+    pub fn iter_typed<C: ScalarAtom>(
+        self,
+        atom_type: URID<C>,
+    ) -> Result<std::slice::Iter<'a, C::InternalType>, AtomReadError> {
+        self.of_type(atom_type).map(|slice| slice.iter())
+    }
+
     /// Returns the length, i.e. number of elements in the vector, without knowing their type.
     ///
     /// This can be figured out thanks to the `child_size` attribute in a vector atom header.
@@ -149,6 +164,39 @@ impl<'a> VectorTypeWriter<'a> {
             type_: PhantomData,
         })
     }
+
+    /// Initializes the vector with a child type that is only known at runtime.
+    ///
+    /// Since the child type isn't known at compile time, the returned writer only accepts raw element bytes, and it is up to the caller to make sure that they are valid bodies of the child type.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the child size is zero, if there is not enough space in
+    /// the underlying buffer, or if any other write error occurs.
+    pub fn of_raw_type(
+        mut self,
+        child_type: URID,
+        child_size: u32,
+    ) -> Result<RawVectorWriter<'a>, AtomWriteError> {
+        if child_size == 0 {
+            return Err(AtomWriteError::IllegalOperation {
+                writing_type_uri: Vector::uri(),
+                error_message: "The child size of a vector may not be zero",
+            });
+        }
+
+        let body = sys::LV2_Atom_Vector_Body {
+            child_type: child_type.get(),
+            child_size,
+        };
+
+        self.writer.write_value(body)?;
+
+        Ok(RawVectorWriter {
+            writer: self.writer,
+            child_size: child_size as usize,
+        })
+    }
 }
 
 impl Atom for Vector {
@@ -220,11 +268,83 @@ impl<'a, A: ScalarAtom> VectorWriter<'a, A> {
     ) -> Result<&mut [A::InternalType], AtomWriteError> {
         self.writer.write_values(data)
     }
+
+    /// Append all elements of an iterator to the vector.
+    ///
+    /// The elements are written one after another, without collecting them first. Returns the number of appended elements.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if there is not enough space in the underlying buffer,
+    /// or if any other write error occurs. In this case, the elements that were written before the
+    /// error remain in the vector and the vector stays valid.
+    pub fn append_iter<I: IntoIterator<Item = A::InternalType>>(
+        &mut self,
+        iter: I,
+    ) -> Result<usize, AtomWriteError> {
+        let mut count = 0;
+        for child in iter {
+            self.push(child)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Handle to append raw elements to a vector whose child type is only known at runtime.
+///
+/// This writer is created by [`VectorTypeWriter::of_raw_type`](struct.VectorTypeWriter.html#method.of_raw_type).
+pub struct RawVectorWriter<'a> {
+    writer: AtomWriter<'a>,
+    child_size: usize,
+}
+
+impl<'a> RawVectorWriter<'a> {
+    /// Push the bytes of a single element to the vector.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the length of the element doesn't match the child size of
+    /// the vector, if there is not enough space in the underlying buffer, or if any other write error
+    /// occurs.
+    pub fn push_bytes(&mut self, element: &[u8]) -> Result<&mut [u8], AtomWriteError> {
+        if element.len() != self.child_size {
+            return Err(AtomWriteError::IllegalOperation {
+                writing_type_uri: Vector::uri(),
+                error_message: "Element size does not match the child size of the vector",
+            });
+        }
+        self.writer.write_bytes(element)
+    }
+
+    /// Append the bytes of multiple elements to the vector.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the length of the data isn't a multiple of the child size
+    /// of the vector, if there is not enough space in the underlying buffer, or if any other write
+    /// error occurs.
+    pub fn append_bytes(&mut self, elements: &[u8]) -> Result<&mut [u8], AtomWriteError> {
+        if !elements.len().is_multiple_of(self.child_size) {
+            return Err(AtomWriteError::IllegalOperation {
+                writing_type_uri: Vector::uri(),
+                error_message: "Data size is not a multiple of the child size of the vector",
+            });
+        }
+        self.writer.write_bytes(elements)
+    }
+
+    /// The size of a single element, in bytes.
+    #[inline]
+    pub fn child_size(&self) -> usize {
+        self.child_size
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::atoms::AtomURIDCollection;
+    use crate::space::error::{AtomReadError, AtomWriteError};
     use crate::space::*;
     use crate::AtomHeader;
     use std::mem::size_of;
@@ -285,4 +405,138 @@ mod tests {
             assert_eq!(children[children.len() - 1], 1);
         }
     }
+
+    #[test]
+    fn test_raw_vector() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = space
+                .write_atom(urids.vector)
+                .unwrap()
+                .of_raw_type(urids.float.into_general(), 4)
+                .unwrap();
+
+            writer.push_bytes(&1.5f32.to_ne_bytes()).unwrap();
+            let mut data = Vec::new();
+            data.extend_from_slice(&2.5f32.to_ne_bytes());
+            data.extend_from_slice(&(-3.0f32).to_ne_bytes());
+            writer.append_bytes(&data).unwrap();
+
+            // Elements of the wrong size are rejected.
+            assert!(writer.push_bytes(&1.0f64.to_ne_bytes()).is_err());
+            assert!(writer.append_bytes(&[0; 6]).is_err());
+        }
+
+        // reading
+        {
+            let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+            let children: &[f32] = atom
+                .read(urids.vector)
+                .unwrap()
+                .of_type(urids.float)
+                .unwrap();
+
+            assert_eq!(children, &[1.5, 2.5, -3.0]);
+
+            let iter = atom
+                .read(urids.vector)
+                .unwrap()
+                .iter_typed(urids.float)
+                .unwrap();
+            assert_eq!(vec![1.5, 2.5, -3.0], iter.copied().collect::<Vec<f32>>());
+
+            // A float vector can not be reinterpreted as an int vector.
+            assert!(matches!(
+                atom.read(urids.vector).unwrap().iter_typed(urids.int),
+                Err(AtomReadError::AtomUridMismatch { .. })
+            ));
+        }
+
+        // A child size of zero is rejected.
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            assert!(space
+                .write_atom(urids.vector)
+                .unwrap()
+                .of_raw_type(urids.float.into_general(), 0)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_append_iter() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(4);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = space
+                .write_atom(urids.vector)
+                .unwrap()
+                .of_type(urids.int)
+                .unwrap();
+
+            assert_eq!(3, writer.append_iter((0..3).map(|i| i * 2)).unwrap());
+
+            // The space runs out mid-iteration.
+            assert!(writer.append_iter(10..100).is_err());
+        }
+
+        // reading
+        {
+            let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+            assert_eq!(atom.header().size_of_body(), 32 - size_of::<AtomHeader>());
+
+            let children: &[i32] = atom.read(urids.vector).unwrap().of_type(urids.int).unwrap();
+            assert_eq!(children, &[0, 2, 4, 10]);
+        }
+    }
+
+    #[test]
+    fn test_vector_out_of_space() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(4);
+        let raw_space = raw_space.as_space_mut();
+
+        let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+        let mut writer = space
+            .write_atom(urids.vector)
+            .unwrap()
+            .of_type(urids.int)
+            .unwrap();
+        writer.push(1).unwrap();
+
+        // 16 bytes of headers and one child are used, 12 bytes are left.
+        let error = writer.append(&[2; 5]).err().unwrap();
+        assert_eq!(
+            error,
+            AtomWriteError::OutOfSpace {
+                used: 20,
+                capacity: 32,
+                requested: 20,
+            }
+        );
+        assert_eq!(error.needed(), Some(40));
+        assert_eq!(error.available(), Some(12));
+
+        let error = AtomWriteError::RewindBeyondAllocated {
+            allocated: 0,
+            requested: 1,
+        };
+        assert_eq!(error.needed(), None);
+        assert_eq!(error.available(), None);
+    }
 }
@@ -0,0 +1,330 @@
+//! Reading atoms that were serialized with a different byte order.
+//!
+//! Atoms are always stored in the byte order of the host. If atoms are written to a file and read on another machine, for example as a part of a plugin's state, the byte order of the reading machine may differ from the one of the writing machine. The [`from_bytes_endian`](fn.from_bytes_endian.html) function copies such an atom into a buffer and converts it to the host's byte order, so that it can be read as usual.
+//!
+//! # Example
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_atom::endian::{from_bytes_endian, Endianness};
+//! use urid::*;
+//!
+//! let map = HashURIDMapper::new();
+//! let urids: AtomURIDCollection = map.populate_collection().unwrap();
+//!
+//! // An int atom, serialized on a big-endian machine.
+//! let mut bytes = Vec::new();
+//! bytes.extend_from_slice(&4u32.to_be_bytes());
+//! bytes.extend_from_slice(&urids.int.get().to_be_bytes());
+//! bytes.extend_from_slice(&42i32.to_be_bytes());
+//! bytes.extend_from_slice(&[0; 4]);
+//!
+//! let buffer = from_bytes_endian(&bytes, Endianness::Big, &urids).unwrap();
+//! let atom = unsafe { buffer.as_space().read().next_atom() }.unwrap();
+//! assert_eq!(42, *atom.read(urids.int).unwrap());
+//! ```
+use crate::atoms::object::{Object, Property};
+use crate::atoms::sequence::Sequence;
+use crate::atoms::tuple::Tuple;
+use crate::atoms::vector::Vector;
+use crate::atoms::AtomURIDCollection;
+use crate::space::error::AtomReadError;
+use crate::space::AlignedVec;
+use crate::util::padded_size;
+use crate::AtomHeader;
+use std::mem::size_of;
+use urid::{Uri, UriBound};
+
+/// The byte order of serialized atoms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    /// The least significant byte comes first.
+    Little,
+    /// The most significant byte comes first.
+    Big,
+}
+
+impl Endianness {
+    /// Return the byte order of the host.
+    pub fn native() -> Self {
+        if cfg!(target_endian = "big") {
+            Endianness::Big
+        } else {
+            Endianness::Little
+        }
+    }
+}
+
+/// The maximal nesting depth of containers converted by [`from_bytes_endian`](fn.from_bytes_endian.html).
+///
+/// The top-level atom has a depth of 0. Properties count as a level of their own, so an object nested in the property of another object is two levels deeper than its parent.
+pub const MAX_ENDIAN_DEPTH: usize = 64;
+
+/// Copy an atom with the given byte order into a buffer with the host's byte order.
+///
+/// The byte order of the headers of all atoms and of the bodies of all standard atom types is converted. Containers like tuples, objects, sequences and vectors are converted recursively. Strings and chunks are copied verbatim, and so are the bodies of atom types that aren't included in the URID collection, since their layout is unknown.
+///
+/// Note that the URIDs in the buffer have to be mapped by the same mapper as the URID collection, which usually means that they have to be translated first if the atom was written by another host.
+///
+/// # Errors
+///
+/// Returns an error if the size of an atom exceeds the given bytes, or if containers are nested deeper than [`MAX_ENDIAN_DEPTH`](constant.MAX_ENDIAN_DEPTH.html).
+pub fn from_bytes_endian(
+    bytes: &[u8],
+    endianness: Endianness,
+    urids: &AtomURIDCollection,
+) -> Result<AlignedVec<AtomHeader>, AtomReadError> {
+    let mut buffer =
+        AlignedVec::<AtomHeader>::new_with_capacity(padded_size::<AtomHeader>(bytes.len()) / 8);
+    buffer.as_bytes_mut()[..bytes.len()].copy_from_slice(bytes);
+
+    if endianness != Endianness::native() {
+        swap_atom(&mut buffer.as_bytes_mut()[..bytes.len()], urids, 0)?;
+    }
+
+    Ok(buffer)
+}
+
+fn get_mut(bytes: &mut [u8], start: usize, length: usize) -> Result<&mut [u8], AtomReadError> {
+    let available = bytes.len();
+    bytes
+        .get_mut(start..start + length)
+        .ok_or(AtomReadError::ReadingOutOfBounds {
+            available,
+            requested: start + length,
+        })
+}
+
+fn swap(bytes: &mut [u8], start: usize, length: usize) -> Result<(), AtomReadError> {
+    get_mut(bytes, start, length)?.reverse();
+    Ok(())
+}
+
+fn read_u32(bytes: &[u8], start: usize) -> u32 {
+    let mut value = [0; 4];
+    value.copy_from_slice(&bytes[start..start + 4]);
+    u32::from_ne_bytes(value)
+}
+
+/// Return the depth of the children of a container, or an error if they would be nested too deeply.
+fn descend(depth: usize, container_uri: &'static Uri) -> Result<usize, AtomReadError> {
+    if depth >= MAX_ENDIAN_DEPTH {
+        Err(AtomReadError::InvalidAtomValue {
+            reading_type_uri: container_uri,
+            error_message: "Atoms are nested too deeply",
+        })
+    } else {
+        Ok(depth + 1)
+    }
+}
+
+/// Swap the header and the body of the atom at the start of `bytes` and return the unpadded size of the atom.
+fn swap_atom(
+    bytes: &mut [u8],
+    urids: &AtomURIDCollection,
+    depth: usize,
+) -> Result<usize, AtomReadError> {
+    swap(bytes, 0, 4)?;
+    swap(bytes, 4, 4)?;
+    let body_size = read_u32(bytes, 0) as usize;
+    let atom_type = read_u32(bytes, 4);
+
+    let body = get_mut(bytes, size_of::<AtomHeader>(), body_size)?;
+    swap_body(atom_type, body, urids, depth)?;
+
+    Ok(size_of::<AtomHeader>() + body_size)
+}
+
+fn swap_body(
+    atom_type: u32,
+    body: &mut [u8],
+    urids: &AtomURIDCollection,
+    depth: usize,
+) -> Result<(), AtomReadError> {
+    if atom_type == urids.int
+        || atom_type == urids.float
+        || atom_type == urids.bool
+        || atom_type == urids.urid
+    {
+        swap(body, 0, 4)
+    } else if atom_type == urids.long || atom_type == urids.double {
+        swap(body, 0, 8)
+    } else if atom_type == urids.literal {
+        swap(body, 0, 4)?;
+        swap(body, 4, 4)
+    } else if atom_type == urids.vector {
+        swap(body, 0, 4)?;
+        swap(body, 4, 4)?;
+        let child_size = read_u32(body, 0) as usize;
+        let child_type = read_u32(body, 4);
+        if child_size == 4 || child_size == 8 {
+            let depth = descend(depth, Vector::uri())?;
+            let mut offset = 8;
+            while offset + child_size <= body.len() {
+                swap_body(
+                    child_type,
+                    &mut body[offset..offset + child_size],
+                    urids,
+                    depth,
+                )?;
+                offset += child_size;
+            }
+        }
+        Ok(())
+    } else if atom_type == urids.tuple {
+        let depth = descend(depth, Tuple::uri())?;
+        let mut offset = 0;
+        while offset < body.len() {
+            offset += padded_size::<AtomHeader>(swap_atom(&mut body[offset..], urids, depth)?);
+        }
+        Ok(())
+    } else if atom_type == urids.object || atom_type == urids.blank {
+        swap(body, 0, 4)?;
+        swap(body, 4, 4)?;
+        let depth = descend(depth, Object::uri())?;
+        let mut offset = 8;
+        while offset < body.len() {
+            offset += padded_size::<AtomHeader>(swap_property(&mut body[offset..], urids, depth)?);
+        }
+        Ok(())
+    } else if atom_type == urids.property {
+        swap_property(body, urids, depth).map(|_| ())
+    } else if atom_type == urids.sequence {
+        swap(body, 0, 4)?;
+        swap(body, 4, 4)?;
+        let depth = descend(depth, Sequence::uri())?;
+        let mut offset = 8;
+        while offset < body.len() {
+            // Frame and beat time stamps are both eight bytes long.
+            swap(body, offset, 8)?;
+            offset += 8;
+            offset += padded_size::<AtomHeader>(swap_atom(&mut body[offset..], urids, depth)?);
+        }
+        Ok(())
+    } else {
+        Ok(())
+    }
+}
+
+/// Swap the key, the context and the value of a property and return the unpadded size of the property.
+fn swap_property(
+    bytes: &mut [u8],
+    urids: &AtomURIDCollection,
+    depth: usize,
+) -> Result<usize, AtomReadError> {
+    swap(bytes, 0, 4)?;
+    swap(bytes, 4, 4)?;
+    let depth = descend(depth, Property::uri())?;
+    Ok(8 + swap_atom(&mut bytes[8..], urids, depth)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::endian::*;
+    use crate::prelude::*;
+    use urid::*;
+
+    fn foreign() -> Endianness {
+        match Endianness::native() {
+            Endianness::Little => Endianness::Big,
+            Endianness::Big => Endianness::Little,
+        }
+    }
+
+    fn to_foreign(value: &[u8]) -> Vec<u8> {
+        value.iter().rev().copied().collect()
+    }
+
+    #[test]
+    fn test_big_endian_int() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&4u32.to_be_bytes());
+        bytes.extend_from_slice(&urids.int.get().to_be_bytes());
+        bytes.extend_from_slice(&0x0102_0304i32.to_be_bytes());
+        bytes.extend_from_slice(&[0; 4]);
+
+        let buffer = from_bytes_endian(&bytes, Endianness::Big, &urids).unwrap();
+        let atom = unsafe { buffer.as_space().read().next_atom() }.unwrap();
+        assert_eq!(0x0102_0304, *atom.read(urids.int).unwrap());
+
+        // Atoms in the host's byte order are copied verbatim.
+        let buffer = from_bytes_endian(buffer.as_bytes(), Endianness::native(), &urids).unwrap();
+        let atom = unsafe { buffer.as_space().read().next_atom() }.unwrap();
+        assert_eq!(0x0102_0304, *atom.read(urids.int).unwrap());
+    }
+
+    #[test]
+    fn test_foreign_tuple() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+
+        // A tuple with a long and a string.
+        let mut bytes = Vec::new();
+        bytes.extend(to_foreign(&32u32.to_ne_bytes()));
+        bytes.extend(to_foreign(&urids.tuple.get().to_ne_bytes()));
+        bytes.extend(to_foreign(&8u32.to_ne_bytes()));
+        bytes.extend(to_foreign(&urids.long.get().to_ne_bytes()));
+        bytes.extend(to_foreign(&(-17i64).to_ne_bytes()));
+        bytes.extend(to_foreign(&3u32.to_ne_bytes()));
+        bytes.extend(to_foreign(&urids.string.get().to_ne_bytes()));
+        bytes.extend_from_slice(b"ab\0\0\0\0\0\0");
+
+        let buffer = from_bytes_endian(&bytes, foreign(), &urids).unwrap();
+        let tuple = unsafe { buffer.as_space().read().next_atom() }
+            .unwrap()
+            .read(urids.tuple)
+            .unwrap();
+        let atoms: Vec<&UnidentifiedAtom> = tuple.collect();
+        assert_eq!(2, atoms.len());
+        assert_eq!(-17, *atoms[0].read(urids.long).unwrap());
+        assert_eq!("ab", atoms[1].read(urids.string).unwrap());
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+
+        let mut bytes = Vec::new();
+        bytes.extend(to_foreign(&16u32.to_ne_bytes()));
+        bytes.extend(to_foreign(&urids.int.get().to_ne_bytes()));
+        bytes.extend_from_slice(&[0; 8]);
+
+        assert!(from_bytes_endian(&bytes, foreign(), &urids).is_err());
+    }
+
+    #[test]
+    fn test_nesting_depth() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+
+        // An int, wrapped in the given number of tuples.
+        let nested = |tuples: usize| {
+            let mut bytes = Vec::new();
+            bytes.extend(to_foreign(&4u32.to_ne_bytes()));
+            bytes.extend(to_foreign(&urids.int.get().to_ne_bytes()));
+            bytes.extend(to_foreign(&42i32.to_ne_bytes()));
+            bytes.extend_from_slice(&[0; 4]);
+            for _ in 0..tuples {
+                let mut tuple = Vec::new();
+                tuple.extend(to_foreign(&(bytes.len() as u32).to_ne_bytes()));
+                tuple.extend(to_foreign(&urids.tuple.get().to_ne_bytes()));
+                tuple.extend(bytes);
+                bytes = tuple;
+            }
+            bytes
+        };
+
+        let buffer = from_bytes_endian(&nested(MAX_ENDIAN_DEPTH), foreign(), &urids).unwrap();
+        let mut atom = unsafe { buffer.as_space().read().next_atom() }.unwrap();
+        while let Ok(mut tuple) = atom.read(urids.tuple) {
+            atom = tuple.next().unwrap();
+        }
+        assert_eq!(42, *atom.read(urids.int).unwrap());
+
+        assert!(from_bytes_endian(&nested(MAX_ENDIAN_DEPTH + 1), foreign(), &urids).is_err());
+    }
+}
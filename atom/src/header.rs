@@ -51,6 +51,11 @@ impl AtomHeader {
         URID::new(self.inner.type_).expect("Invalid header URID type.")
     }
 
+    #[inline]
+    pub(crate) fn try_urid(self) -> Option<URID> {
+        URID::new(self.inner.type_)
+    }
+
     #[inline]
     pub(crate) fn check_urid<A: Atom>(self, other: URID<A>) -> Result<(), AtomReadError> {
         if other == self.urid() {
@@ -74,13 +74,16 @@ use space::*;
 use urid::*;
 
 pub mod atoms;
+pub mod endian;
 mod header;
+mod owned;
 #[cfg(feature = "lv2-core")]
 pub mod port;
 pub mod space;
 
 mod unidentified;
 pub(crate) mod util;
+pub use owned::OwnedAtom;
 pub use unidentified::UnidentifiedAtom;
 
 /// Prelude of `lv2_atom` for wildcard usage.
@@ -88,6 +91,7 @@ pub mod prelude {
     pub use atoms::{
         chunk::Chunk,
         object::{Object, ObjectHeader, PropertyHeader},
+        path::Path,
         scalar::{AtomURID, Bool, Double, Float, Int, Long},
         sequence::Sequence,
         string::{Literal, LiteralInfo, String},
@@ -96,10 +100,10 @@ pub mod prelude {
     };
 
     #[cfg(feature = "lv2-core")]
-    pub use port::AtomPort;
+    pub use port::{AtomPort, SequencePort};
 
     use crate::*;
-    pub use crate::{atoms::AtomURIDCollection, Atom, UnidentifiedAtom};
+    pub use crate::{atoms::AtomURIDCollection, Atom, OwnedAtom, UnidentifiedAtom};
 }
 
 /// A special prelude re-exporting all utilities to implement custom atom types.
@@ -165,4 +169,225 @@ pub trait Atom: UriBound {
     fn write(
         writer: AtomWriter,
     ) -> Result<<Self::WriteHandle as AtomHandle>::Handle, AtomWriteError>;
+
+    /// Try to read the given atom as an atom of this type.
+    ///
+    /// This is a shorthand for [`UnidentifiedAtom::read`], which reads a bit more naturally when the atom type is known in advance:
+    ///
+    /// ```
+    /// # use lv2_atom::prelude::*;
+    /// # use lv2_atom::space::error::AtomReadError;
+    /// fn read_int(atom: &UnidentifiedAtom, urids: &AtomURIDCollection) -> Result<i32, AtomReadError> {
+    ///     let i: &i32 = Int::try_read(atom, urids.int)?;
+    ///     Ok(*i)
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the atom's type does not match the given URID, or if the atom is malformed.
+    fn try_read<'a>(
+        atom: &'a UnidentifiedAtom,
+        urid: URID<Self>,
+    ) -> Result<<Self::ReadHandle as AtomHandle<'a>>::Handle, AtomReadError>
+    where
+        Self: Sized,
+    {
+        atom.read(urid)
+    }
+}
+
+/// Read an atom by dispatching on its type.
+///
+/// The first argument is a reference to an [`UnidentifiedAtom`](struct.UnidentifiedAtom.html), which is followed by a block of arms. Every arm consists of the URID of an atom type and a closure-like expression that is invoked with the read handle if the atom can be read as this type. The arms are tried in order and the last arm, starting with `_`, is evaluated if none of them matched:
+///
+/// ```
+/// use lv2_atom::prelude::*;
+/// use lv2_atom::read_atom;
+///
+/// fn as_f64(atom: &UnidentifiedAtom, urids: &AtomURIDCollection) -> Option<f64> {
+///     read_atom!(atom, {
+///         urids.int => |v| Some(*v as f64),
+///         urids.float => |v| Some(*v as f64),
+///         urids.double => |v| Some(*v),
+///         _ => None,
+///     })
+/// }
+/// ```
+///
+/// Since an arm only matches if the atom can actually be read, an atom of a listed type that is malformed is handled by the last arm too.
+#[macro_export]
+macro_rules! read_atom {
+    ($atom:expr, { $($urid:expr => |$value:pat| $body:expr,)* _ => $default:expr $(,)? }) => {{
+        let atom: &$crate::UnidentifiedAtom = $atom;
+        $(
+            if let Ok($value) = atom.read($urid) {
+                $body
+            } else
+        )* {
+            $default
+        }
+    }};
+}
+
+/// Assert that an atom can be read as the given type and that its value equals the expected one.
+///
+/// The first argument is a reference to an [`UnidentifiedAtom`](struct.UnidentifiedAtom.html), the second one the URID of the expected atom type and the third one the expected value. The value may be anything the read value can be borrowed from, for example an `i32` for an `Int` atom or a `&str` or `String` for a `String` atom:
+///
+/// ```
+/// use lv2_atom::prelude::*;
+/// use lv2_atom::space::*;
+/// use lv2_atom::{assert_atom_eq, AtomHeader};
+/// use urid::*;
+///
+/// let map = HashURIDMapper::new();
+/// let urids: AtomURIDCollection = map.populate_collection().unwrap();
+///
+/// let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(8);
+/// let raw_space = raw_space.as_space_mut();
+/// SpaceCursor::new(raw_space.as_bytes_mut())
+///     .write_atom(urids.int)
+///     .unwrap()
+///     .set(42)
+///     .unwrap();
+///
+/// let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+/// assert_atom_eq!(atom, urids.int, 42);
+/// ```
+///
+/// # Panics
+///
+/// The macro panics if the atom can't be read as the given type, with a message that contains the error and the type URID of the atom, or if the read value differs from the expected one, with a message that contains both values.
+#[macro_export]
+macro_rules! assert_atom_eq {
+    ($atom:expr, $urid:expr, $expected:expr $(,)?) => {{
+        fn expected_as<'e, T: ?Sized, E: ::core::borrow::Borrow<T>>(
+            _actual: &T,
+            expected: &'e E,
+        ) -> &'e T {
+            expected.borrow()
+        }
+
+        let atom: &$crate::UnidentifiedAtom = $atom;
+        match atom.read($urid) {
+            Ok(actual) => {
+                let expected = $expected;
+                assert_eq!(
+                    actual,
+                    expected_as(actual, &expected),
+                    "atom value differs from the expected value"
+                );
+            }
+            Err(error) => panic!(
+                "assertion failed: atom of type URID {} can't be read as the expected type: {:?}",
+                atom.header().urid().get(),
+                error
+            ),
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::space::*;
+    use crate::AtomHeader;
+    use urid::*;
+
+    #[test]
+    fn test_read_atom() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let describe = |atom: &UnidentifiedAtom| -> std::string::String {
+            read_atom!(atom, {
+                urids.int => |v| format!("int {}", v),
+                urids.float => |v| format!("float {}", v),
+                _ => "unknown".to_owned(),
+            })
+        };
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(8);
+        let raw_space = raw_space.as_space_mut();
+        {
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            cursor.write_atom(urids.int).unwrap().set(42).unwrap();
+            cursor.write_atom(urids.float).unwrap().set(1.5).unwrap();
+            cursor.write_atom(urids.long).unwrap().set(17).unwrap();
+        }
+
+        let mut reader = raw_space.read();
+        let int = unsafe { reader.next_atom() }.unwrap();
+        let float = unsafe { reader.next_atom() }.unwrap();
+        let long = unsafe { reader.next_atom() }.unwrap();
+
+        assert_eq!(describe(int), "int 42");
+        assert_eq!(describe(float), "float 1.5");
+        assert_eq!(describe(long), "unknown");
+    }
+
+    #[test]
+    fn test_assert_atom_eq() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(8);
+        let raw_space = raw_space.as_space_mut();
+        {
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            cursor.write_atom(urids.int).unwrap().set(42).unwrap();
+            cursor.write_atom(urids.long).unwrap().set(17).unwrap();
+            cursor
+                .write_atom(urids.string)
+                .unwrap()
+                .append("Hello")
+                .unwrap();
+        }
+
+        let mut reader = raw_space.read();
+        let int = unsafe { reader.next_atom() }.unwrap();
+        let long = unsafe { reader.next_atom() }.unwrap();
+        let string = unsafe { reader.next_atom() }.unwrap();
+
+        assert_atom_eq!(int, urids.int, 42);
+        assert_atom_eq!(long, urids.long, 17);
+        assert_atom_eq!(string, urids.string, "Hello");
+        assert_atom_eq!(string, urids.string, std::string::String::from("Hello"));
+    }
+
+    fn write_int(raw_space: &mut AtomSpace, urids: &AtomURIDCollection) {
+        SpaceCursor::new(raw_space.as_bytes_mut())
+            .write_atom(urids.int)
+            .unwrap()
+            .set(42)
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "atom value differs from the expected value")]
+    fn test_assert_atom_eq_wrong_value() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(2);
+        let raw_space = raw_space.as_space_mut();
+        write_int(raw_space, &urids);
+
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        assert_atom_eq!(atom, urids.int, 17);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be read as the expected type")]
+    fn test_assert_atom_eq_wrong_type() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(2);
+        let raw_space = raw_space.as_space_mut();
+        write_int(raw_space, &urids);
+
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        assert_atom_eq!(atom, urids.float, 42.0);
+    }
 }
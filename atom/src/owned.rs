@@ -0,0 +1,145 @@
+use crate::space::error::AtomWriteError;
+use crate::space::{AlignedVec, AlignedVecCursor, AtomSpace, SpaceWriter};
+use crate::{AtomHeader, UnidentifiedAtom};
+
+/// An atom that is stored in its own, growable buffer.
+///
+/// Atoms usually live in buffers provided by the host, which are only valid during a single processing cycle. An owned atom is backed by an [`AlignedVec`](crate::space::AlignedVec) instead, which makes it possible to keep atoms around, for example to stash them in the plugin's state or to send them to a worker thread.
+///
+/// # Example
+///
+/// ```
+/// use lv2_atom::prelude::*;
+/// use lv2_atom::space::SpaceWriter;
+/// use lv2_atom::OwnedAtom;
+/// use urid::*;
+///
+/// let map = HashURIDMapper::new();
+/// let urids: AtomURIDCollection = map.populate_collection().unwrap();
+///
+/// let mut owned = OwnedAtom::new();
+/// owned.write().write_atom(urids.string).unwrap().append("Hello").unwrap();
+///
+/// let atom = owned.atom().unwrap();
+/// assert_eq!(atom.read(urids.string).unwrap(), "Hello");
+///
+/// // The raw bytes of the atom, including the header.
+/// let bytes = owned.into_bytes();
+/// assert_eq!(&bytes[8..14], b"Hello\0");
+/// ```
+#[derive(Clone, Default)]
+pub struct OwnedAtom {
+    buffer: AlignedVec<AtomHeader>,
+}
+
+impl OwnedAtom {
+    /// Create a new, empty owned atom.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an owned copy of the given atom.
+    ///
+    /// # Errors
+    ///
+    /// This method never fails in practice, since the buffer grows as needed. An error would only be returned if the copied atom couldn't be aligned.
+    pub fn from_atom(atom: &UnidentifiedAtom) -> Result<Self, AtomWriteError> {
+        let mut owned = Self::new();
+        owned.write().copy_atom(atom)?;
+        Ok(owned)
+    }
+
+    /// Return a writer that replaces the atom.
+    ///
+    /// The writer is a regular [`AlignedVecCursor`](crate::space::AlignedVecCursor) that starts at the beginning of the buffer and grows it as needed. Only the first atom written with it is considered to be the owned atom.
+    ///
+    /// If writing fails midway, the owned atom contains the partially written atom and should be discarded or written again.
+    #[inline]
+    pub fn write(&mut self) -> AlignedVecCursor<'_, AtomHeader> {
+        self.buffer.write()
+    }
+
+    /// Return the space that contains the atom, including its header and padding.
+    ///
+    /// The space is empty if no atom has been written yet.
+    pub fn as_space(&self) -> &AtomSpace {
+        let len = self.atom().map_or(0, |atom| {
+            crate::util::padded_size::<AtomHeader>(atom.header().size_of_atom())
+        });
+        let bytes = self.buffer.as_bytes();
+
+        // SAFETY: The bytes start at the beginning of the buffer, which is aligned.
+        unsafe { AtomSpace::from_bytes_unchecked(&bytes[..len.min(bytes.len())]) }
+    }
+
+    /// Return the atom, or `None` if no atom has been written yet.
+    pub fn atom(&self) -> Option<&UnidentifiedAtom> {
+        let space = self.buffer.as_space();
+
+        // SAFETY: The buffer is zero-initialized and therefore, every header in it is initialized. A header without a valid type is rejected before reading the atom and the bounds of the body are checked by the reader.
+        unsafe {
+            space.assume_init_slice().first()?.try_urid()?;
+            space.read().next_atom().ok()
+        }
+    }
+
+    /// Convert the atom into its raw bytes, including the header and padding.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.as_space().as_bytes().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::space::SpaceWriter;
+    use crate::OwnedAtom;
+    use urid::*;
+
+    #[test]
+    fn test_owned_atom() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+
+        let mut owned = OwnedAtom::new();
+        assert!(owned.atom().is_none());
+        assert_eq!(owned.as_space().bytes_len(), 0);
+
+        owned
+            .write()
+            .write_atom(urids.int)
+            .unwrap()
+            .set(42)
+            .unwrap();
+        assert_eq!(*owned.atom().unwrap().read(urids.int).unwrap(), 42);
+        assert_eq!(owned.as_space().bytes_len(), 16);
+
+        // Writing again replaces the previous atom.
+        let mut cursor = owned.write();
+        let mut writer = cursor
+            .write_atom(urids.vector)
+            .unwrap()
+            .of_type(urids.long)
+            .unwrap();
+        for i in 0..64 {
+            writer.push(i).unwrap();
+        }
+        let atom = owned.atom().unwrap();
+        let vector = atom
+            .read(urids.vector)
+            .unwrap()
+            .of_type(urids.long)
+            .unwrap();
+        assert_eq!(vector.len(), 64);
+        assert_eq!(vector[63], 63);
+
+        // The copy reads like the original.
+        let copy = OwnedAtom::from_atom(atom).unwrap();
+        assert_eq!(copy.as_space().as_bytes(), owned.as_space().as_bytes());
+
+        let bytes = owned.into_bytes();
+        assert_eq!(bytes.len(), 8 + 8 + 64 * 8);
+        assert_eq!(bytes, copy.into_bytes());
+    }
+}
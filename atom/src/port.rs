@@ -23,14 +23,16 @@
 //!     ports.output.write(urids.int).unwrap();
 //! }
 //! ```
+use crate::atoms::sequence::{SequenceIterator, SequenceURIDs, SequenceUnit, SequenceWriter};
 use crate::header::AtomHeader;
 use crate::space::error::{AtomReadError, AtomWriteError};
 use crate::space::*;
 use crate::{AtomHandle, UnidentifiedAtom};
-use lv2_core::port::PortType;
+use lv2_core::port::{port_class, PortType};
 use std::ffi::c_void;
+use std::marker::PhantomData;
 use std::ptr::NonNull;
-use urid::URID;
+use urid::{Uri, UriBound, URID};
 
 /// A handle to read atoms from a port.
 ///
@@ -60,6 +62,20 @@ impl<'a> PortReader<'a> {
     ) -> Result<<A::ReadHandle as AtomHandle>::Handle, AtomReadError> {
         self.atom.read(urid)
     }
+
+    /// Return the header of the contained atom.
+    ///
+    /// The header contains the type URID of the atom, which can be used to dispatch on the atom type once instead of trying to [`read`](#method.read) every candidate type.
+    #[inline]
+    pub fn header(&self) -> AtomHeader {
+        *self.atom.header()
+    }
+
+    /// Return the contained atom without identifying it.
+    #[inline]
+    pub fn into_unidentified(self) -> &'a UnidentifiedAtom {
+        self.atom
+    }
 }
 
 /// A handle to write atoms into a port.
@@ -105,7 +121,12 @@ impl<'a> PortWriter<'a> {
 /// [See also the module documentation.](index.html)
 pub struct AtomPort;
 
+unsafe impl UriBound for AtomPort {
+    const URI: &'static [u8] = sys::LV2_ATOM__AtomPort;
+}
+
 impl PortType for AtomPort {
+    const CLASS: Option<&'static Uri> = Some(port_class::<Self>());
     type InputPortType = PortReader<'static>;
     type OutputPortType = PortWriter<'static>;
 
@@ -122,6 +143,119 @@ impl PortType for AtomPort {
     }
 }
 
+/// A handle to read a sequence from a port.
+///
+/// If you add a [`SequencePort`](struct.SequencePort.html) to your ports struct, you will receive an instance of this struct to read the sequence.
+pub struct SequencePortReader<'a, U: SequenceUnit> {
+    reader: PortReader<'a>,
+    unit: PhantomData<U>,
+}
+
+impl<'a, U: SequenceUnit> SequencePortReader<'a, U> {
+    /// Read the sequence and return an iterator over its events.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the port doesn't contain a sequence, if the time stamps of the sequence aren't measured in `U`, or if the sequence is malformed.
+    #[inline]
+    pub fn read(&self, urids: &SequenceURIDs<U>) -> Result<SequenceIterator<'a, U>, AtomReadError> {
+        self.reader.atom.read(urids.sequence)?.with_unit(urids.unit)
+    }
+
+    /// Return the underlying port reader, e.g. to read the contained atom as another type.
+    #[inline]
+    pub fn into_port_reader(self) -> PortReader<'a> {
+        self.reader
+    }
+}
+
+/// A handle to write a sequence into a port.
+///
+/// If you add a [`SequencePort`](struct.SequencePort.html) to your ports struct, you will receive an instance of this struct to write the sequence.
+pub struct SequencePortWriter<'a, U: SequenceUnit> {
+    writer: PortWriter<'a>,
+    unit: PhantomData<U>,
+}
+
+impl<'a, U: SequenceUnit> SequencePortWriter<'a, U> {
+    /// Initialize the sequence and return a writer for its events.
+    ///
+    /// Like [`PortWriter::write`](struct.PortWriter.html#method.write), this method can only be called once.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the buffer isn't big enough to write the header of the sequence.
+    #[inline]
+    pub fn write<'write>(
+        &mut self,
+        urids: &SequenceURIDs<U>,
+    ) -> Result<SequenceWriter<'write, U>, AtomWriteError> {
+        self.writer.write(urids.sequence)?.with_unit(urids.unit)
+    }
+}
+
+/// The port type for sequences of events with time stamps in the unit `U`.
+///
+/// This port type uses the same buffers as the [`AtomPort`](struct.AtomPort.html), but it is limited to sequences. A plugin stores the URIDs of the sequence and the unit once in a [`SequenceURIDs`](../atoms/sequence/struct.SequenceURIDs.html) collection and passes it to the `read` and `write` methods of the ports:
+///
+/// ```
+/// use lv2_atom::atoms::sequence::SequenceURIDs;
+/// use lv2_atom::port::SequencePort;
+/// use lv2_core::prelude::*;
+/// use lv2_units::units::Frame;
+///
+/// #[derive(PortCollection)]
+/// struct MyPorts {
+///     input: InputPort<SequencePort<Frame>>,
+///     output: OutputPort<SequencePort<Frame>>,
+/// }
+///
+/// /// Something like a plugin's run method.
+/// fn run(ports: &mut MyPorts, urids: &SequenceURIDs<Frame>) {
+///     let input = ports.input.read(urids).unwrap();
+///     let mut output = ports.output.write(urids).unwrap();
+///     // Forward all events.
+///     for (timestamp, atom) in input {
+///         output.forward(timestamp, atom).unwrap();
+///     }
+/// }
+/// ```
+pub struct SequencePort<U: SequenceUnit> {
+    unit: PhantomData<U>,
+}
+
+unsafe impl<U: SequenceUnit> UriBound for SequencePort<U> {
+    const URI: &'static [u8] = sys::LV2_ATOM__AtomPort;
+}
+
+impl<U: SequenceUnit> PortType for SequencePort<U> {
+    const CLASS: Option<&'static Uri> = Some(port_class::<Self>());
+    type InputPortType = SequencePortReader<'static, U>;
+    type OutputPortType = SequencePortWriter<'static, U>;
+
+    #[inline]
+    unsafe fn input_from_raw(
+        pointer: NonNull<c_void>,
+        sample_count: u32,
+    ) -> SequencePortReader<'static, U> {
+        SequencePortReader {
+            reader: AtomPort::input_from_raw(pointer, sample_count),
+            unit: PhantomData,
+        }
+    }
+
+    #[inline]
+    unsafe fn output_from_raw(
+        pointer: NonNull<c_void>,
+        sample_count: u32,
+    ) -> SequencePortWriter<'static, U> {
+        SequencePortWriter {
+            writer: AtomPort::output_from_raw(pointer, sample_count),
+            unit: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -163,6 +297,76 @@ mod tests {
                 .unwrap();
             let reader = unsafe { AtomPort::input_from_raw(NonNull::from(chunk).cast(), 0) };
             assert_eq!(*reader.read::<Int>(urids.int).unwrap(), 42);
+
+            let header = reader.header();
+            assert_eq!(header.urid(), urids.int);
+            assert_eq!(header.size_of_body(), 2 * size_of::<i32>());
+
+            let atom = reader.into_unidentified();
+            assert_eq!(atom.header().urid(), urids.int);
+            assert_eq!(*atom.read(urids.int).unwrap(), 42);
+        }
+    }
+
+    #[test]
+    fn test_sequence_port() {
+        use crate::atoms::sequence::SequenceURIDs;
+        use units::units::{Beat, Frame};
+
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+        let frame_urids: SequenceURIDs<Frame> = map.populate_collection().unwrap();
+        let beat_urids: SequenceURIDs<Beat> = map.populate_collection().unwrap();
+
+        // Sequence ports are described as atom ports.
+        assert_eq!(SequencePort::<Frame>::CLASS, AtomPort::CLASS);
+        assert!(AtomPort::CLASS.is_some());
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing a chunk to indicate the size of the space.
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = space.write_atom(urids.chunk).unwrap();
+            writer.allocate(256 - size_of::<sys::LV2_Atom>()).unwrap();
+        }
+
+        // Writing the sequence with the port.
+        {
+            let mut port = unsafe {
+                SequencePort::<Frame>::output_from_raw(
+                    NonNull::from(raw_space.as_bytes_mut()).cast(),
+                    0,
+                )
+            };
+            let mut writer = port.write(&frame_urids).unwrap();
+            writer.new_event(0, urids.int).unwrap().set(42).unwrap();
+            writer.new_event(3, urids.long).unwrap().set(17).unwrap();
+        }
+
+        // Reading the sequence with the port.
+        {
+            let chunk = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(urids.chunk)
+                .unwrap();
+            let port =
+                unsafe { SequencePort::<Frame>::input_from_raw(NonNull::from(chunk).cast(), 0) };
+
+            let mut events = port.read(&frame_urids).unwrap();
+            let (timestamp, atom) = events.next().unwrap();
+            assert_eq!(0, timestamp);
+            assert_eq!(42, *atom.read(urids.int).unwrap());
+            let (timestamp, atom) = events.next().unwrap();
+            assert_eq!(3, timestamp);
+            assert_eq!(17, *atom.read(urids.long).unwrap());
+            assert!(events.next().is_none());
+
+            // The sequence isn't measured in beats.
+            let port =
+                unsafe { SequencePort::<Beat>::input_from_raw(NonNull::from(chunk).cast(), 0) };
+            assert!(port.read(&beat_urids).is_err());
         }
     }
 }
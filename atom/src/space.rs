@@ -2,6 +2,8 @@
 #![deny(missing_docs)]
 
 mod aligned;
+#[cfg(debug_assertions)]
+mod alloc_counter;
 mod allocator;
 mod atom_writer;
 mod cursor;
@@ -11,9 +13,11 @@ mod terminated;
 mod vec;
 
 pub use aligned::{AlignedSpace, AtomSpace};
+#[cfg(debug_assertions)]
+pub use alloc_counter::{alloc_counter, AllocationGuard};
 pub use allocator::*;
 pub use atom_writer::AtomWriter;
-pub use cursor::SpaceCursor;
+pub use cursor::{Checkpoint, SpaceCursor};
 pub use reader::SpaceReader;
 pub use terminated::Terminated;
 pub use vec::{AlignedVec, AlignedVecCursor};
@@ -0,0 +1,118 @@
+//! Debug-only instrumentation of heap allocations made by atom spaces.
+//!
+//! Only [`AlignedVec`](crate::space::AlignedVec) may allocate while writing atoms, when one of its cursors needs to grow the buffer. These growths are counted per thread, which lets plugin authors verify that their `run` method doesn't allocate by accident.
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[inline]
+pub(crate) fn record_allocation() {
+    ALLOCATIONS.with(|count| count.set(count.get() + 1));
+}
+
+/// Return the number of times an atom space had to allocate on the current thread.
+///
+/// This counter is only available in debug builds.
+#[inline]
+pub fn alloc_counter() -> usize {
+    ALLOCATIONS.with(|count| count.get())
+}
+
+/// A guard that asserts that no atom space allocated during its lifetime.
+///
+/// Create the guard at the beginning of a scope, e.g. a plugin's `run` method. If an atom space allocated on the current thread before the guard is dropped, the guard panics.
+///
+/// This guard is only available in debug builds.
+pub struct AllocationGuard {
+    start: usize,
+}
+
+impl AllocationGuard {
+    /// Start observing the allocations of the current thread.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            start: alloc_counter(),
+        }
+    }
+
+    /// Return the number of allocations since the guard was created.
+    #[inline]
+    pub fn allocations(&self) -> usize {
+        alloc_counter() - self.start
+    }
+}
+
+impl Default for AllocationGuard {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for AllocationGuard {
+    fn drop(&mut self) {
+        let allocations = self.allocations();
+        if allocations > 0 && !std::thread::panicking() {
+            panic!(
+                "Atom spaces allocated {} times while an AllocationGuard was alive",
+                allocations
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::space::*;
+    use crate::AtomHeader;
+    use urid::*;
+
+    #[test]
+    fn test_cursor_does_not_allocate() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+
+        let guard = AllocationGuard::new();
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            space.write_atom(urids.int).unwrap().set(42).unwrap();
+            space.write_atom(urids.long).unwrap().set(17).unwrap();
+        }
+        assert_eq!(guard.allocations(), 0);
+    }
+
+    #[test]
+    fn test_vec_growth_allocates() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let mut raw_space = AlignedVec::<AtomHeader>::new();
+
+        let start = alloc_counter();
+        {
+            let mut space = raw_space.write();
+            space.write_atom(urids.int).unwrap().set(42).unwrap();
+        }
+        assert!(alloc_counter() > start);
+    }
+
+    #[test]
+    #[should_panic(expected = "allocated")]
+    fn test_guard_panics() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let mut raw_space = AlignedVec::<AtomHeader>::new();
+
+        let _guard = AllocationGuard::new();
+        raw_space
+            .write()
+            .write_atom(urids.int)
+            .unwrap()
+            .set(42)
+            .unwrap();
+    }
+}
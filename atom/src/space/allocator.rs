@@ -200,6 +200,56 @@ pub trait SpaceWriter: SpaceAllocator + Sized {
         Ok(space.as_uninit_slice_mut())
     }
 
+    /// Returns the number of bytes that can still be allocated from the current buffer.
+    ///
+    /// For writers backed by a fixed buffer, like [`SpaceCursor`](crate::space::SpaceCursor), an
+    /// allocation larger than this returns [`AtomWriteError::OutOfSpace`]. Growable writers may
+    /// reallocate their buffer instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lv2_atom::atom_prelude::*;
+    ///
+    /// let mut buffer = vec![0; 64];
+    /// let mut writer = SpaceCursor::new(&mut buffer);
+    ///
+    /// writer.allocate(5).unwrap();
+    /// assert_eq!(writer.remaining_capacity(), 59);
+    /// ```
+    #[inline]
+    fn remaining_capacity(&self) -> usize {
+        self.remaining_bytes().len()
+    }
+
+    /// Returns the number of bytes that can still be allocated for values of type `T`.
+    ///
+    /// This is the [remaining capacity](SpaceWriter::remaining_capacity), minus the padding that
+    /// is required to align the next allocation for `T`. Checking it up-front allows to reject a
+    /// large write with an error before a partial atom is written. If the padding can't be
+    /// computed, no aligned allocation is possible and `0` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lv2_atom::atom_prelude::*;
+    ///
+    /// let mut buffer = AlignedVec::<u64>::new_with_capacity(8);
+    /// let mut writer = SpaceCursor::new(buffer.as_space_mut().as_bytes_mut());
+    ///
+    /// writer.allocate(5).unwrap();
+    /// assert_eq!(writer.aligned_remaining::<u8>(), 59);
+    /// assert_eq!(writer.aligned_remaining::<u64>(), 56);
+    /// ```
+    #[inline]
+    fn aligned_remaining<T: 'static>(&self) -> usize {
+        let remaining = self.remaining_bytes();
+        match crate::util::try_padding_for::<T>(remaining) {
+            Ok(padding) => remaining.len().saturating_sub(padding),
+            Err(_) => 0,
+        }
+    }
+
     /// Writes an atom of a given type into the buffer.
     ///
     /// This method only initializes the new Atom header with the given type, tracking its
@@ -326,6 +376,30 @@ mod tests {
         assert_eq!(31, cursor.remaining_bytes().len());
     }
 
+    #[test]
+    fn test_remaining_capacity() {
+        let mut space = AlignedVec::<u64>::new_with_capacity(4);
+        let mut cursor = SpaceCursor::new(space.as_space_mut().as_bytes_mut());
+        assert_eq!(32, cursor.remaining_capacity());
+        assert_eq!(32, cursor.aligned_remaining::<u64>());
+
+        cursor.write_value(1u8).unwrap();
+        assert_eq!(31, cursor.remaining_capacity());
+        assert_eq!(31, cursor.aligned_remaining::<u8>());
+        assert_eq!(28, cursor.aligned_remaining::<u32>());
+        assert_eq!(24, cursor.aligned_remaining::<u64>());
+
+        // Everything that is reported can actually be allocated.
+        let remaining = cursor.aligned_remaining::<u64>();
+        cursor.allocate_aligned::<u64>(remaining).unwrap();
+        assert_eq!(0, cursor.remaining_capacity());
+        assert_eq!(0, cursor.aligned_remaining::<u64>());
+        assert!(matches!(
+            cursor.allocate(1),
+            Err(AtomWriteError::OutOfSpace { .. })
+        ));
+    }
+
     // SAFETY: this is just for testing, values aren't actually read using this URID.
     const INT_URID: URID<Int> = unsafe { URID::new_unchecked(5) };
 
@@ -53,6 +53,18 @@ impl<'a> AtomWriter<'a> {
         unsafe { &mut space.assume_init_slice_mut()[0] }
     }
 
+    /// Returns the body of the atom that has been written so far.
+    ///
+    /// # Safety
+    ///
+    /// The caller is responsible to ensure that the internal structure of the atom stays valid.
+    #[inline]
+    pub(crate) unsafe fn body_bytes_mut(&mut self) -> &mut [u8] {
+        let start = self.atom_header_index + std::mem::size_of::<AtomHeader>();
+        let end = start + self.atom_header().size_of_body();
+        &mut self.parent.allocated_bytes_mut()[start..end]
+    }
+
     /// Writes an atom header into the given [`SpaceWriter`], and returns a new writer that starts
     /// tracking its size.
     ///
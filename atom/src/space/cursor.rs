@@ -13,6 +13,14 @@ pub struct SpaceCursor<'a> {
     allocated_length: usize,
 }
 
+/// A saved write position of a [`SpaceCursor`].
+///
+/// Checkpoints are created by [`SpaceCursor::checkpoint`] and restored by [`SpaceCursor::rewind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    allocated_length: usize,
+}
+
 impl<'a> SpaceCursor<'a> {
     /// Create a new [`SpaceCursor`] from a given mutable byte buffer.
     pub fn new(data: &'a mut [u8]) -> Self {
@@ -21,6 +29,56 @@ impl<'a> SpaceCursor<'a> {
             allocated_length: 0,
         }
     }
+
+    /// Save the current write position of the cursor.
+    ///
+    /// This can be used to speculatively write atoms: If the written atom turns out to be invalid,
+    /// the cursor can be rewound to the checkpoint with [`rewind`](SpaceCursor::rewind) and the
+    /// reclaimed space can be used for something else.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lv2_atom::atom_prelude::*;
+    ///
+    /// let mut buffer = vec![0; 64];
+    /// let mut cursor = SpaceCursor::new(&mut buffer);
+    ///
+    /// let checkpoint = cursor.checkpoint();
+    /// cursor.write_bytes(b"Hello").unwrap();
+    /// cursor.rewind(checkpoint).unwrap();
+    /// cursor.write_bytes(b"World").unwrap();
+    ///
+    /// assert_eq!(cursor.allocated_bytes(), b"World");
+    /// ```
+    #[inline]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            allocated_length: self.allocated_length,
+        }
+    }
+
+    /// Restore the write position of a checkpoint.
+    ///
+    /// Everything that has been written after the checkpoint was created is discarded and will be
+    /// overwritten by further writes.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the checkpoint lies beyond the currently allocated bytes,
+    /// e.g. because the cursor already has been rewound to an earlier checkpoint.
+    pub fn rewind(&mut self, checkpoint: Checkpoint) -> Result<(), AtomWriteError> {
+        if checkpoint.allocated_length > self.allocated_length {
+            return Err(AtomWriteError::RewindBeyondAllocated {
+                requested: checkpoint.allocated_length,
+                allocated: self.allocated_length,
+            });
+        }
+
+        self.allocated_length = checkpoint.allocated_length;
+
+        Ok(())
+    }
 }
 
 impl<'a> SpaceAllocator for SpaceCursor<'a> {
@@ -81,3 +139,48 @@ impl<'a> SpaceAllocator for SpaceCursor<'a> {
         &self.data[self.allocated_length..]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::space::*;
+    use crate::AtomHeader;
+    use urid::*;
+
+    #[test]
+    fn test_checkpoint() {
+        let map = HashURIDMapper::new();
+        let urids = crate::atoms::AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(8);
+        let raw_space = raw_space.as_space_mut();
+
+        {
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            cursor.write_atom(urids.int).unwrap().set(1).unwrap();
+
+            // Write a partial string, then discard it.
+            let checkpoint = cursor.checkpoint();
+            cursor
+                .write_atom(urids.string)
+                .unwrap()
+                .append("discarded")
+                .unwrap();
+            cursor.rewind(checkpoint).unwrap();
+
+            // Reuse the reclaimed space.
+            cursor.write_atom(urids.long).unwrap().set(42).unwrap();
+
+            // A checkpoint beyond the allocated bytes is rejected.
+            let later = cursor.checkpoint();
+            cursor.rewind(checkpoint).unwrap();
+            assert!(cursor.rewind(later).is_err());
+            cursor.write_atom(urids.long).unwrap().set(42).unwrap();
+        }
+
+        let mut reader = raw_space.read();
+        let first = unsafe { reader.next_atom() }.unwrap();
+        assert_eq!(1, *first.read(urids.int).unwrap());
+        let second = unsafe { reader.next_atom() }.unwrap();
+        assert_eq!(42, *second.read(urids.long).unwrap());
+    }
+}
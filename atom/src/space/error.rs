@@ -142,6 +142,34 @@ pub enum AtomWriteError {
     AlignmentError(AlignmentError),
 }
 
+impl AtomWriteError {
+    /// The total size of the buffer, in bytes, that would have been needed for the failed write to succeed.
+    ///
+    /// This is the amount of bytes that were already used in the buffer plus the amount of requested bytes. A caller can use it to grow the buffer before retrying the write. Returns `None` if this isn't an [`OutOfSpace`](AtomWriteError::OutOfSpace) error.
+    #[inline]
+    pub fn needed(&self) -> Option<usize> {
+        match *self {
+            AtomWriteError::OutOfSpace {
+                used, requested, ..
+            } => Some(used.saturating_add(requested)),
+            _ => None,
+        }
+    }
+
+    /// The amount of bytes that were still available in the buffer when the write failed.
+    ///
+    /// Returns `None` if this isn't an [`OutOfSpace`](AtomWriteError::OutOfSpace) error.
+    #[inline]
+    pub fn available(&self) -> Option<usize> {
+        match *self {
+            AtomWriteError::OutOfSpace { used, capacity, .. } => {
+                Some(capacity.saturating_sub(used))
+            }
+            _ => None,
+        }
+    }
+}
+
 impl Display for AtomWriteError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -223,6 +251,13 @@ pub enum AtomReadError {
     },
     /// An alignment error curred when trying to read from the underlying buffer.
     AlignmentError(AlignmentError),
+    /// The size of the atom being read exceeds the maximum size allowed by the caller.
+    TooLarge {
+        /// The maximum allowed size of the atom, in bytes
+        max_size: usize,
+        /// The size of the atom noted in its header, in bytes
+        requested: usize,
+    },
 }
 
 impl Display for AtomReadError {
@@ -268,6 +303,16 @@ impl Display for AtomReadError {
                 )
             }
             AtomReadError::AlignmentError(e) => Display::fmt(e, f),
+            AtomReadError::TooLarge {
+                max_size,
+                requested,
+            } => {
+                write!(
+                    f,
+                    "Atom of {} bytes exceeds the maximum allowed size of {} bytes",
+                    requested, max_size
+                )
+            }
         }
     }
 }
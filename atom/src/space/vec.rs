@@ -105,7 +105,15 @@ impl<T: Copy + 'static> AlignedVec<T> {
 
         if max > byte_len {
             let new_size = crate::util::byte_index_to_value_index::<T>(max);
+            #[cfg(debug_assertions)]
+            let previous_capacity = self.inner.capacity();
+
             self.inner.resize(new_size, MaybeUninit::zeroed());
+
+            #[cfg(debug_assertions)]
+            if self.inner.capacity() != previous_capacity {
+                crate::space::alloc_counter::record_allocation();
+            }
         }
 
         let bytes = self.as_bytes_mut();
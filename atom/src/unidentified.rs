@@ -1,7 +1,9 @@
+use crate::atoms::{object, path, scalar, sequence, string, tuple, vector};
 use crate::space::error::{AtomReadError, AtomWriteError};
-use crate::space::AtomSpace;
+use crate::space::{AlignedVec, AtomSpace, SpaceReader};
 use crate::{Atom, AtomHandle, AtomHeader};
-use urid::URID;
+use std::fmt;
+use urid::{Unmap, Uri, UriBound, URID};
 
 /// An atom of yet unknown type.
 ///
@@ -50,6 +52,48 @@ impl UnidentifiedAtom {
         ))
     }
 
+    /// Wrap an atom from the `lv2_sys` crate.
+    ///
+    /// This is the bridge between C code handing over atoms and the safe atom API.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an alignment error if the atom isn't aligned to 64 bits, as required by the LV2 specification.
+    ///
+    /// # Safety
+    ///
+    /// The caller has to ensure that the given header is immediately followed by a valid atom body of the size noted in the header.
+    #[inline]
+    pub unsafe fn from_sys(atom: &lv2_sys::LV2_Atom) -> Result<&Self, AtomReadError> {
+        let bytes = ::core::slice::from_raw_parts(
+            atom as *const lv2_sys::LV2_Atom as *const u8,
+            ::core::mem::size_of::<lv2_sys::LV2_Atom>(),
+        );
+        Self::from_space(AtomSpace::from_bytes(bytes)?)
+    }
+
+    /// Wrap a pointer to an atom from the `lv2_sys` crate.
+    ///
+    /// This works like [`from_sys`](#method.from_sys), but also accepts null pointers.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the pointer is null or if the atom isn't aligned to 64 bits.
+    ///
+    /// # Safety
+    ///
+    /// If the pointer isn't null, it has to point to a valid atom header that is immediately followed by a valid atom body of the size noted in the header. The atom has to outlive the lifetime `'a`.
+    #[inline]
+    pub unsafe fn from_sys_ptr<'a>(
+        atom: *const lv2_sys::LV2_Atom,
+    ) -> Result<&'a Self, AtomReadError> {
+        let atom = atom.as_ref().ok_or(AtomReadError::ReadingOutOfBounds {
+            available: 0,
+            requested: ::core::mem::size_of::<AtomHeader>(),
+        })?;
+        Self::from_sys(atom)
+    }
+
     #[inline]
     pub(crate) unsafe fn from_header(header: &AtomHeader) -> &Self {
         // SAFETY: UnidentifiedAtom is repr(C) and has AtomHeader as its only field, so transmuting between the two is safe.
@@ -144,4 +188,468 @@ impl UnidentifiedAtom {
         // SAFETY: the bytes are necessarily aligned, since they are right after the aligned AtomHeader
         unsafe { AtomSpace::from_bytes_mut_unchecked(self.body_bytes_mut()) }
     }
+
+    /// Copy the atom, including its header, into a newly allocated buffer.
+    ///
+    /// The size noted in the atom's header is checked against `max_atom_size` before anything is allocated, which guards against corrupted or malicious headers that claim gigantic sizes.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a `TooLarge` error if the size of the atom, including its header, exceeds `max_atom_size`.
+    pub fn to_aligned_vec(
+        &self,
+        max_atom_size: usize,
+    ) -> Result<AlignedVec<AtomHeader>, AtomReadError> {
+        let size = self.header.size_of_atom();
+        if size > max_atom_size {
+            return Err(AtomReadError::TooLarge {
+                max_size: max_atom_size,
+                requested: size,
+            });
+        }
+
+        let mut vec = AlignedVec::<AtomHeader>::new_with_capacity(
+            crate::util::padded_size::<AtomHeader>(size) / ::core::mem::size_of::<AtomHeader>(),
+        );
+        vec.as_bytes_mut()[..size].copy_from_slice(self.atom_space().as_bytes());
+        Ok(vec)
+    }
+
+    /// Print the atom as a human-readable tree.
+    ///
+    /// The standard atom types are recognized by the URIs of their URIDs, which are retrieved from the given unmapper. Their values are printed, and tuples, objects and sequences are printed recursively. Atoms of other types are printed with their URI and the size of their body. Atoms that are malformed are marked as such, and atoms that are nested more than 32 levels deep are printed as `<too deep>`.
+    ///
+    /// This method is meant to be used in `Debug` implementations:
+    ///
+    /// ```
+    /// use lv2_atom::prelude::*;
+    /// use urid::*;
+    /// use std::fmt;
+    ///
+    /// struct AtomDebug<'a>(&'a UnidentifiedAtom, &'a HashURIDMapper);
+    ///
+    /// impl<'a> fmt::Debug for AtomDebug<'a> {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         self.0.debug_fmt(self.1, f)
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method only returns an error if writing to the formatter fails.
+    pub fn debug_fmt<U: Unmap>(&self, unmap: &U, f: &mut fmt::Formatter) -> fmt::Result {
+        self.debug_fmt_indented(unmap, f, 0)
+    }
+
+    fn debug_fmt_indented<U: Unmap>(
+        &self,
+        unmap: &U,
+        f: &mut fmt::Formatter,
+        indent: usize,
+    ) -> fmt::Result {
+        if indent > MAX_DEBUG_DEPTH {
+            return write!(f, "<too deep>");
+        }
+        let uri = unmap.unmap(self.header.urid());
+        let is = |bound: &Uri| uri == Some(bound);
+        let body = self.body();
+        let mut reader = body.read();
+
+        // SAFETY: The type of the atom is checked before every read.
+        unsafe {
+            if is(scalar::Int::uri()) {
+                debug_value::<i32>(f, "Int", &mut reader)
+            } else if is(scalar::Long::uri()) {
+                debug_value::<i64>(f, "Long", &mut reader)
+            } else if is(scalar::Float::uri()) {
+                debug_value::<f32>(f, "Float", &mut reader)
+            } else if is(scalar::Double::uri()) {
+                debug_value::<f64>(f, "Double", &mut reader)
+            } else if is(scalar::Bool::uri()) {
+                match reader.next_value::<i32>() {
+                    Ok(value) => write!(f, "Bool({})", *value != 0),
+                    Err(_) => write!(f, "Bool(<malformed>)"),
+                }
+            } else if is(scalar::AtomURID::uri()) {
+                match reader.next_value::<u32>() {
+                    Ok(urid) => write!(f, "URID({})", DebugUri(unmap, *urid)),
+                    Err(_) => write!(f, "URID(<malformed>)"),
+                }
+            } else if is(string::String::uri()) {
+                match string::String::read(body) {
+                    Ok(string) => write!(f, "String({:?})", string),
+                    Err(_) => write!(f, "String(<malformed>)"),
+                }
+            } else if is(string::Literal::uri()) {
+                match string::Literal::read(body) {
+                    Ok((_, string)) => write!(f, "Literal({:?})", string),
+                    Err(_) => write!(f, "Literal(<malformed>)"),
+                }
+            } else if is(path::Path::uri()) {
+                match path::Path::read(body) {
+                    Ok(path) => write!(f, "Path({:?})", path),
+                    Err(_) => write!(f, "Path(<malformed>)"),
+                }
+            } else if is(vector::Vector::uri()) {
+                self.debug_vector(unmap, f, &mut reader)
+            } else if is(tuple::Tuple::uri()) {
+                let atoms = match tuple::Tuple::read(body) {
+                    Ok(atoms) => atoms,
+                    Err(_) => return write!(f, "Tuple(<malformed>)"),
+                };
+                writeln!(f, "Tuple [")?;
+                for atom in atoms {
+                    write!(f, "{:1$}", "", (indent + 1) * 4)?;
+                    atom.debug_fmt_indented(unmap, f, indent + 1)?;
+                    writeln!(f)?;
+                }
+                write!(f, "{:1$}]", "", indent * 4)
+            } else if is(object::Object::uri()) || is(object::Blank::uri()) {
+                let (header, properties) = match object::Object::read(body) {
+                    Ok(object) => object,
+                    Err(_) => return write!(f, "Object(<malformed>)"),
+                };
+                writeln!(f, "Object {} {{", DebugUri(unmap, header.otype.get()))?;
+                for (property, atom) in properties {
+                    write!(
+                        f,
+                        "{:2$}{}: ",
+                        "",
+                        DebugUri(unmap, property.key.get()),
+                        (indent + 1) * 4
+                    )?;
+                    atom.debug_fmt_indented(unmap, f, indent + 1)?;
+                    writeln!(f)?;
+                }
+                write!(f, "{:1$}}}", "", indent * 4)
+            } else if is(sequence::Sequence::uri()) {
+                self.debug_sequence(unmap, f, &mut reader, indent)
+            } else {
+                match uri {
+                    Some(uri) => write!(f, "<{}>", uri.to_string_lossy())?,
+                    None => write!(f, "URID {}", self.header.urid().get())?,
+                }
+                write!(f, " ({} bytes)", self.header.size_of_body())
+            }
+        }
+    }
+
+    /// Print the elements of a vector.
+    ///
+    /// # Safety
+    ///
+    /// The atom has to be a vector and the reader has to point to its body.
+    unsafe fn debug_vector<U: Unmap>(
+        &self,
+        unmap: &U,
+        f: &mut fmt::Formatter,
+        reader: &mut SpaceReader,
+    ) -> fmt::Result {
+        let header: &lv2_sys::LV2_Atom_Vector_Body = match reader.next_value() {
+            Ok(header) => header,
+            Err(_) => return write!(f, "Vector(<malformed>)"),
+        };
+        let child_uri = URID::new(header.child_type).and_then(|child_type| unmap.unmap(child_type));
+        let is = |bound: &Uri| child_uri == Some(bound);
+
+        if is(scalar::Int::uri()) {
+            debug_slice::<i32>(f, "Int", reader)
+        } else if is(scalar::Long::uri()) {
+            debug_slice::<i64>(f, "Long", reader)
+        } else if is(scalar::Float::uri()) {
+            debug_slice::<f32>(f, "Float", reader)
+        } else if is(scalar::Double::uri()) {
+            debug_slice::<f64>(f, "Double", reader)
+        } else if is(scalar::Bool::uri()) {
+            debug_slice::<i32>(f, "Bool", reader)
+        } else if is(scalar::AtomURID::uri()) {
+            debug_slice::<u32>(f, "URID", reader)
+        } else {
+            write!(
+                f,
+                "Vector<{}> ({} bytes)",
+                DebugUri(unmap, header.child_type),
+                reader.remaining_bytes().len()
+            )
+        }
+    }
+
+    /// Print the events of a sequence.
+    ///
+    /// # Safety
+    ///
+    /// The atom has to be a sequence and the reader has to point to its body.
+    unsafe fn debug_sequence<U: Unmap>(
+        &self,
+        unmap: &U,
+        f: &mut fmt::Formatter,
+        reader: &mut SpaceReader,
+        indent: usize,
+    ) -> fmt::Result {
+        let header: &lv2_sys::LV2_Atom_Sequence_Body = match reader.next_value() {
+            Ok(header) => header,
+            Err(_) => return write!(f, "Sequence(<malformed>)"),
+        };
+        let beats = URID::new(header.unit).and_then(|unit| unmap.unmap(unit))
+            == Some(units::units::Beat::uri());
+
+        writeln!(f, "Sequence [")?;
+        while !reader.remaining_bytes().is_empty() {
+            let event = reader.try_read(|reader| {
+                let stamp: &lv2_sys::LV2_Atom_Event__bindgen_ty_1 = reader.next_value()?;
+                Ok((*stamp, reader.next_atom()?))
+            });
+            let (stamp, atom) = match event {
+                Ok(event) => event,
+                Err(_) => break,
+            };
+
+            write!(f, "{:1$}", "", (indent + 1) * 4)?;
+            if beats {
+                write!(f, "@{} beats: ", stamp.beats)?;
+            } else {
+                write!(f, "@{}: ", stamp.frames)?;
+            }
+            atom.debug_fmt_indented(unmap, f, indent + 1)?;
+            writeln!(f)?;
+        }
+        write!(f, "{:1$}]", "", indent * 4)
+    }
+}
+
+/// The maximal nesting depth printed by [`UnidentifiedAtom::debug_fmt`](struct.UnidentifiedAtom.html#method.debug_fmt), which protects against stack overflows.
+const MAX_DEBUG_DEPTH: usize = 32;
+
+/// Display helper that prints the URI of a URID, or the raw number if it can't be unmapped.
+struct DebugUri<'a, U: Unmap>(&'a U, u32);
+
+impl<'a, U: Unmap> fmt::Display for DebugUri<'a, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match URID::new(self.1).and_then(|urid| self.0.unmap(urid)) {
+            Some(uri) => write!(f, "<{}>", uri.to_string_lossy()),
+            None => write!(f, "{}", self.1),
+        }
+    }
+}
+
+/// Print a single scalar value.
+///
+/// # Safety
+///
+/// The reader has to point to a valid value of type `T`.
+unsafe fn debug_value<T: fmt::Debug + 'static>(
+    f: &mut fmt::Formatter,
+    name: &str,
+    reader: &mut SpaceReader,
+) -> fmt::Result {
+    match reader.next_value::<T>() {
+        Ok(value) => write!(f, "{}({:?})", name, value),
+        Err(_) => write!(f, "{}(<malformed>)", name),
+    }
+}
+
+/// Print the elements of a vector.
+///
+/// # Safety
+///
+/// The reader has to point to a valid slice of type `T`.
+unsafe fn debug_slice<T: fmt::Debug + 'static>(
+    f: &mut fmt::Formatter,
+    name: &str,
+    reader: &mut SpaceReader,
+) -> fmt::Result {
+    match reader.as_slice::<T>() {
+        Ok(slice) => write!(f, "Vector<{}>{:?}", name, slice),
+        Err(_) => write!(f, "Vector<{}>(<malformed>)", name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::atom_prelude::*;
+    use crate::space::AlignedVec;
+    use crate::unidentified::MAX_DEBUG_DEPTH;
+    use urid::*;
+
+    #[test]
+    fn test_from_sys() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+
+        #[repr(C, align(8))]
+        struct CIntAtom {
+            header: lv2_sys::LV2_Atom,
+            body: i32,
+        }
+
+        let c_atom = CIntAtom {
+            header: lv2_sys::LV2_Atom {
+                size: ::core::mem::size_of::<i32>() as u32,
+                type_: urids.int.get(),
+            },
+            body: 42,
+        };
+
+        let atom = unsafe { UnidentifiedAtom::from_sys(&c_atom.header) }.unwrap();
+        assert_eq!(atom.header().urid(), urids.int);
+        assert_eq!(*atom.read(urids.int).unwrap(), c_atom.body);
+
+        let atom = unsafe { UnidentifiedAtom::from_sys_ptr(&c_atom.header) }.unwrap();
+        assert_eq!(*atom.read(urids.int).unwrap(), 42);
+
+        assert!(unsafe { UnidentifiedAtom::from_sys_ptr(::core::ptr::null()) }.is_err());
+
+        // Unaligned atoms are rejected.
+        let mut space = AlignedVec::<AtomHeader>::new_with_capacity(2);
+        let bytes = space.as_bytes_mut();
+        let unaligned = bytes[4..].as_ptr() as *const lv2_sys::LV2_Atom;
+        assert!(matches!(
+            unsafe { UnidentifiedAtom::from_sys_ptr(unaligned) },
+            Err(AtomReadError::AlignmentError(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_aligned_vec() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+
+        #[repr(C, align(8))]
+        struct CIntAtom {
+            header: lv2_sys::LV2_Atom,
+            body: i32,
+        }
+
+        let c_atom = CIntAtom {
+            header: lv2_sys::LV2_Atom {
+                size: ::core::mem::size_of::<i32>() as u32,
+                type_: urids.int.get(),
+            },
+            body: 42,
+        };
+        let atom = unsafe { UnidentifiedAtom::from_sys(&c_atom.header) }.unwrap();
+
+        let vec = atom.to_aligned_vec(1024).unwrap();
+        let copy = unsafe { vec.as_space().read().next_atom() }.unwrap();
+        assert_eq!(*copy.read(urids.int).unwrap(), 42);
+
+        assert_eq!(
+            atom.to_aligned_vec(8).err(),
+            Some(AtomReadError::TooLarge {
+                max_size: 8,
+                requested: 12
+            })
+        );
+
+        // A corrupted header claiming a 4 GB body is rejected without touching the body.
+        let huge_header = lv2_sys::LV2_Atom {
+            size: u32::MAX,
+            type_: urids.chunk.get(),
+        };
+        let mut space = AlignedVec::<AtomHeader>::new_with_capacity(1);
+        space.as_bytes_mut().copy_from_slice(unsafe {
+            ::core::slice::from_raw_parts(
+                &huge_header as *const lv2_sys::LV2_Atom as *const u8,
+                ::core::mem::size_of::<lv2_sys::LV2_Atom>(),
+            )
+        });
+        // Reading the atom fails, since its body is out of bounds...
+        assert!(unsafe { space.as_space().read().next_atom() }.is_err());
+        // ...but a header that has been wrapped without bounds checks still can't cause a huge allocation.
+        let huge = unsafe { UnidentifiedAtom::from_space(space.as_space()) }.unwrap();
+        assert_eq!(
+            huge.to_aligned_vec(1 << 20).err(),
+            Some(AtomReadError::TooLarge {
+                max_size: 1 << 20,
+                requested: u32::MAX as usize + 8
+            })
+        );
+    }
+
+    #[test]
+    fn test_debug_fmt() {
+        struct AtomDebug<'a>(&'a UnidentifiedAtom, &'a HashURIDMapper);
+
+        impl<'a> std::fmt::Debug for AtomDebug<'a> {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                self.0.debug_fmt(self.1, f)
+            }
+        }
+
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let frame: URID<units::units::Frame> = map.map_type().unwrap();
+        let object_type = map.map_str("urn:my-type").unwrap();
+        let key = map.map_str("urn:my-key").unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(64);
+        let raw_space = raw_space.as_space_mut();
+
+        {
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut tuple = cursor.write_atom(urids.tuple).unwrap();
+            tuple.init(urids.int).unwrap().set(42).unwrap();
+            tuple.init(urids.string).unwrap().append("hello").unwrap();
+            tuple
+                .init(urids.vector)
+                .unwrap()
+                .of_type(urids.float)
+                .unwrap()
+                .append(&[1.0, 2.5])
+                .unwrap();
+            {
+                let mut object = tuple
+                    .init(urids.object)
+                    .unwrap()
+                    .write_header(ObjectHeader {
+                        id: None,
+                        otype: object_type,
+                    })
+                    .unwrap();
+                object
+                    .new_property(key, urids.bool)
+                    .unwrap()
+                    .set(1)
+                    .unwrap();
+            }
+            {
+                let mut sequence = tuple
+                    .init(urids.sequence)
+                    .unwrap()
+                    .with_unit(frame)
+                    .unwrap();
+                sequence.new_event(10, urids.long).unwrap().set(-3).unwrap();
+            }
+            // Chunks don't have a special representation.
+            let mut chunk = tuple.init(urids.chunk).unwrap();
+            chunk.write_bytes(&[1, 2, 3]).unwrap();
+        }
+
+        let before = raw_space.as_bytes().to_vec();
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        let output = format!("{:?}", AtomDebug(atom, &map));
+        assert_eq!(
+            output,
+            "Tuple [\n    Int(42)\n    String(\"hello\")\n    Vector<Float>[1.0, 2.5]\n    \
+             Object <urn:my-type> {\n        <urn:my-key>: Bool(true)\n    }\n    \
+             Sequence [\n        @10: Long(-3)\n    ]\n    <http://lv2plug.in/ns/ext/atom#Chunk> (3 bytes)\n]"
+        );
+
+        // Printing never modifies the atom.
+        assert_eq!(before, raw_space.as_bytes());
+
+        // Deeply nested atoms are cut off.
+        let depth = MAX_DEBUG_DEPTH + 8;
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(depth);
+        for (i, header) in raw_space.as_bytes_mut().chunks_mut(8).enumerate() {
+            let size = ((depth - 1 - i) * 8) as u32;
+            header[..4].copy_from_slice(&size.to_ne_bytes());
+            header[4..].copy_from_slice(&urids.tuple.get().to_ne_bytes());
+        }
+        let atom = unsafe { raw_space.as_space().read().next_atom() }.unwrap();
+        let output = format!("{:?}", AtomDebug(atom, &map));
+        assert_eq!(output.matches("Tuple [").count(), MAX_DEBUG_DEPTH + 1);
+        assert_eq!(output.matches("<too deep>").count(), 1);
+    }
 }
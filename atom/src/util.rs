@@ -47,6 +47,12 @@ pub(crate) fn byte_index_to_value_index<T>(size: usize) -> usize {
     }
 }
 
+/// Rounds a size in bytes up to the next multiple of the size of `T`.
+#[inline]
+pub(crate) fn padded_size<T>(size: usize) -> usize {
+    value_index_to_byte_index::<T>(byte_index_to_value_index::<T>(size))
+}
+
 #[inline]
 pub(crate) fn try_padding_for<T: 'static>(data: &[u8]) -> Result<usize, AlignmentError> {
     let value = data.as_ptr().align_offset(::core::mem::align_of::<T>());
@@ -3,21 +3,91 @@ use proc_macro2::Span;
 use syn::Field;
 use syn::{parse_macro_input, Data, DataStruct, Ident};
 use syn::{DeriveInput, Generics, Lifetime};
+use syn::{Lit, Meta, NestedMeta, Type};
 
 struct FeatureCollectionField<'a> {
     identifier: &'a Ident,
+    ty: &'a Type,
+    classes: Vec<Ident>,
 }
 
 impl<'a> FeatureCollectionField<'a> {
     fn from_input_field(input: &'a Field) -> Self {
+        let mut classes = Vec::new();
+        for attribute in input.attrs.iter() {
+            if !attribute.path.is_ident("lv2_feature") {
+                continue;
+            }
+            let list = match attribute.parse_meta() {
+                Ok(Meta::List(list)) => list,
+                _ => panic!(
+                    "Expected a list of attributes, like `#[lv2_feature(class = \"audio\")]`"
+                ),
+            };
+            for item in list.nested.iter() {
+                match item {
+                    NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("class") => {
+                        match &pair.lit {
+                            Lit::Str(class) => classes.push(Self::parse_class(&class.value())),
+                            _ => panic!("The threading class has to be a string literal"),
+                        }
+                    }
+                    _ => panic!("Unknown feature attribute, only `class` is supported"),
+                }
+            }
+        }
+
         FeatureCollectionField {
             identifier: input.ident.as_ref().unwrap(),
+            ty: &input.ty,
+            classes,
         }
     }
 
+    /// Return the feature type if the field is required, i.e. not an `Option`.
+    fn required_feature(&self) -> Option<&'a Type> {
+        let is_optional = match self.ty {
+            Type::Path(path) => match path.path.segments.last() {
+                Some(segment) => segment.ident == "Option",
+                None => false,
+            },
+            _ => false,
+        };
+        if is_optional {
+            None
+        } else {
+            Some(self.ty)
+        }
+    }
+
+    fn parse_class(class: &str) -> Ident {
+        let variant = match class {
+            "discovery" => "Discovery",
+            "instantiation" => "Instantiation",
+            "audio" => "Audio",
+            "other" => "Other",
+            _ => panic!(
+                "Unknown threading class `{}`, expected one of `discovery`, `instantiation`, `audio` or `other`",
+                class
+            ),
+        };
+        Ident::new(variant, Span::call_site())
+    }
+
     fn make_retrieval(&self) -> impl ::quote::ToTokens {
         let identifier = self.identifier;
-        quote! {#identifier: cache.retrieve_feature(class)?,}
+        if self.classes.is_empty() {
+            quote! {#identifier: cache.retrieve_feature(class)?,}
+        } else {
+            let classes = self.classes.iter();
+            quote! {
+                #identifier: if #(class == ThreadingClass::#classes)||* {
+                    cache.retrieve_feature(class)?
+                } else {
+                    None
+                },
+            }
+        }
     }
 }
 
@@ -47,6 +117,10 @@ impl<'a> FeatureCollectionStruct<'a> {
         let struct_name = self.struct_name;
         let generics = self.generics;
         let retrievals = self.fields.iter().map(|field| field.make_retrieval());
+        let required_features = self
+            .fields
+            .iter()
+            .filter_map(|field| field.required_feature());
         // retrieve the first lifetime of the struct, or set it to `'static` if there is none.
         let lifetime = self
             .generics
@@ -57,6 +131,10 @@ impl<'a> FeatureCollectionStruct<'a> {
 
         (quote! {
             impl#generics FeatureCollection<#lifetime> for #struct_name#generics {
+                const REQUIRED_FEATURES: &'static [&'static ::std::ffi::CStr] = &[
+                    #(feature_uri::<#required_features>(),)*
+                ];
+
                 fn from_cache(
                     cache: &mut FeatureCache<#lifetime>,
                     class: ThreadingClass,
@@ -14,6 +14,8 @@ mod port_collection_derive;
 use proc_macro::TokenStream;
 
 /// Generate external symbols for LV2 plugins.
+///
+/// The macro takes a comma-separated list of plugin types. The list may be preceded by declarations of data that is shared between all plugins of the library, for example `#[shared] static WAVETABLE: Vec<f32>;`. Every declaration is turned into a `static` of the type `BundleShared<Vec<f32>>`, which is initialized lazily by the first plugin instance that requests it. The shared data has to be `Send + Sync` since it may be accessed by multiple instances from multiple threads.
 #[proc_macro]
 pub fn lv2_descriptors(input: TokenStream) -> TokenStream {
     lv2_descriptors::lv2_descriptors_impl(input)
@@ -25,7 +27,12 @@ pub fn port_collection_derive(input: TokenStream) -> TokenStream {
     port_collection_derive::port_collection_derive_impl(input)
 }
 
-#[proc_macro_derive(FeatureCollection)]
+/// Implement the `FeatureCollection` trait for a feature struct.
+///
+/// Fields may be restricted to certain threading classes with the `lv2_feature` attribute, for example `#[lv2_feature(class = "audio")]`. Such fields have to be `Option`s and are `None` when the collection is populated for another class.
+///
+/// Fields of the type `Option<F>` are optional: They are `None` if the host doesn't provide the feature, while a missing feature of any other field fails the creation of the collection with a `MissingFeatureError`. All fields that are not `Option`s are listed as required features in `REQUIRED_FEATURES`.
+#[proc_macro_derive(FeatureCollection, attributes(lv2_feature))]
 pub fn feature_collection_derive(input: TokenStream) -> TokenStream {
     feature_collection_derive::feature_collection_derive_impl(input)
 }
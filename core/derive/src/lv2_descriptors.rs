@@ -1,7 +1,7 @@
 use proc_macro::TokenStream;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{parse_macro_input, Result, Token, Type};
+use syn::{parse_macro_input, Attribute, Error, Ident, Result, Token, Type, Visibility};
 
 /// An instance descriptor that should be exported.
 ///
@@ -54,23 +54,85 @@ impl Lv2InstanceDescriptor {
     }
 }
 
+/// Data that is shared between all plugins of the library.
+///
+/// Shared data is declared like a `static` item without an initializer, but prefixed with the
+/// `#[shared]` attribute, for example `#[shared] static TABLE: Vec<f32>;`.
+struct Lv2SharedData {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    data_type: Type,
+}
+
+impl Parse for Lv2SharedData {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        let shared_index = attrs
+            .iter()
+            .position(|attr| attr.path.is_ident("shared"))
+            .ok_or_else(|| Error::new(input.span(), "expected a `#[shared]` attribute"))?;
+        attrs.remove(shared_index);
+
+        let vis = input.parse()?;
+        input.parse::<Token![static]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let data_type = input.parse()?;
+        input.parse::<Token![;]>()?;
+
+        Ok(Self {
+            attrs,
+            vis,
+            ident,
+            data_type,
+        })
+    }
+}
+
+impl Lv2SharedData {
+    /// Create the static item that holds the shared data.
+    fn make_static(&self) -> impl ::quote::ToTokens {
+        let attrs = &self.attrs;
+        let vis = &self.vis;
+        let ident = &self.ident;
+        let data_type = &self.data_type;
+        quote! {
+            #(#attrs)*
+            #vis static #ident: BundleShared<#data_type> = BundleShared::new();
+        }
+    }
+}
+
 /// A collection for instance descriptors.
 ///
 /// The contained instance descriptors are used to create the export function `lv2_descriptor` that
-/// tells the host of a library's plugins.
+/// tells the host of a library's plugins. The list may be preceded by the declarations of shared
+/// data.
 struct Lv2InstanceDescriptorList {
+    shared: Vec<Lv2SharedData>,
     descriptors: Punctuated<Lv2InstanceDescriptor, Token![,]>,
 }
 
 impl Parse for Lv2InstanceDescriptorList {
     fn parse(input: ParseStream) -> Result<Self> {
+        let mut shared = Vec::new();
+        while input.peek(Token![#]) {
+            shared.push(input.parse()?);
+        }
         Ok(Self {
+            shared,
             descriptors: Punctuated::parse_terminated(input)?,
         })
     }
 }
 
 impl Lv2InstanceDescriptorList {
+    /// Create the static items for all shared data.
+    fn make_shared_statics(&self) -> impl Iterator<Item = impl ::quote::ToTokens> + '_ {
+        self.shared.iter().map(Lv2SharedData::make_static)
+    }
+
     /// Implement `PluginInstanceDescriptor` for all plugin instances.
     fn make_instance_descriptor_impls(&self) -> impl Iterator<Item = impl ::quote::ToTokens> + '_ {
         self.descriptors
@@ -115,10 +177,12 @@ impl Lv2InstanceDescriptorList {
 #[inline]
 pub fn lv2_descriptors_impl(input: TokenStream) -> TokenStream {
     let list: Lv2InstanceDescriptorList = parse_macro_input!(input);
+    let shared = list.make_shared_statics();
     let descriptors = list.make_instance_descriptor_impls();
     let export_function = list.make_descriptor_function();
 
     (quote! {
+        #(#shared)*
         #(#descriptors)*
         #export_function
     })
@@ -28,6 +28,21 @@ impl<'a> PortCollectionField<'a> {
         }
     }
 
+    /// Create the description of the port.
+    fn make_port_info(&self) -> impl ::quote::ToTokens {
+        let port_type = self.port_type;
+        let symbol = self.identifier.to_string();
+        let symbol = symbol.trim_start_matches("r#");
+        quote! {
+            PortInfo {
+                symbol: #symbol,
+                class: <#port_type as PortHandle>::CLASS,
+                direction: <#port_type as PortHandle>::DIRECTION,
+                optional: <#port_type as PortHandle>::OPTIONAL,
+            },
+        }
+    }
+
     /// Create the corresponding field declaration line for the raw pointer struct.
     fn make_raw_field_declaration(&self) -> impl ::quote::ToTokens {
         let identifier = self.identifier;
@@ -99,6 +114,7 @@ impl<'a> PortCollectionStruct<'a> {
             .fields
             .iter()
             .map(PortCollectionField::make_connection_from_raw);
+        let port_infos = self.fields.iter().map(PortCollectionField::make_port_info);
         let raw_field_declarations = self
             .fields
             .iter()
@@ -117,6 +133,10 @@ impl<'a> PortCollectionStruct<'a> {
             impl PortCollection for #struct_name {
                 type Cache = #internal_cache_name;
 
+                const PORTS: &'static [PortInfo] = &[
+                    #(#port_infos)*
+                ];
+
                 #[inline]
                 unsafe fn from_connections(connections: &<Self as PortCollection>::Cache, sample_count: u32) -> Option<Self> {
                     Some(
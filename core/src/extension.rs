@@ -9,7 +9,7 @@
 //! This is a complete example on how to create an extension and implement it for a plugin:
 //!
 //! ```
-//! use lv2_core::extension::ExtensionDescriptor;
+//! use lv2_core::extension::{downcast_extension, ExtensionDescriptor};
 //! use lv2_core::prelude::*;
 //! use urid::*;
 //! use std::any::Any;
@@ -111,7 +111,7 @@
 //! let mut plugin = MyPlugin::new(&plugin_info, &mut ()).unwrap();
 //!
 //! let extension = MyPlugin::extension_data(MyExtensionDescriptor::<MyPlugin>::uri())
-//!     .and_then(|interface| interface.downcast_ref::<MyExtensionInterface>())
+//!     .and_then(downcast_extension::<MyExtensionInterface>)
 //!     .unwrap();
 //!
 //! unsafe { (extension.add_number)(&mut plugin as *mut _ as *mut c_void, 42) };
@@ -132,6 +132,16 @@ pub trait ExtensionDescriptor: UriBound {
     const INTERFACE: &'static Self::ExtensionInterface;
 }
 
+/// Downcast the return value of a plugin's `extension_data` function to a known interface type.
+///
+/// The interfaces are returned as `&'static dyn Any`, since the plugin doesn't know which one the host requests. If you know which extension you've asked for, e.g. in tests or when you host a plugin yourself, you can use this function to retrieve the concrete [`ExtensionInterface`](trait.ExtensionDescriptor.html#associatedtype.ExtensionInterface). It returns `None` if the interface has a different type.
+///
+/// [For a usage example, see the module documentation.](index.html)
+#[inline]
+pub fn downcast_extension<I: 'static>(data: &'static dyn Any) -> Option<&'static I> {
+    data.downcast_ref::<I>()
+}
+
 /// Generate the body of a plugin's `extension_data` function.
 ///
 /// This macro takes a URI as it's first argument, followed by a list of extension descriptors. This will
@@ -139,10 +149,12 @@ pub trait ExtensionDescriptor: UriBound {
 ///
 /// The generated statement returns a value of `Option<&'static dyn std::any::Any>`.
 ///
+/// Any number of descriptors may be listed, for example `match_extensions!(uri, StateDescriptor<Self>, WorkerDescriptor<Self>, OptionsDescriptor<Self>)`. They are checked in the given order and the interface of the first matching descriptor is returned. The URIs of the listed descriptors must be unique, since a descriptor that shares its URI with a previous one would never be matched.
+///
 /// See the documentation of the `extension` module for more information on how to use this macro.
 #[macro_export]
 macro_rules! match_extensions {
-    ($uri:expr, $($descriptor:ty),*) => {
+    ($uri:expr, $($descriptor:ty),* $(,)?) => {
         match ($uri).to_bytes_with_nul() {
             $(
                 <$descriptor as UriBound>::URI => Some(<$descriptor as ExtensionDescriptor>::INTERFACE as &'static dyn ::std::any::Any),
@@ -153,3 +165,95 @@ macro_rules! match_extensions {
 }
 
 pub use crate::match_extensions;
+
+#[cfg(test)]
+mod tests {
+    use crate::extension::*;
+    use crate::prelude::*;
+    use std::marker::PhantomData;
+    use urid::*;
+
+    struct FirstDescriptor<P: Plugin> {
+        _plugin: PhantomData<P>,
+    }
+
+    unsafe impl<P: Plugin> UriBound for FirstDescriptor<P> {
+        const URI: &'static [u8] = b"urn:extension:first\0";
+    }
+
+    impl<P: Plugin> ExtensionDescriptor for FirstDescriptor<P> {
+        type ExtensionInterface = u32;
+
+        const INTERFACE: &'static u32 = &1;
+    }
+
+    struct SecondDescriptor<P: Plugin> {
+        _plugin: PhantomData<P>,
+    }
+
+    unsafe impl<P: Plugin> UriBound for SecondDescriptor<P> {
+        const URI: &'static [u8] = b"urn:extension:second\0";
+    }
+
+    impl<P: Plugin> ExtensionDescriptor for SecondDescriptor<P> {
+        type ExtensionInterface = f32;
+
+        const INTERFACE: &'static f32 = &2.0;
+    }
+
+    struct ThirdDescriptor<P: Plugin> {
+        _plugin: PhantomData<P>,
+    }
+
+    unsafe impl<P: Plugin> UriBound for ThirdDescriptor<P> {
+        const URI: &'static [u8] = b"urn:extension:third\0";
+    }
+
+    impl<P: Plugin> ExtensionDescriptor for ThirdDescriptor<P> {
+        type ExtensionInterface = &'static str;
+
+        const INTERFACE: &'static &'static str = &"third";
+    }
+
+    #[uri("urn:extension:plugin")]
+    struct ExtendedPlugin;
+
+    impl Plugin for ExtendedPlugin {
+        type Ports = ();
+        type InitFeatures = ();
+        type AudioFeatures = ();
+
+        fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+            Some(Self)
+        }
+
+        fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+
+        fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+            match_extensions!(
+                uri,
+                FirstDescriptor<Self>,
+                SecondDescriptor<Self>,
+                ThirdDescriptor<Self>,
+            )
+        }
+    }
+
+    #[test]
+    fn test_match_multiple_extensions() {
+        let first = ExtendedPlugin::extension_data(FirstDescriptor::<ExtendedPlugin>::uri())
+            .and_then(downcast_extension::<u32>);
+        assert_eq!(first, Some(&1));
+
+        let second = ExtendedPlugin::extension_data(SecondDescriptor::<ExtendedPlugin>::uri())
+            .and_then(downcast_extension::<f32>);
+        assert_eq!(second, Some(&2.0));
+
+        let third = ExtendedPlugin::extension_data(ThirdDescriptor::<ExtendedPlugin>::uri())
+            .and_then(downcast_extension::<&'static str>);
+        assert_eq!(third, Some(&"third"));
+
+        let unknown = Uri::from_bytes_with_nul(b"urn:extension:unknown\0").unwrap();
+        assert!(ExtendedPlugin::extension_data(unknown).is_none());
+    }
+}
@@ -44,6 +44,15 @@ impl<'a> FeatureCache<'a> {
         self.internal.contains_key(T::uri())
     }
 
+    /// Iterate over the URIs of all features that haven't been retrieved yet.
+    ///
+    /// Retrieved features are removed from the cache and therefore, this iterator only yields features that haven't been consumed by a `FeatureCollection` or a call to [`retrieve_feature`](#method.retrieve_feature). This is useful to log features that the host provides, but that the plugin doesn't recognize.
+    ///
+    /// The cache is only borrowed and still contains all features afterwards. Only the URIs are yielded, since the features themselves may still be retrieved from the cache.
+    pub fn remaining(&self) -> impl Iterator<Item = &'a CStr> + '_ {
+        self.internal.keys().copied()
+    }
+
     /// Try to retrieve a feature.
     ///
     /// If the feature is not found, this method will return `None`. Since the resulting feature object may have mutable access to the raw data, it will be removed from the cache to avoid aliasing.
@@ -4,10 +4,12 @@ use urid::{Uri, UriBound};
 mod cache;
 mod core_features;
 mod descriptor;
+mod raw;
 
 pub use cache::FeatureCache;
 pub use core_features::*;
 pub use descriptor::FeatureDescriptor;
+pub use raw::RawFeature;
 
 use std::ffi::c_void;
 
@@ -67,6 +69,13 @@ impl std::fmt::Display for MissingFeatureError {
     }
 }
 
+/// Return the URI of a feature in a constant context.
+///
+/// This is used by the `FeatureCollection` derive macro to list the required features of a collection.
+pub const fn feature_uri<F: Feature>() -> &'static Uri {
+    unsafe { Uri::from_bytes_with_nul_unchecked(F::URI) }
+}
+
 /// Convenience trait for feature collections.
 ///
 /// The feature cache is only for temporary use; Once a feature is retrieved, it is removed from the cache. Therefore you need a way to properly store features.
@@ -83,7 +92,26 @@ impl std::fmt::Display for MissingFeatureError {
 ///         live: IsLive,
 ///         hardrt: Option<HardRTCapable>,
 ///     }
+///
+/// If the feature sets of two threading classes overlap, one collection can serve both of them. Fields that should only be populated in some classes are marked with the `lv2_feature` attribute. These fields have to be `Option`s, and are `None` if the collection is populated for a class that isn't listed:
+///
+///     use lv2_core::plugin::*;
+///     use lv2_core::feature::*;
+///
+///     #[derive(FeatureCollection)]
+///     struct MyCollection {
+///         live: IsLive,
+///         #[lv2_feature(class = "audio")]
+///         hardrt: Option<HardRTCapable>,
+///         #[lv2_feature(class = "instantiation", class = "other")]
+///         in_place_broken: Option<InPlaceBroken>,
+///     }
+///
+/// The derived implementation also lists the URIs of all non-`Option` fields in [`REQUIRED_FEATURES`](#associatedconstant.REQUIRED_FEATURES), which tools can use to generate the `lv2:requiredFeature` statements of a plugin's `.ttl` file.
 pub trait FeatureCollection<'a>: Sized + 'a {
+    /// The URIs of the features that have to be provided by the host to populate the collection.
+    const REQUIRED_FEATURES: &'static [&'static Uri] = &[];
+
     /// Populate a collection with features from the cache for the given threading class.
     fn from_cache(
         cache: &mut FeatureCache<'a>,
@@ -106,7 +134,7 @@ impl<'a> FeatureCollection<'a> for () {
 mod tests {
     use crate::feature::FeatureCache;
     use crate::{feature::*, plugin::*};
-    use std::ffi::c_void;
+    use std::ffi::{c_void, CStr};
     use std::os::raw::c_char;
     use std::pin::Pin;
     use urid::UriBound;
@@ -150,6 +178,15 @@ mod tests {
         _c: crate::feature::IsLive,
     }
 
+    #[derive(FeatureCollection)]
+    struct ClassTaggedCollection<'a> {
+        a: FeatureA<'a>,
+        #[lv2_feature(class = "audio")]
+        b: Option<FeatureB<'a>>,
+        #[lv2_feature(class = "instantiation")]
+        c: Option<crate::feature::IsLive>,
+    }
+
     struct FeatureTestSetting<'a> {
         pub data_a: Pin<Box<i32>>,
         pub _feature_a_sys: Pin<Box<::sys::LV2_Feature>>,
@@ -267,6 +304,28 @@ mod tests {
         assert!(feature_a_found && feature_b_found);
     }
 
+    #[test]
+    fn test_remaining_features() {
+        let setting = FeatureTestSetting::new();
+        let mut features_cache = setting.features_cache;
+
+        let mut remaining: Vec<&CStr> = features_cache.remaining().collect();
+        remaining.sort();
+        assert_eq!(remaining, [IsLive::uri(), FeatureA::uri(), FeatureB::uri()]);
+
+        let _: FeatureB = features_cache
+            .retrieve_feature(ThreadingClass::Other)
+            .unwrap();
+
+        let mut remaining: Vec<&CStr> = features_cache.remaining().collect();
+        remaining.sort();
+        assert_eq!(remaining, [IsLive::uri(), FeatureA::uri()]);
+
+        // Iterating doesn't consume the features.
+        assert!(features_cache.contains::<FeatureA>());
+        assert!(features_cache.contains::<IsLive>());
+    }
+
     #[test]
     fn test_feature_collection() {
         // Construct the setting.
@@ -277,4 +336,75 @@ mod tests {
         assert_eq!(*cache.a.number, *setting.data_a);
         assert_eq!(*cache.b.number, *setting.data_b);
     }
+
+    #[test]
+    fn test_class_tagged_feature_collection() {
+        let setting = FeatureTestSetting::new();
+        let mut features_cache = setting.features_cache;
+        let collection =
+            ClassTaggedCollection::from_cache(&mut features_cache, ThreadingClass::Audio).unwrap();
+        assert_eq!(*collection.a.number, *setting.data_a);
+        assert_eq!(*collection.b.unwrap().number, *setting.data_b);
+        assert!(collection.c.is_none());
+        // Features of excluded fields are left in the cache.
+        assert!(features_cache.contains::<crate::feature::IsLive>());
+
+        let setting = FeatureTestSetting::new();
+        let mut features_cache = setting.features_cache;
+        let collection =
+            ClassTaggedCollection::from_cache(&mut features_cache, ThreadingClass::Instantiation)
+                .unwrap();
+        assert_eq!(*collection.a.number, *setting.data_a);
+        assert!(collection.b.is_none());
+        assert!(collection.c.is_some());
+        assert!(features_cache.contains::<FeatureB>());
+    }
+
+    #[derive(FeatureCollection)]
+    struct PartiallyRequiredCollection<'a> {
+        _a: FeatureA<'a>,
+        _b: Option<FeatureB<'a>>,
+    }
+
+    #[test]
+    fn test_optional_features() {
+        let setting = FeatureTestSetting::new();
+        let features_list: &[*const sys::LV2_Feature] =
+            &[setting._feature_a_sys.as_ref().get_ref(), std::ptr::null()];
+
+        // The required feature is present and the optional one is missing.
+        let mut features_cache = unsafe { FeatureCache::from_raw(features_list.as_ptr()) };
+        let collection =
+            PartiallyRequiredCollection::from_cache(&mut features_cache, ThreadingClass::Other)
+                .unwrap();
+        assert_eq!(*collection._a.number, *setting.data_a);
+        assert!(collection._b.is_none());
+
+        // The optional feature is present and the required one is missing.
+        let features_list: &[*const sys::LV2_Feature] =
+            &[setting._feature_b_sys.as_ref().get_ref(), std::ptr::null()];
+        let mut features_cache = unsafe { FeatureCache::from_raw(features_list.as_ptr()) };
+        let error =
+            PartiallyRequiredCollection::from_cache(&mut features_cache, ThreadingClass::Other)
+                .err()
+                .unwrap();
+        assert_eq!(error.uri, FeatureA::uri());
+    }
+
+    #[test]
+    fn test_required_features() {
+        assert_eq!(
+            PartiallyRequiredCollection::REQUIRED_FEATURES,
+            &[FeatureA::uri()]
+        );
+        assert_eq!(
+            Collection::REQUIRED_FEATURES,
+            &[
+                FeatureA::uri(),
+                FeatureB::uri(),
+                crate::feature::IsLive::uri()
+            ]
+        );
+        assert!(<() as FeatureCollection>::REQUIRED_FEATURES.is_empty());
+    }
 }
@@ -0,0 +1,80 @@
+//! A feature that provides the uninterpreted data pointer of the host.
+use crate::feature::*;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use urid::UriBound;
+
+/// A feature that hands out the raw data pointer provided by the host.
+///
+/// This is meant for experimental extensions that aren't wrapped by Rust-LV2 yet: The data pointer isn't interpreted in any way and it's up to you to cast and dereference it. The URI of the feature is provided by the type parameter `U`, which is usually a marker type declared with the `uri` attribute:
+///
+///     use lv2_core::feature::*;
+///     use lv2_core::plugin::*;
+///     use urid::*;
+///
+///     #[uri("urn:my-extension")]
+///     struct MyExtension;
+///
+///     #[derive(FeatureCollection)]
+///     struct Features {
+///         my_extension: Option<RawFeature<MyExtension>>,
+///     }
+///
+/// The feature can be used in every threading class.
+pub struct RawFeature<U: UriBound> {
+    data: *const c_void,
+    uri: PhantomData<U>,
+}
+
+unsafe impl<U: UriBound> UriBound for RawFeature<U> {
+    const URI: &'static [u8] = U::URI;
+}
+
+unsafe impl<U: UriBound> Feature for RawFeature<U> {
+    unsafe fn from_feature_ptr(feature: *const c_void, _: ThreadingClass) -> Option<Self> {
+        Some(Self {
+            data: feature,
+            uri: PhantomData,
+        })
+    }
+}
+
+impl<U: UriBound> RawFeature<U> {
+    /// Return the data pointer provided by the host.
+    ///
+    /// The pointer may be null, depending on the feature.
+    pub fn data(&self) -> *const c_void {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::feature::*;
+    use std::ffi::c_void;
+    use std::os::raw::c_char;
+    use urid::*;
+
+    #[uri("urn:lv2Feature:experimental")]
+    struct Experimental;
+
+    #[test]
+    fn test_raw_feature() {
+        let mut data: i32 = 42;
+        let feature = ::sys::LV2_Feature {
+            URI: Experimental::URI.as_ptr() as *const c_char,
+            data: &mut data as *mut i32 as *mut c_void,
+        };
+        let features_list: &[*const sys::LV2_Feature] = &[&feature, std::ptr::null()];
+        let mut cache = unsafe { FeatureCache::from_raw(features_list.as_ptr()) };
+
+        assert_eq!(
+            RawFeature::<Experimental>::uri().to_bytes_with_nul(),
+            Experimental::URI
+        );
+
+        let raw: RawFeature<Experimental> = cache.retrieve_feature(ThreadingClass::Audio).unwrap();
+        assert_eq!(raw.data(), &data as *const i32 as *const c_void);
+        assert_eq!(unsafe { *(raw.data() as *const i32) }, 42);
+    }
+}
@@ -0,0 +1,166 @@
+//! Sample-accurate processing of audio blocks between events.
+//!
+//! A common idiom in plugins is to iterate over the events of a cycle and to render the output up to the time of each event before the event is handled. The [`BlockProcessor`](trait.BlockProcessor.html) trait and the [`process_blocks`](fn.process_blocks.html) function implement this loop, so plugins only have to describe how a block is rendered and how an event is handled.
+
+/// A processor that renders audio in blocks and handles events between them.
+///
+/// Implementors are driven by [`process_blocks`](fn.process_blocks.html), which splits the output buffer at the time stamp of every event.
+pub trait BlockProcessor {
+    /// The type of events that split the blocks.
+    type Event;
+
+    /// The maximal length of a block, in frames.
+    ///
+    /// Longer blocks are split even if there is no event in between, which is useful if some state is updated at a fixed control rate. Defaults to `usize::MAX`, which means that blocks are only split at events.
+    const MAX_BLOCK_SIZE: usize = usize::MAX;
+
+    /// Render a block of output.
+    ///
+    /// `output` is the part of the output buffer covered by this block and `offset` is the index of its first frame within the complete buffer. Blocks are never empty.
+    fn process_block(&mut self, output: &mut [f32], offset: usize);
+
+    /// Handle an event.
+    ///
+    /// This is called after every block that precedes the event's time stamp has been rendered.
+    fn handle_event(&mut self, event: Self::Event);
+}
+
+/// Render the output buffer in blocks, split at the time stamps of the given events.
+///
+/// The events are expected to be given in chronological order, with time stamps as frame offsets within the output buffer. Time stamps that lie before the previous event are treated as if they were simultaneous to it and time stamps beyond the end of the buffer are treated as if they were at the end of it, so every event is handled exactly once.
+pub fn process_blocks<P, I>(processor: &mut P, output: &mut [f32], events: I)
+where
+    P: BlockProcessor + ?Sized,
+    I: IntoIterator<Item = (usize, P::Event)>,
+{
+    let mut offset: usize = 0;
+    for (time_stamp, event) in events {
+        let time_stamp = time_stamp.max(offset).min(output.len());
+        render(processor, output, offset, time_stamp);
+        offset = time_stamp;
+        processor.handle_event(event);
+    }
+    render(processor, output, offset, output.len());
+}
+
+/// Render the frames from `start` to `end`, respecting the maximal block size.
+fn render<P: BlockProcessor + ?Sized>(
+    processor: &mut P,
+    output: &mut [f32],
+    mut start: usize,
+    end: usize,
+) {
+    let max_block_size = P::MAX_BLOCK_SIZE.max(1);
+    while start < end {
+        let block_end = start + (end - start).min(max_block_size);
+        processor.process_block(&mut output[start..block_end], start);
+        start = block_end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plugin::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Call {
+        Block(usize, usize),
+        Event(u8),
+    }
+
+    #[derive(Default)]
+    struct MockProcessor {
+        calls: Vec<Call>,
+        value: f32,
+    }
+
+    impl BlockProcessor for MockProcessor {
+        type Event = u8;
+
+        fn process_block(&mut self, output: &mut [f32], offset: usize) {
+            self.calls.push(Call::Block(offset, output.len()));
+            for frame in output.iter_mut() {
+                *frame = self.value;
+            }
+        }
+
+        fn handle_event(&mut self, event: u8) {
+            self.calls.push(Call::Event(event));
+            self.value = event as f32;
+        }
+    }
+
+    struct ControlRateProcessor(Vec<(usize, usize)>);
+
+    impl BlockProcessor for ControlRateProcessor {
+        type Event = ();
+        const MAX_BLOCK_SIZE: usize = 4;
+
+        fn process_block(&mut self, output: &mut [f32], offset: usize) {
+            self.0.push((offset, output.len()));
+        }
+
+        fn handle_event(&mut self, _: ()) {}
+    }
+
+    #[test]
+    fn test_block_boundaries() {
+        let mut processor = MockProcessor::default();
+        let mut output = [-1.0f32; 16];
+
+        process_blocks(
+            &mut processor,
+            &mut output,
+            vec![(0, 1), (4, 2), (4, 3), (10, 4)],
+        );
+
+        assert_eq!(
+            processor.calls,
+            vec![
+                Call::Event(1),
+                Call::Block(0, 4),
+                Call::Event(2),
+                Call::Event(3),
+                Call::Block(4, 6),
+                Call::Event(4),
+                Call::Block(10, 6),
+            ]
+        );
+        assert_eq!(&output[0..4], &[1.0; 4]);
+        assert_eq!(&output[4..10], &[3.0; 6]);
+        assert_eq!(&output[10..16], &[4.0; 6]);
+    }
+
+    #[test]
+    fn test_block_boundaries_out_of_range() {
+        let mut processor = MockProcessor::default();
+        let mut output = [-1.0f32; 8];
+
+        process_blocks(&mut processor, &mut output, vec![(6, 1), (2, 2), (20, 3)]);
+
+        assert_eq!(
+            processor.calls,
+            vec![
+                Call::Block(0, 6),
+                Call::Event(1),
+                Call::Event(2),
+                Call::Block(6, 2),
+                Call::Event(3),
+            ]
+        );
+
+        let mut processor = MockProcessor::default();
+        process_blocks(&mut processor, &mut output, Vec::new());
+        assert_eq!(processor.calls, vec![Call::Block(0, 8)]);
+    }
+
+    #[test]
+    fn test_max_block_size() {
+        let mut processor = ControlRateProcessor(Vec::new());
+        let mut output = [0.0f32; 10];
+
+        process_blocks(&mut processor, &mut output, vec![(5, ())]);
+
+        assert_eq!(processor.0, vec![(0, 4), (4, 1), (5, 4), (9, 1)]);
+    }
+}
@@ -1,7 +1,16 @@
 //! Types to create plugins.
+mod block;
 pub(crate) mod info;
+mod shared;
+#[cfg(feature = "test-utils")]
+mod test_utils;
+pub mod ttl;
 
+pub use block::{process_blocks, BlockProcessor};
 pub use info::PluginInfo;
+pub use shared::BundleShared;
+#[cfg(feature = "test-utils")]
+pub use test_utils::{TestInstance, TestInstanceBuilder};
 
 #[cfg(feature = "lv2-core-derive")]
 pub use lv2_core_derive::*;
@@ -39,6 +48,16 @@ pub trait Plugin: UriBound + Sized + Send + Sync + 'static {
     /// If a host feature is missing, the plugin creation simply fails and your plugin host will tell you so. However, this collection may only contain features that are usable in the "Audio" thread class. Otherwise, the backend may panic during initialization. Please consult each feature's documentation.
     type AudioFeatures: FeatureCollection<'static>;
 
+    /// The minor version of the plugin.
+    ///
+    /// This corresponds to the `lv2:minorVersion` property of the plugin's description and may be used by tools that generate it. Hosts consider a plugin with a higher minor version as a compatible replacement of the same plugin with a lower version. Defaults to 0.
+    const MINOR_VERSION: u32 = 0;
+
+    /// The micro version of the plugin.
+    ///
+    /// This corresponds to the `lv2:microVersion` property of the plugin's description and may be used by tools that generate it. Micro versions are reserved for changes that don't affect the plugin's interface, like bug fixes. Defaults to 0.
+    const MICRO_VERSION: u32 = 0;
+
     /// Create a new plugin instance.
     ///
     /// This method only creates an instance of the plugin, it does not reset or set up it's internal state. This is done by the `activate` method.
@@ -257,6 +276,16 @@ impl<T: Plugin> PluginInstance<T> {
         }
     }
 
+    /// Create a builder for a standalone instance of the plugin.
+    ///
+    /// The instance is created and driven by the same functions a host would use, but features and port buffers are passed as references. This is meant for unit tests of plugins and extensions.
+    ///
+    /// This method is only available if the `test-utils` feature is enabled.
+    #[cfg(feature = "test-utils")]
+    pub fn test_builder<'a>() -> TestInstanceBuilder<'a, T> {
+        TestInstanceBuilder::new()
+    }
+
     /// Retrieve the internal plugin.
     pub fn plugin_handle(&mut self) -> &mut T {
         &mut self.instance
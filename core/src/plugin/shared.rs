@@ -0,0 +1,95 @@
+//! Data that is shared by all plugin instances of a bundle.
+use crate::plugin::PluginInfo;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Lazily initialized data that is shared between all plugins and instances of a bundle.
+///
+/// A plugin library is only loaded once per process, no matter how many plugins it exports and how many instances of these plugins the host creates. A `static` of this type is therefore initialized once per bundle: The first plugin instance that calls [`get_or_init`](#method.get_or_init) from its `new` method runs the initialization function and every instance created afterwards receives a reference to the same data.
+///
+/// Usually, shared data is declared in the [`lv2_descriptors`](../macro.lv2_descriptors.html) macro by prefixing a `static` item with the `#[shared]` attribute, but it may also be declared manually:
+///
+/// ```
+/// use lv2_core::prelude::*;
+///
+/// static WAVETABLE: BundleShared<Vec<f32>> = BundleShared::new();
+/// ```
+///
+/// # Thread safety
+///
+/// Hosts may instantiate plugins from different threads at the same time and the instances may run in parallel. The shared data is therefore required to be `Send + Sync` and it's only accessible via shared references. If instances need to modify shared data, the data has to provide its own synchronization, for example with a `Mutex` or atomics, and modifications must not block when they are done in the audio threading class.
+///
+/// Initialization is guaranteed to happen exactly once: If several instances are created concurrently, all but one of them block until the initialization function has finished. The initialization function is called in the "Instantiation" threading class and may therefore allocate memory and read files from the bundle.
+pub struct BundleShared<T: Send + Sync> {
+    cell: OnceLock<(PathBuf, T)>,
+}
+
+impl<T: Send + Sync> BundleShared<T> {
+    /// Create new, uninitialized shared data.
+    pub const fn new() -> Self {
+        Self {
+            cell: OnceLock::new(),
+        }
+    }
+
+    /// Get the shared data or initialize it if this hasn't happened yet.
+    ///
+    /// The initialization function receives the path of the bundle, as reported by the plugin info.
+    pub fn get_or_init<F: FnOnce(&Path) -> T>(&self, plugin_info: &PluginInfo, init: F) -> &T {
+        &self
+            .cell
+            .get_or_init(|| {
+                let bundle_path = plugin_info.bundle_path().to_path_buf();
+                let data = init(&bundle_path);
+                (bundle_path, data)
+            })
+            .1
+    }
+
+    /// Get the shared data, if it has already been initialized.
+    pub fn get(&self) -> Option<&T> {
+        self.cell.get().map(|(_, data)| data)
+    }
+
+    /// Get the path of the bundle, if the shared data has already been initialized.
+    pub fn bundle_path(&self) -> Option<&Path> {
+        self.cell.get().map(|(path, _)| path.as_path())
+    }
+
+    /// Check whether the shared data has already been initialized.
+    pub fn is_initialized(&self) -> bool {
+        self.cell.get().is_some()
+    }
+}
+
+impl<T: Send + Sync> Default for BundleShared<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plugin::*;
+    use std::path::Path;
+    use urid::*;
+
+    #[test]
+    fn test_bundle_shared() {
+        let shared: BundleShared<usize> = BundleShared::new();
+        assert!(!shared.is_initialized());
+        assert_eq!(shared.get(), None);
+        assert_eq!(shared.bundle_path(), None);
+
+        let uri = Uri::from_bytes_with_nul(b"urn:shared\0").unwrap();
+        let info = PluginInfo::new(uri, Path::new("/usr/lib/lv2/shared.lv2/"), 44100.0);
+
+        assert_eq!(*shared.get_or_init(&info, |path| path.iter().count()), 5);
+        assert_eq!(*shared.get_or_init(&info, |_| panic!()), 5);
+        assert_eq!(shared.get(), Some(&5));
+        assert_eq!(
+            shared.bundle_path(),
+            Some(Path::new("/usr/lib/lv2/shared.lv2/"))
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Utilities to host a plugin in unit tests.
+use crate::plugin::*;
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// Builder for a standalone plugin instance, created by [`PluginInstance::test_builder`](struct.PluginInstance.html#method.test_builder).
+///
+/// The builder collects the information a host would pass to the plugin and instantiates it via the same functions a real host would use. Host features are passed by reference and have to outlive the instance.
+pub struct TestInstanceBuilder<'a, T: Plugin> {
+    sample_rate: f64,
+    bundle_path: CString,
+    features: Vec<*const sys::LV2_Feature>,
+    lifetime: PhantomData<&'a sys::LV2_Feature>,
+    plugin: PhantomData<T>,
+}
+
+impl<'a, T: Plugin> TestInstanceBuilder<'a, T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            sample_rate: 44100.0,
+            bundle_path: CString::default(),
+            features: Vec::new(),
+            lifetime: PhantomData,
+            plugin: PhantomData,
+        }
+    }
+
+    /// Set the sample rate of the instance. Defaults to 44100 Hz.
+    pub fn sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Set the bundle path of the instance. Defaults to an empty path.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the path contains a null byte.
+    pub fn bundle_path(mut self, bundle_path: &str) -> Self {
+        self.bundle_path = CString::new(bundle_path).expect("Bundle path contains a null byte");
+        self
+    }
+
+    /// Provide a host feature to the instance.
+    pub fn feature(mut self, feature: &'a sys::LV2_Feature) -> Self {
+        self.features.push(feature);
+        self
+    }
+
+    /// Instantiate the plugin.
+    ///
+    /// Returns `None` if the instantiation failed, for example because a required feature is missing or because the plugin's `new` method returned `None`.
+    pub fn build(mut self) -> Option<TestInstance<'a, T>> {
+        let descriptor = sys::LV2_Descriptor {
+            URI: T::URI.as_ptr() as *const c_char,
+            instantiate: None,
+            connect_port: None,
+            activate: None,
+            run: None,
+            deactivate: None,
+            cleanup: None,
+            extension_data: None,
+        };
+        self.features.push(std::ptr::null());
+
+        let handle = unsafe {
+            PluginInstance::<T>::instantiate(
+                &descriptor,
+                self.sample_rate,
+                self.bundle_path.as_ptr(),
+                self.features.as_ptr(),
+            )
+        };
+        NonNull::new(handle as *mut PluginInstance<T>).map(|instance| TestInstance {
+            instance,
+            lifetime: PhantomData,
+        })
+    }
+}
+
+/// A plugin instance that is hosted by a unit test.
+///
+/// The instance is cleaned up when it's dropped. Port buffers are connected via references, which have to outlive the instance just like the host features.
+pub struct TestInstance<'a, T: Plugin> {
+    instance: NonNull<PluginInstance<T>>,
+    lifetime: PhantomData<&'a mut ()>,
+}
+
+impl<'a, T: Plugin> TestInstance<'a, T> {
+    /// Connect a buffer to the port with the given index.
+    ///
+    /// The type of the buffer has to match the type of the port, for example `f32` for control ports or `[f32]` for audio ports.
+    pub fn connect_port<D: ?Sized>(&mut self, index: u32, data: &'a mut D) {
+        unsafe {
+            PluginInstance::<T>::connect_port(self.handle(), index, data as *mut D as *mut c_void)
+        }
+    }
+
+    /// Call the plugin's `activate` method.
+    pub fn activate(&mut self) {
+        unsafe { PluginInstance::<T>::activate(self.handle()) }
+    }
+
+    /// Call the plugin's `deactivate` method.
+    pub fn deactivate(&mut self) {
+        unsafe { PluginInstance::<T>::deactivate(self.handle()) }
+    }
+
+    /// Call the plugin's `run` method.
+    ///
+    /// The method isn't called if not all required ports are connected.
+    ///
+    /// # Safety
+    ///
+    /// The plugin interprets the connected buffers according to the types of its ports. Therefore, the type of every connected buffer has to match the type of its port and buffers of audio and CV ports need to hold at least `sample_count` frames.
+    pub unsafe fn run(&mut self, sample_count: u32) {
+        PluginInstance::<T>::run(self.handle(), sample_count)
+    }
+
+    /// Retrieve the plugin.
+    pub fn plugin(&mut self) -> &mut T {
+        self.instance().plugin_handle()
+    }
+
+    /// Retrieve the plugin instance.
+    pub fn instance(&mut self) -> &mut PluginInstance<T> {
+        unsafe { self.instance.as_mut() }
+    }
+
+    /// Retrieve the raw handle of the instance.
+    ///
+    /// This handle can be passed to the extern functions of extension interfaces.
+    pub fn handle(&mut self) -> LV2_Handle {
+        self.instance.as_ptr() as LV2_Handle
+    }
+}
+
+impl<'a, T: Plugin> Drop for TestInstance<'a, T> {
+    fn drop(&mut self) {
+        unsafe { PluginInstance::<T>::cleanup(self.handle()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::feature::IsLive;
+    use crate::prelude::*;
+    use std::ffi::c_void;
+    use urid::*;
+
+    #[derive(FeatureCollection)]
+    struct Features {
+        _live: IsLive,
+    }
+
+    #[derive(PortCollection)]
+    struct Ports {
+        gain: InputPort<Control>,
+        input: InputPort<Audio>,
+        output: OutputPort<Audio>,
+    }
+
+    #[uri("urn:test-utils:amp")]
+    struct Amp {
+        sample_rate: f64,
+        active: bool,
+    }
+
+    impl Plugin for Amp {
+        type Ports = Ports;
+        type InitFeatures = Features;
+        type AudioFeatures = ();
+
+        fn new(plugin_info: &PluginInfo, _: &mut Features) -> Option<Self> {
+            Some(Self {
+                sample_rate: plugin_info.sample_rate(),
+                active: false,
+            })
+        }
+
+        fn activate(&mut self, _: &mut Features) {
+            self.active = true;
+        }
+
+        fn run(&mut self, ports: &mut Ports, _: &mut (), _: u32) {
+            for (input, output) in Iterator::zip(ports.input.iter(), ports.output.iter_mut()) {
+                *output = *input * *ports.gain;
+            }
+        }
+    }
+
+    #[test]
+    fn test_test_instance() {
+        // A required feature is missing.
+        assert!(PluginInstance::<Amp>::test_builder().build().is_none());
+
+        let live = sys::LV2_Feature {
+            URI: IsLive::URI.as_ptr() as *const _,
+            data: std::ptr::null_mut::<c_void>(),
+        };
+        let mut gain: f32 = 2.0;
+        let mut input: [f32; 4] = [1.0, 2.0, 3.0, 4.0];
+        let mut output: [f32; 4] = [0.0; 4];
+
+        let mut instance = PluginInstance::<Amp>::test_builder()
+            .sample_rate(48000.0)
+            .bundle_path("/lv2/amp.lv2/")
+            .feature(&live)
+            .build()
+            .unwrap();
+        assert_eq!(instance.plugin().sample_rate, 48000.0);
+
+        instance.activate();
+        assert!(instance.plugin().active);
+
+        instance.connect_port(0, &mut gain);
+        instance.connect_port(1, &mut input[..]);
+        instance.connect_port(2, &mut output[..]);
+        unsafe { instance.run(4) };
+        drop(instance);
+
+        assert_eq!(output, [2.0, 4.0, 6.0, 8.0]);
+    }
+}
@@ -0,0 +1,93 @@
+//! Generation of description stubs for plugins.
+use crate::feature::FeatureCollection;
+use crate::plugin::Plugin;
+use crate::port::{PortCollection, PortDirection};
+use std::fmt::Write;
+use urid::Uri;
+
+/// Convert a URI to a string, replacing invalid UTF-8 sequences.
+fn uri_str(uri: &Uri) -> String {
+    uri.to_string_lossy().into_owned()
+}
+
+/// Generate a `manifest.ttl` for a plugin.
+///
+/// The manifest tells the host where to find the plugin's binary, named `binary`, and its full description, named `ttl`. Both paths are relative to the bundle.
+pub fn manifest_ttl<P: Plugin>(binary: &str, ttl: &str) -> String {
+    format!(
+        "@prefix lv2: <http://lv2plug.in/ns/lv2core#> .\n\
+         @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .\n\
+         \n\
+         <{}>\n    \
+             a lv2:Plugin ;\n    \
+             lv2:binary <{}> ;\n    \
+             rdfs:seeAlso <{}> .\n",
+        uri_str(P::uri()),
+        binary,
+        ttl
+    )
+}
+
+/// Generate a skeleton of a plugin's description.
+///
+/// The description contains everything that can be derived from the plugin type: The URI, the versions, the features required by the [`InitFeatures`](trait.Plugin.html#associatedtype.InitFeatures) and [`AudioFeatures`](trait.Plugin.html#associatedtype.AudioFeatures) and the [ports](../port/trait.PortCollection.html#associatedconstant.PORTS) with their index, symbol, type and direction. The name of every port is its symbol, and types or directions that are unknown to the framework are omitted.
+///
+/// Human-readable information, like the name of the plugin, its license, or the ranges of control ports, isn't known to the framework and has to be added to the result.
+pub fn plugin_ttl<P: Plugin>() -> String {
+    let mut ttl = String::new();
+    ttl.push_str("@prefix lv2: <http://lv2plug.in/ns/lv2core#> .\n\n");
+
+    // Writing to a string never fails, which is why the results are ignored.
+    let _ = write!(
+        ttl,
+        "<{}>\n    a lv2:Plugin ;\n    lv2:minorVersion {} ;\n    lv2:microVersion {}",
+        uri_str(P::uri()),
+        P::MINOR_VERSION,
+        P::MICRO_VERSION
+    );
+
+    let mut features: Vec<&Uri> = Vec::new();
+    let init_features = <P::InitFeatures as FeatureCollection<'static>>::REQUIRED_FEATURES;
+    let audio_features = <P::AudioFeatures as FeatureCollection<'static>>::REQUIRED_FEATURES;
+    for feature in init_features.iter().chain(audio_features.iter()) {
+        if !features.contains(feature) {
+            features.push(feature);
+        }
+    }
+    for feature in features {
+        let _ = write!(ttl, " ;\n    lv2:requiredFeature <{}>", uri_str(feature));
+    }
+
+    let ports = <P::Ports as PortCollection>::PORTS;
+    for (index, port) in ports.iter().enumerate() {
+        ttl.push_str(if index == 0 {
+            " ;\n    lv2:port [\n"
+        } else {
+            " , [\n"
+        });
+        let mut classes: Vec<String> = Vec::new();
+        match port.direction {
+            Some(PortDirection::Input) => classes.push("lv2:InputPort".to_owned()),
+            Some(PortDirection::Output) => classes.push("lv2:OutputPort".to_owned()),
+            None => (),
+        }
+        if let Some(class) = port.class {
+            classes.push(format!("<{}>", uri_str(class)));
+        }
+        if !classes.is_empty() {
+            let _ = writeln!(ttl, "        a {} ;", classes.join(" , "));
+        }
+        let _ = write!(
+            ttl,
+            "        lv2:index {} ;\n        lv2:symbol \"{}\" ;\n        lv2:name \"{}\"",
+            index, port.symbol, port.symbol
+        );
+        if port.optional {
+            ttl.push_str(" ;\n        lv2:portProperty lv2:connectionOptional");
+        }
+        ttl.push_str("\n    ]");
+    }
+    ttl.push_str(" .\n");
+
+    ttl
+}
@@ -12,6 +12,7 @@ pub use cv::*;
 use std::ffi::c_void;
 use std::ops::{Deref, DerefMut};
 use std::ptr::NonNull;
+use urid::{Uri, UriBound};
 
 #[cfg(feature = "lv2-core-derive")]
 pub use lv2_core_derive::*;
@@ -20,6 +21,11 @@ pub use lv2_core_derive::*;
 ///
 /// A port can read input or create a pointer to the output, but the exact type of input/output (pointer) depends on the type of port. This trait generalizes these types and behaviour.
 pub trait PortType {
+    /// The class of the port in the plugin's description, for example `lv2:AudioPort`.
+    ///
+    /// The class is only used to [describe](trait.PortCollection.html#associatedconstant.PORTS) port collections and defaults to `None`, which means that it is unknown. Port types that are [URI bounds](../../urid/trait.UriBound.html) can use [`port_class`](fn.port_class.html) to return their URI.
+    const CLASS: Option<&'static Uri> = None;
+
     /// The type of input read by the port.
     type InputPortType: Sized;
     /// The type of output reference created by the port.
@@ -44,8 +50,49 @@ pub trait PortType {
     unsafe fn output_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> Self::OutputPortType;
 }
 
+/// The direction of a port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PortDirection {
+    /// The plugin reads from the port.
+    Input,
+    /// The plugin writes to the port.
+    Output,
+}
+
+/// Description of a port of a port collection.
+///
+/// The derived implementation of [`PortCollection`](trait.PortCollection.html) lists its ports in the order of the fields, which means that the index of a port is its position in [`PORTS`](trait.PortCollection.html#associatedconstant.PORTS). This information is enough to generate the port declarations of a plugin's `.ttl` file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PortInfo {
+    /// The symbol of the port, which is the name of the field.
+    pub symbol: &'static str,
+    /// The URI of the port type, for example `lv2:AudioPort`, if it is known.
+    pub class: Option<&'static Uri>,
+    /// The direction of the port, if it is known.
+    pub direction: Option<PortDirection>,
+    /// Whether the host may leave the port disconnected, i.e. whether it is `lv2:connectionOptional`.
+    pub optional: bool,
+}
+
+/// Return the URI of a URI bound in a constant context, for use as the [class](trait.PortType.html#associatedconstant.CLASS) of a port type.
+pub const fn port_class<T: UriBound + ?Sized>() -> &'static Uri {
+    // SAFETY: The `UriBound` contract requires `URI` to be a valid, null-terminated URI.
+    unsafe { Uri::from_bytes_with_nul_unchecked(T::URI) }
+}
+
 /// Abstraction of safe port handles.
+///
+/// The associated constants describe the port and are only used to [describe](trait.PortCollection.html#associatedconstant.PORTS) port collections. They default to an unknown, required port.
 pub trait PortHandle: Sized {
+    /// The direction of the port, if it is known.
+    const DIRECTION: Option<PortDirection> = None;
+
+    /// The URI of the port type, if it is known.
+    const CLASS: Option<&'static Uri> = None;
+
+    /// Whether the host may leave the port disconnected.
+    const OPTIONAL: bool = false;
+
     /// Try to create a port handle from a port connection pointer and the sample count.
     ///
     /// If the pointer is null, this method will return `None`.
@@ -73,6 +120,9 @@ impl<T: PortType> Deref for InputPort<T> {
 }
 
 impl<T: PortType> PortHandle for InputPort<T> {
+    const DIRECTION: Option<PortDirection> = Some(PortDirection::Input);
+    const CLASS: Option<&'static Uri> = T::CLASS;
+
     #[inline]
     unsafe fn from_raw(pointer: *mut c_void, sample_count: u32) -> Option<Self> {
         Some(Self {
@@ -105,6 +155,9 @@ impl<T: PortType> DerefMut for OutputPort<T> {
 }
 
 impl<T: PortType> PortHandle for OutputPort<T> {
+    const DIRECTION: Option<PortDirection> = Some(PortDirection::Output);
+    const CLASS: Option<&'static Uri> = T::CLASS;
+
     #[inline]
     unsafe fn from_raw(pointer: *mut c_void, sample_count: u32) -> Option<Self> {
         Some(Self {
@@ -114,6 +167,10 @@ impl<T: PortType> PortHandle for OutputPort<T> {
 }
 
 impl<T: PortHandle> PortHandle for Option<T> {
+    const DIRECTION: Option<PortDirection> = T::DIRECTION;
+    const CLASS: Option<&'static Uri> = T::CLASS;
+    const OPTIONAL: bool = true;
+
     unsafe fn from_raw(pointer: *mut c_void, sample_count: u32) -> Option<Self> {
         Some(T::from_raw(pointer, sample_count))
     }
@@ -145,9 +202,14 @@ pub trait PortCollection: Sized {
     /// The host passes port pointers to the plugin one by one and in an undefined order. Therefore, the plugin instance can not collect these pointers in the port collection directly. Instead, the pointers are stored in a cache which is then used to create the proper port collection.
     type Cache: PortPointerCache;
 
+    /// The descriptions of all ports in the collection, ordered by their index.
+    ///
+    /// The derived implementation lists every field of the struct. Defaults to an empty slice.
+    const PORTS: &'static [PortInfo] = &[];
+
     /// Try to construct a port collection instance from a port pointer cache.
     ///
-    /// If one of the port connection pointers is null, this method will return `None`, because a `PortCollection` can not be constructed.
+    /// If one of the port connection pointers is null, this method will return `None`, because a `PortCollection` can not be constructed. The only exception are ports wrapped in an `Option`, which are `lv2:connectionOptional` and simply become `None` if the host left them disconnected.
     ///
     /// # Safety
     ///
@@ -176,3 +238,55 @@ pub trait PortPointerCache: Sized + Default {
 impl PortPointerCache for () {
     fn connect(&mut self, _index: u32, _pointer: *mut c_void) {}
 }
+
+#[cfg(test)]
+#[cfg(feature = "lv2-core-derive")]
+mod tests {
+    use crate::port::*;
+    use std::ffi::c_void;
+
+    #[derive(PortCollection)]
+    struct Ports {
+        gain: InputPort<Control>,
+        modulation: Option<InputPort<CV>>,
+        input: InputPort<Audio>,
+        output: OutputPort<Audio>,
+        monitor: Option<OutputPort<Audio>>,
+    }
+
+    #[test]
+    fn test_optional_ports() {
+        let mut gain: f32 = 2.0;
+        let mut modulation: [f32; 4] = [0.5; 4];
+        let mut input: [f32; 4] = [1.0; 4];
+        let mut output: [f32; 4] = [0.0; 4];
+
+        assert!(!Ports::PORTS[0].optional);
+        assert!(Ports::PORTS[1].optional);
+        assert!(Ports::PORTS[4].optional);
+        assert_eq!(Some(PortDirection::Output), Ports::PORTS[4].direction);
+
+        // Only the required ports are connected, the optional ones are left disconnected.
+        let mut cache = <Ports as PortCollection>::Cache::default();
+        cache.connect(0, &mut gain as *mut f32 as *mut c_void);
+        cache.connect(1, std::ptr::null_mut());
+        cache.connect(2, input.as_mut_ptr() as *mut c_void);
+        cache.connect(3, output.as_mut_ptr() as *mut c_void);
+
+        let ports = unsafe { Ports::from_connections(&cache, 4) }.unwrap();
+        assert_eq!(2.0, *ports.gain);
+        assert!(ports.modulation.is_none());
+        assert_eq!(&[1.0; 4], *ports.input);
+        assert_eq!(4, ports.output.len());
+        assert!(ports.monitor.is_none());
+
+        // Connecting an optional port makes it available.
+        cache.connect(1, modulation.as_mut_ptr() as *mut c_void);
+        let ports = unsafe { Ports::from_connections(&cache, 4) }.unwrap();
+        assert_eq!(&[0.5; 4], *ports.modulation.unwrap());
+
+        // A disconnected required port still fails the collection.
+        cache.connect(2, std::ptr::null_mut());
+        assert!(unsafe { Ports::from_connections(&cache, 4) }.is_none());
+    }
+}
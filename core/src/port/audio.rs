@@ -1,8 +1,8 @@
-use crate::port::PortType;
+use crate::port::{port_class, PortType};
 use std::cell::Cell;
 use std::ffi::c_void;
 use std::ptr::NonNull;
-use urid::UriBound;
+use urid::{Uri, UriBound};
 
 /// A port connected to an array of float audio samples. Using this port **requires** the `inPlaceBroken` feature.
 ///
@@ -66,6 +66,7 @@ unsafe impl UriBound for Audio {
 }
 
 impl PortType for Audio {
+    const CLASS: Option<&'static Uri> = Some(port_class::<Self>());
     type InputPortType = &'static [f32];
     type OutputPortType = &'static mut [f32];
 
@@ -132,6 +133,7 @@ unsafe impl UriBound for InPlaceAudio {
 }
 
 impl PortType for InPlaceAudio {
+    const CLASS: Option<&'static Uri> = Some(port_class::<Self>());
     type InputPortType = &'static [Cell<f32>];
     type OutputPortType = &'static [Cell<f32>];
 
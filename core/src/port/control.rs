@@ -1,8 +1,8 @@
-use crate::port::PortType;
+use crate::port::{port_class, PortType};
 use std::cell::Cell;
 use std::ffi::c_void;
 use std::ptr::NonNull;
-use urid::UriBound;
+use urid::{Uri, UriBound};
 
 /// A port connected to a single float ([`f32`]). Using this port **requires** the `inPlaceBroken` feature.
 ///
@@ -65,6 +65,7 @@ unsafe impl UriBound for Control {
 }
 
 impl PortType for Control {
+    const CLASS: Option<&'static Uri> = Some(port_class::<Self>());
     type InputPortType = f32;
     type OutputPortType = &'static mut f32;
 
@@ -130,6 +131,7 @@ unsafe impl UriBound for InPlaceControl {
 }
 
 impl PortType for InPlaceControl {
+    const CLASS: Option<&'static Uri> = Some(port_class::<Self>());
     type InputPortType = &'static Cell<f32>;
     type OutputPortType = &'static Cell<f32>;
 
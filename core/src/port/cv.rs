@@ -1,14 +1,16 @@
-use crate::port::PortType;
+use crate::port::{port_class, PortType};
 use std::cell::Cell;
 use std::ffi::c_void;
 use std::ptr::NonNull;
-use urid::UriBound;
+use urid::{Uri, UriBound};
 
 /// A port connected to an array of float control values. Using this port **requires** the `inPlaceBroken` feature.
 ///
 /// Ports of this type are connected to a buffer of float control values, represented as a slice.
 /// They have the same buffer format as [`Audio`](crate::port::Audio) ports, except the buffer represents
 /// audio-rate control data rather than audio.
+/// Unlike a [`Control`](crate::port::Control) port, which holds a single value for the whole run, a CV port holds one value per frame:
+/// The slice always has exactly as many elements as the `sample_count` of the current run.
 /// Like a [`Control`](crate::port::Control) port, a CV port SHOULD have properties describing its value, in particular minimum, maximum, and default.
 ///
 /// Hosts may present CV ports to users as controls in the same way as control ports.
@@ -79,6 +81,7 @@ unsafe impl UriBound for CV {
 }
 
 impl PortType for CV {
+    const CLASS: Option<&'static Uri> = Some(port_class::<Self>());
     type InputPortType = &'static [f32];
     type OutputPortType = &'static mut [f32];
 
@@ -158,6 +161,7 @@ unsafe impl UriBound for InPlaceCV {
 }
 
 impl PortType for InPlaceCV {
+    const CLASS: Option<&'static Uri> = Some(port_class::<Self>());
     type InputPortType = &'static [Cell<f32>];
     type OutputPortType = &'static [Cell<f32>];
 
@@ -179,3 +183,28 @@ impl PortType for InPlaceCV {
         .as_slice_of_cells()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::port::*;
+    use std::ffi::c_void;
+
+    #[test]
+    fn test_cv_length() {
+        let mut buffer: [f32; 16] = [0.0; 16];
+        let pointer = buffer.as_mut_ptr() as *mut c_void;
+
+        for sample_count in [0, 1, 7, 16].iter().copied() {
+            let input = unsafe { InputPort::<CV>::from_raw(pointer, sample_count) }.unwrap();
+            assert_eq!(sample_count as usize, input.len());
+            let output = unsafe { OutputPort::<CV>::from_raw(pointer, sample_count) }.unwrap();
+            assert_eq!(sample_count as usize, output.len());
+
+            let input = unsafe { InputPort::<InPlaceCV>::from_raw(pointer, sample_count) }.unwrap();
+            assert_eq!(sample_count as usize, input.len());
+            let output =
+                unsafe { OutputPort::<InPlaceCV>::from_raw(pointer, sample_count) }.unwrap();
+            assert_eq!(sample_count as usize, output.len());
+        }
+    }
+}
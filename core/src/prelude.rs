@@ -1,8 +1,27 @@
 //! Prelude for wildcard use, containing many important types.
+//!
+//! All port types are part of the prelude, so port collections can be declared with it alone:
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//!
+//! #[derive(PortCollection)]
+//! struct Ports {
+//!     gain: InputPort<Control>,
+//!     modulation: Option<InputPort<CV>>,
+//!     input: InputPort<Audio>,
+//!     output: OutputPort<Audio>,
+//! }
+//! ```
 pub use crate::extension::ExtensionDescriptor;
-pub use crate::feature::{FeatureCache, FeatureCollection, MissingFeatureError, ThreadingClass};
+pub use crate::feature::{
+    feature_uri, FeatureCache, FeatureCollection, MissingFeatureError, ThreadingClass,
+};
 pub use crate::match_extensions;
-pub use crate::plugin::{Plugin, PluginInfo, PluginInstance, PluginInstanceDescriptor};
+pub use crate::plugin::{
+    process_blocks, BlockProcessor, BundleShared, Plugin, PluginInfo, PluginInstance,
+    PluginInstanceDescriptor,
+};
 
 #[cfg(feature = "lv2-core-derive")]
 pub use crate::plugin::{lv2_descriptors, PortCollection};
@@ -82,6 +82,32 @@ lv2_descriptors! {
     Amp
 }
 
+#[uri("http://lv2plug.in/plugins.rs/versioned")]
+struct Versioned;
+
+impl Plugin for Versioned {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    const MINOR_VERSION: u32 = 2;
+    const MICRO_VERSION: u32 = 5;
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+}
+
+#[test]
+fn test_version() {
+    assert_eq!(Amp::MINOR_VERSION, 0);
+    assert_eq!(Amp::MICRO_VERSION, 0);
+    assert_eq!(Versioned::MINOR_VERSION, 2);
+    assert_eq!(Versioned::MICRO_VERSION, 5);
+}
+
 #[test]
 fn test_discovery() {
     use lv2_sys::*;
@@ -153,3 +179,99 @@ fn test_plugin() {
         assert!((input[i] * gain - output[i]).abs() < f32::EPSILON);
     }
 }
+
+#[test]
+fn test_ttl() {
+    use lv2_core::plugin::ttl::{manifest_ttl, plugin_ttl};
+
+    assert_eq!(
+        AmpPorts::PORTS[1],
+        PortInfo {
+            symbol: "input",
+            class: Some(Uri::from_bytes_with_nul(lv2_sys::LV2_CORE__AudioPort).unwrap()),
+            direction: Some(PortDirection::Input),
+            optional: false,
+        }
+    );
+
+    let manifest = manifest_ttl::<Amp>("libamp.so", "amp.ttl");
+    assert!(manifest.contains("<http://lv2plug.in/plugins.rs/example_amp>\n    a lv2:Plugin ;"));
+    assert!(manifest.contains("lv2:binary <libamp.so> ;"));
+    assert!(manifest.contains("rdfs:seeAlso <amp.ttl> ."));
+
+    let ttl = plugin_ttl::<Amp>();
+    assert!(ttl.contains("<http://lv2plug.in/plugins.rs/example_amp>\n    a lv2:Plugin ;"));
+    assert!(ttl.contains("lv2:minorVersion 0 ;"));
+    assert!(ttl.contains("lv2:requiredFeature <http://lv2plug.in/ns/lv2core#hardRTCapable>"));
+    assert!(!ttl.contains("isLive"));
+    assert!(ttl.contains(
+        "a lv2:InputPort , <http://lv2plug.in/ns/lv2core#ControlPort> ;\n        lv2:index 0 ;\n        lv2:symbol \"gain\""
+    ));
+    assert!(ttl.contains(
+        "a lv2:OutputPort , <http://lv2plug.in/ns/lv2core#AudioPort> ;\n        lv2:index 2 ;\n        lv2:symbol \"output\""
+    ));
+    assert!(ttl.ends_with("    ] .\n"));
+
+    let ttl = plugin_ttl::<Versioned>();
+    assert!(ttl.contains("lv2:microVersion 5 .\n"));
+    assert!(!ttl.contains("lv2:port"));
+}
+
+/// A port type that doesn't provide a class.
+struct RawPortType;
+
+impl PortType for RawPortType {
+    type InputPortType = std::ptr::NonNull<std::ffi::c_void>;
+    type OutputPortType = std::ptr::NonNull<std::ffi::c_void>;
+
+    unsafe fn input_from_raw(
+        pointer: std::ptr::NonNull<std::ffi::c_void>,
+        _: u32,
+    ) -> Self::InputPortType {
+        pointer
+    }
+
+    unsafe fn output_from_raw(
+        pointer: std::ptr::NonNull<std::ffi::c_void>,
+        _: u32,
+    ) -> Self::OutputPortType {
+        pointer
+    }
+}
+
+/// A port handle that doesn't describe itself.
+struct RawPortHandle;
+
+impl PortHandle for RawPortHandle {
+    unsafe fn from_raw(_: *mut std::ffi::c_void, _: u32) -> Option<Self> {
+        Some(Self)
+    }
+}
+
+#[derive(PortCollection)]
+#[allow(dead_code)]
+struct RawPorts {
+    typed: InputPort<RawPortType>,
+    handle: RawPortHandle,
+}
+
+#[test]
+fn test_ports_without_metadata() {
+    assert_eq!(
+        RawPorts::PORTS,
+        &[
+            PortInfo {
+                symbol: "typed",
+                class: None,
+                direction: Some(PortDirection::Input),
+                optional: false,
+            },
+            PortInfo {
+                symbol: "handle",
+                class: None,
+                direction: None,
+                optional: false,
+            },
+        ]
+    );
+}
@@ -0,0 +1,94 @@
+use lv2_core::prelude::*;
+use urid::*;
+
+#[uri("http://lv2plug.in/plugins.rs/descriptors#first")]
+struct First;
+
+impl Plugin for First {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+}
+
+#[uri("http://lv2plug.in/plugins.rs/descriptors#second")]
+struct Second;
+
+impl Plugin for Second {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+}
+
+lv2_descriptors! {
+    First,
+    Second
+}
+
+/// Describe all descriptors exported by `lv2_descriptor`, one line per descriptor.
+///
+/// Every line contains the URI of the plugin and the names of all callbacks that are set.
+fn dump_descriptors() -> Vec<String> {
+    let mut lines = Vec::new();
+    for index in 0.. {
+        let descriptor: &LV2_Descriptor = match unsafe { lv2_descriptor(index).as_ref() } {
+            Some(descriptor) => descriptor,
+            None => break,
+        };
+
+        let uri = unsafe { Uri::from_ptr(descriptor.URI) };
+        let callbacks = [
+            ("instantiate", descriptor.instantiate.is_some()),
+            ("connect_port", descriptor.connect_port.is_some()),
+            ("activate", descriptor.activate.is_some()),
+            ("run", descriptor.run.is_some()),
+            ("deactivate", descriptor.deactivate.is_some()),
+            ("cleanup", descriptor.cleanup.is_some()),
+            ("extension_data", descriptor.extension_data.is_some()),
+        ];
+        let callbacks: Vec<&str> = callbacks
+            .iter()
+            .filter(|(_, set)| *set)
+            .map(|(name, _)| *name)
+            .collect();
+
+        let line = format!(
+            "{}: {} [{}]",
+            index,
+            uri.to_str().unwrap(),
+            callbacks.join(", ")
+        );
+        println!("{}", line);
+        lines.push(line);
+    }
+    lines
+}
+
+#[test]
+fn test_dump_descriptors() {
+    let callbacks = "instantiate, connect_port, activate, run, deactivate, cleanup, extension_data";
+    assert_eq!(
+        dump_descriptors(),
+        vec![
+            format!(
+                "0: http://lv2plug.in/plugins.rs/descriptors#first [{}]",
+                callbacks
+            ),
+            format!(
+                "1: http://lv2plug.in/plugins.rs/descriptors#second [{}]",
+                callbacks
+            ),
+        ]
+    );
+}
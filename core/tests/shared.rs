@@ -0,0 +1,112 @@
+use lv2_core::prelude::*;
+use std::ffi::CStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use urid::*;
+
+/// The data shared by all plugins of this library.
+struct Wavetable {
+    bundle: String,
+    instances: AtomicUsize,
+}
+
+static INITIALIZATIONS: AtomicUsize = AtomicUsize::new(0);
+
+fn load_wavetable(plugin_info: &PluginInfo) -> &'static Wavetable {
+    let wavetable = WAVETABLE.get_or_init(plugin_info, |bundle_path| {
+        INITIALIZATIONS.fetch_add(1, Ordering::SeqCst);
+        Wavetable {
+            bundle: bundle_path.to_str().unwrap().to_owned(),
+            instances: AtomicUsize::new(0),
+        }
+    });
+    wavetable.instances.fetch_add(1, Ordering::SeqCst);
+    wavetable
+}
+
+#[uri("http://lv2plug.in/plugins.rs/shared#synth")]
+struct Synth {
+    wavetable: &'static Wavetable,
+}
+
+impl Plugin for Synth {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(plugin_info: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self {
+            wavetable: load_wavetable(plugin_info),
+        })
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+}
+
+#[uri("http://lv2plug.in/plugins.rs/shared#sampler")]
+struct Sampler {
+    wavetable: &'static Wavetable,
+}
+
+impl Plugin for Sampler {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(plugin_info: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self {
+            wavetable: load_wavetable(plugin_info),
+        })
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+}
+
+lv2_descriptors! {
+    /// The wavetable shared by the synth and the sampler.
+    #[shared]
+    static WAVETABLE: Wavetable;
+    Synth,
+    Sampler
+}
+
+#[test]
+fn test_shared_data() {
+    use lv2_sys::*;
+
+    assert!(!WAVETABLE.is_initialized());
+
+    let bundle = CStr::from_bytes_with_nul(b"/lv2/shared.lv2/\0").unwrap();
+    let features: [*const LV2_Feature; 1] = [std::ptr::null()];
+
+    let mut handles = Vec::new();
+    for index in 0..4 {
+        let descriptor = unsafe { lv2_descriptor(index % 2).as_ref() }.unwrap();
+        let handle = unsafe {
+            (descriptor.instantiate.unwrap())(
+                descriptor,
+                44100.0,
+                bundle.as_ptr(),
+                features.as_ptr(),
+            )
+        };
+        assert!(!handle.is_null());
+        handles.push((descriptor, handle));
+    }
+
+    assert_eq!(INITIALIZATIONS.load(Ordering::SeqCst), 1);
+    let wavetable = WAVETABLE.get().unwrap();
+    assert_eq!(wavetable.bundle, "/lv2/shared.lv2/");
+    assert_eq!(wavetable.instances.load(Ordering::SeqCst), 4);
+    assert_eq!(
+        WAVETABLE.bundle_path().unwrap().to_str(),
+        Some("/lv2/shared.lv2/")
+    );
+
+    let synth = unsafe { &*(handles[0].1 as *const Synth) };
+    let sampler = unsafe { &*(handles[1].1 as *const Sampler) };
+    assert!(std::ptr::eq(synth.wavetable, sampler.wavetable));
+
+    for (descriptor, handle) in handles {
+        unsafe { (descriptor.cleanup.unwrap())(handle) };
+    }
+}
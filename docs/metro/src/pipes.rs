@@ -1,5 +1,6 @@
 // We cover the individual pipes of the plugin before putting it all together:
 use iterpipes::*;
+use lv2::lv2_time::Position;
 use lv2::prelude::*;
 
 // `Sampler` is a simple sampler that plays back the contents of a pre-recorded sample. It simply returns a frame for every index it receives as an input, which means that it can also be played backward or at a different speed. The actual type of frames isn't important and therefore, this sampler is generic.
@@ -322,29 +323,12 @@ impl<'a> Pipe for EventReader<'a> {
             speed_update: None,
         };
 
-        if let Some(atom) = atom {
-            if let Ok((object_header, object_reader)) = atom
-                .read(self.atom_urids.object)
-                .or_else(|_| atom.read(self.atom_urids.blank))
-            {
-                if object_header.otype == self.time_urids.position_class {
-                    for (property_header, property) in object_reader {
-                        if property_header.key == self.time_urids.bar_beat {
-                            updates.beat_update = property
-                                .read(self.atom_urids.float)
-                                .map(|float| *float as f64)
-                                .ok();
-                        }
-                        if property_header.key == self.time_urids.beats_per_minute {
-                            updates.bpm_update = property.read(self.atom_urids.float).ok().copied();
-                        }
-                        if property_header.key == self.time_urids.speed {
-                            updates.speed_update =
-                                property.read(self.atom_urids.float).ok().copied();
-                        }
-                    }
-                }
-            }
+        if let Some(position) =
+            atom.and_then(|atom| Position::from_atom(atom, self.atom_urids, self.time_urids))
+        {
+            updates.beat_update = position.bar_beat.map(|beat| beat as f64);
+            updates.bpm_update = position.beats_per_minute;
+            updates.speed_update = position.speed;
         }
 
         updates
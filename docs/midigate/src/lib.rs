@@ -96,8 +96,9 @@ impl Plugin for Midigate {
         for (timestamp, message) in control_sequence {
             let timestamp = timestamp as usize;
 
+            // A `NoteOn` with a velocity of zero is a `NoteOff` too.
             let message = if let Ok(message) = message.read(self.urids.midi.wmidi) {
-                message
+                normalize_note(message)
             } else {
                 continue;
             };
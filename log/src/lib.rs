@@ -0,0 +1,265 @@
+//! Logging library allowing LV2 plugins to print log messages through the host.
+//!
+//! The [`Log`](struct.Log.html) feature is provided by the host and prints messages of a certain kind, which is identified by a URID of an [`Entry`](trait.Entry.html) type. The URIDs of the standard entry types are gathered in the [`LogURIDCollection`](struct.LogURIDCollection.html).
+//!
+//! Printing a message is generally not realtime-safe, unless it is a [`Trace`](struct.TraceClass.html) message. If you want to log from the audio thread, you can use the [`RtLogBuffer`](rt_buffer/struct.RtLogBuffer.html) (enabled by the `rt-buffer` feature) to defer the printing to another thread.
+//!
+//! Plugins that use the macros of the [`log`](https://docs.rs/log) crate can install their log feature in the [`HostLogger`](log_crate/struct.HostLogger.html) (enabled by the `log-crate` feature), which is used as the backend.
+extern crate lv2_sys as sys;
+
+#[cfg(feature = "log-crate")]
+pub mod log_crate;
+#[cfg(feature = "rt-buffer")]
+pub mod rt_buffer;
+
+use lv2_core::feature::{Feature, ThreadingClass};
+use std::error::Error;
+use std::ffi::{c_void, CStr};
+use std::fmt;
+use std::os::raw::c_char;
+use urid::*;
+
+/// URID marker for error messages.
+pub struct ErrorClass;
+unsafe impl UriBound for ErrorClass {
+    const URI: &'static [u8] = sys::LV2_LOG__Error;
+}
+
+/// URID marker for informative notes.
+pub struct NoteClass;
+unsafe impl UriBound for NoteClass {
+    const URI: &'static [u8] = sys::LV2_LOG__Note;
+}
+
+/// URID marker for debugging traces.
+///
+/// This is the only kind of message that may be printed from the audio thread.
+pub struct TraceClass;
+unsafe impl UriBound for TraceClass {
+    const URI: &'static [u8] = sys::LV2_LOG__Trace;
+}
+
+/// URID marker for warnings.
+pub struct WarningClass;
+unsafe impl UriBound for WarningClass {
+    const URI: &'static [u8] = sys::LV2_LOG__Warning;
+}
+
+/// Marker trait for the kinds of log entries.
+///
+/// # Safety
+///
+/// This trait may only be implemented by URI bounds of subclasses of `log:Entry`.
+pub unsafe trait Entry: UriBound {}
+
+unsafe impl Entry for ErrorClass {}
+unsafe impl Entry for NoteClass {}
+unsafe impl Entry for TraceClass {}
+unsafe impl Entry for WarningClass {}
+
+/// Returned if the host failed to print a message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PrintError;
+
+impl fmt::Display for PrintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The host failed to print the log message")
+    }
+}
+
+impl Error for PrintError {}
+
+// Replaces `lv2_sys::LV2_Log_Log` to avoid checking the function pointer on every call.
+#[repr(C)]
+struct LogInternal {
+    handle: sys::LV2_Log_Handle,
+    printf: unsafe extern "C" fn(
+        handle: sys::LV2_Log_Handle,
+        type_: sys::LV2_URID,
+        fmt: *const c_char,
+        ...
+    ) -> i32,
+    vprintf: *const c_void, // placeholder, not useable yet
+}
+
+/// Feature to print log messages through the host.
+pub struct Log<'a> {
+    internal: &'a LogInternal,
+}
+
+unsafe impl<'a> UriBound for Log<'a> {
+    const URI: &'static [u8] = sys::LV2_LOG__log;
+}
+
+unsafe impl<'a> Feature for Log<'a> {
+    unsafe fn from_feature_ptr(feature: *const c_void, _class: ThreadingClass) -> Option<Self> {
+        let log = (feature as *const sys::LV2_Log_Log).as_ref()?;
+        log.printf?;
+        Some(Self {
+            internal: &*(log as *const sys::LV2_Log_Log as *const LogInternal),
+        })
+    }
+}
+
+impl<'a> Log<'a> {
+    /// Print a message through the host.
+    ///
+    /// The `entry` URID identifies the kind of the message, for example an error or a warning.
+    ///
+    /// This method is not realtime-safe, unless `entry` is a [`TraceClass`](struct.TraceClass.html) URID.
+    pub fn print_cstr(&self, entry: URID<impl Entry>, message: &CStr) -> Result<(), PrintError> {
+        self.print_raw(entry.into_general(), message)
+    }
+
+    /// Print a string slice through the host.
+    ///
+    /// The message is copied into a null-terminated buffer, which is located on the stack for messages of up to 255 bytes. Returns a [`PrintError`](struct.PrintError.html) if the message contains a null byte.
+    ///
+    /// Like [`print_cstr`](#method.print_cstr), this method is not realtime-safe, unless `entry` is a [`TraceClass`](struct.TraceClass.html) URID. Even then, copying a long message allocates.
+    pub fn print_str(&self, entry: URID<impl Entry>, message: &str) -> Result<(), PrintError> {
+        let mut buffer = FormatBuffer::new();
+        fmt::Write::write_str(&mut buffer, message).map_err(|_| PrintError)?;
+        self.print_raw(entry.into_general(), buffer.as_cstr())
+    }
+
+    /// Format a message and print it through the host.
+    ///
+    /// The message is formatted into a buffer on the stack, which avoids an allocation for messages of up to 255 bytes. Longer messages are moved to the heap. Like [`print_str`](#method.print_str), this method returns a [`PrintError`](struct.PrintError.html) if the message contains a null byte.
+    ///
+    /// ```
+    /// use lv2_log::*;
+    ///
+    /// fn report(log: &Log, urids: &LogURIDCollection, x: f32) -> Result<(), PrintError> {
+    ///     log.print_fmt(urids.error, format_args!("bad value {}", x))
+    /// }
+    /// ```
+    ///
+    /// Like [`print_cstr`](#method.print_cstr), this method is not realtime-safe, unless `entry` is a [`TraceClass`](struct.TraceClass.html) URID. Even then, formatting a long message allocates.
+    pub fn print_fmt(
+        &self,
+        entry: URID<impl Entry>,
+        args: fmt::Arguments,
+    ) -> Result<(), PrintError> {
+        let mut buffer = FormatBuffer::new();
+        fmt::Write::write_fmt(&mut buffer, args).map_err(|_| PrintError)?;
+        self.print_raw(entry.into_general(), buffer.as_cstr())
+    }
+
+    fn print_raw(&self, entry: URID, message: &CStr) -> Result<(), PrintError> {
+        let res = unsafe {
+            (self.internal.printf)(
+                self.internal.handle,
+                entry.get(),
+                b"%s\0".as_ptr() as *const c_char,
+                message.as_ptr(),
+            )
+        };
+        if res >= 0 {
+            Ok(())
+        } else {
+            Err(PrintError)
+        }
+    }
+}
+
+const FORMAT_BUFFER_SIZE: usize = 256;
+
+/// Null-terminated buffer for formatted messages, which only allocates if the message doesn't fit on the stack.
+///
+/// Writing a string that contains a null byte fails, since the message couldn't be passed to the host completely.
+struct FormatBuffer {
+    stack: [u8; FORMAT_BUFFER_SIZE],
+    len: usize,
+    heap: Option<Vec<u8>>,
+}
+
+impl FormatBuffer {
+    fn new() -> Self {
+        Self {
+            stack: [0; FORMAT_BUFFER_SIZE],
+            len: 0,
+            heap: None,
+        }
+    }
+
+    fn as_cstr(&mut self) -> &CStr {
+        let bytes = if let Some(heap) = self.heap.as_mut() {
+            heap.push(0);
+            heap.as_slice()
+        } else {
+            self.stack[self.len] = 0;
+            &self.stack[..=self.len]
+        };
+        // SAFETY: The slice has been null-terminated above and writing null bytes into the buffer fails.
+        unsafe { CStr::from_bytes_with_nul_unchecked(bytes) }
+    }
+}
+
+impl fmt::Write for FormatBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.as_bytes().contains(&0) {
+            return Err(fmt::Error);
+        }
+        if let Some(heap) = self.heap.as_mut() {
+            heap.extend_from_slice(s.as_bytes());
+        } else if self.len + s.len() < FORMAT_BUFFER_SIZE {
+            self.stack[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+        } else {
+            let mut heap = Vec::with_capacity(2 * (self.len + s.len()) + 1);
+            heap.extend_from_slice(&self.stack[..self.len]);
+            heap.extend_from_slice(s.as_bytes());
+            self.heap = Some(heap);
+        }
+        Ok(())
+    }
+}
+
+/// A URID cache containing the standard log entry types.
+#[derive(URIDCollection, Clone, Copy)]
+pub struct LogURIDCollection {
+    pub error: URID<ErrorClass>,
+    pub note: URID<NoteClass>,
+    pub trace: URID<TraceClass>,
+    pub warning: URID<WarningClass>,
+}
+
+/// Prelude of `lv2_log` for wildcard usage.
+pub mod prelude {
+    #[cfg(feature = "log-crate")]
+    pub use crate::log_crate::{HostLogger, HostLoggerGuard};
+    #[cfg(feature = "rt-buffer")]
+    pub use crate::rt_buffer::{RtLogBuffer, RtLogConsumer, RtLogProducer};
+    pub use crate::{
+        Entry, ErrorClass, Log, LogURIDCollection, NoteClass, PrintError, TraceClass, WarningClass,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write;
+
+    #[test]
+    fn test_format_buffer() {
+        let mut buffer = FormatBuffer::new();
+        write!(buffer, "bad value {}", 42).unwrap();
+        assert!(buffer.heap.is_none());
+        assert_eq!(buffer.as_cstr().to_str().unwrap(), "bad value 42");
+
+        // Filling the stack buffer exactly leaves no room for the terminator.
+        let long = "x".repeat(FORMAT_BUFFER_SIZE);
+        let mut buffer = FormatBuffer::new();
+        write!(buffer, "{}y", &long[1..]).unwrap();
+        assert!(buffer.heap.is_some());
+        assert_eq!(buffer.as_cstr().to_bytes().len(), FORMAT_BUFFER_SIZE);
+
+        let mut buffer = FormatBuffer::new();
+        write!(buffer, "{}", &long[1..]).unwrap();
+        assert!(buffer.heap.is_none());
+        assert_eq!(buffer.as_cstr().to_bytes().len(), FORMAT_BUFFER_SIZE - 1);
+
+        let mut buffer = FormatBuffer::new();
+        assert!(write!(buffer, "cut\0off").is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! Backend for the [`log`](https://docs.rs/log) crate.
+//!
+//! [`HostLogger`](struct.HostLogger.html) implements the `log::Log` trait on top of the [`Log`](../struct.Log.html) feature, which lets plugins use the macros of the `log` crate. The levels of the `log` crate are mapped to the entry types of LV2 like this:
+//!
+//! | `log::Level` | LV2 entry type |
+//! |--------------|----------------|
+//! | `Error`      | `ErrorClass`   |
+//! | `Warn`       | `WarningClass` |
+//! | `Info`       | `NoteClass`    |
+//! | `Debug`      | `TraceClass`   |
+//! | `Trace`      | `TraceClass`   |
+//!
+//! Printing is not realtime-safe, unless the message is printed as a trace. Therefore, only the `debug!` and `trace!` macros may be used in the audio thread, and only for short messages, since formatting long messages allocates.
+//!
+//! The `log` crate only supports one global logger per process, but the host's log feature is only valid as long as the plugin instance that received it exists. Therefore, every instance installs its log feature with [`HostLogger::install`](struct.HostLogger.html#method.install) and keeps the returned [`HostLoggerGuard`](struct.HostLoggerGuard.html). Messages are printed through the most recently installed feature that is still alive, and dropping the guard uninstalls the feature again. The guard should therefore be stored in the plugin, so that the feature is uninstalled when the instance is cleaned up:
+//!
+//! ```
+//! use lv2_log::log_crate::{HostLogger, HostLoggerGuard};
+//! use lv2_log::*;
+//! use ::log::{warn, LevelFilter};
+//!
+//! struct MyPlugin {
+//!     _logger: Option<HostLoggerGuard>,
+//! }
+//!
+//! fn new(log: Log<'static>, urids: LogURIDCollection) -> MyPlugin {
+//!     // `None` if another logger has already been set for the process.
+//!     let logger = HostLogger::install(log, urids, LevelFilter::Info);
+//!     warn!("The logger is installed");
+//!     MyPlugin { _logger: logger }
+//! }
+//! ```
+use crate::{Log, LogURIDCollection};
+use log::{Level, LevelFilter, Metadata, Record};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+/// A log feature that was installed by a plugin instance.
+struct Installed {
+    id: usize,
+    log: Log<'static>,
+    urids: LogURIDCollection,
+    level: LevelFilter,
+}
+
+// SAFETY: The host's log feature may be used from any non-realtime thread, and from the audio thread for traces. It is removed from the global list before the instance that received it is cleaned up.
+unsafe impl Send for Installed {}
+unsafe impl Sync for Installed {}
+
+static INSTALLED: RwLock<Vec<Installed>> = RwLock::new(Vec::new());
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+static REGISTERED: OnceLock<bool> = OnceLock::new();
+static LOGGER: HostLogger = HostLogger { _private: () };
+
+/// The `log::Log` implementation that prints through the host.
+///
+/// [See also the module documentation.](index.html)
+pub struct HostLogger {
+    _private: (),
+}
+
+impl HostLogger {
+    /// Install the log feature of a plugin instance and print all messages up to the given level through it.
+    ///
+    /// The first call sets the global logger of the `log` crate. Returns `None` if another logger has already been set.
+    ///
+    /// The feature stays installed until the returned guard is dropped. Since the feature is only valid as long as the plugin instance exists, the guard must not outlive the instance.
+    pub fn install(
+        log: Log<'static>,
+        urids: LogURIDCollection,
+        level: LevelFilter,
+    ) -> Option<HostLoggerGuard> {
+        if !*REGISTERED.get_or_init(|| log::set_logger(&LOGGER).is_ok()) {
+            return None;
+        }
+
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let mut installed = INSTALLED.write().unwrap_or_else(PoisonError::into_inner);
+        installed.push(Installed {
+            id,
+            log,
+            urids,
+            level,
+        });
+        update_max_level(&installed);
+        Some(HostLoggerGuard { id })
+    }
+}
+
+/// Set the maximum level of the `log` crate to the highest level of the installed features.
+fn update_max_level(installed: &[Installed]) {
+    let level = installed
+        .iter()
+        .map(|installed| installed.level)
+        .max()
+        .unwrap_or(LevelFilter::Off);
+    log::set_max_level(level);
+}
+
+impl log::Log for HostLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        match INSTALLED.try_read() {
+            Ok(installed) => installed
+                .last()
+                .is_some_and(|installed| metadata.level() <= installed.level),
+            Err(_) => false,
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        // Traces may be printed in the audio thread, which must not block.
+        let installed = match record.level() {
+            Level::Debug | Level::Trace => INSTALLED.try_read().ok(),
+            _ => INSTALLED.read().ok(),
+        };
+        let installed = match installed.as_ref().and_then(|installed| installed.last()) {
+            Some(installed) if record.level() <= installed.level => installed,
+            _ => return,
+        };
+        let (log, urids) = (&installed.log, &installed.urids);
+        // There is nobody to report a failed print to.
+        let _ = match record.level() {
+            Level::Error => log.print_fmt(urids.error, *record.args()),
+            Level::Warn => log.print_fmt(urids.warning, *record.args()),
+            Level::Info => log.print_fmt(urids.note, *record.args()),
+            Level::Debug | Level::Trace => log.print_fmt(urids.trace, *record.args()),
+        };
+    }
+
+    fn flush(&self) {}
+}
+
+/// Keeps the log feature of a plugin instance installed, created by [`HostLogger::install`](struct.HostLogger.html#method.install).
+///
+/// Dropping the guard uninstalls the feature. Messages are then printed through the previously installed feature that is still alive, or dropped if there is none.
+pub struct HostLoggerGuard {
+    id: usize,
+}
+
+impl Drop for HostLoggerGuard {
+    fn drop(&mut self) {
+        let mut installed = INSTALLED.write().unwrap_or_else(PoisonError::into_inner);
+        installed.retain(|installed| installed.id != self.id);
+        update_max_level(&installed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::log_crate::*;
+    use log::Log as _;
+    use lv2_core::feature::{Feature, ThreadingClass};
+    use std::os::raw::{c_char, c_int};
+    use urid::*;
+
+    unsafe extern "C" fn print(_: sys::LV2_Log_Handle, _: u32, _: *const c_char) -> c_int {
+        0
+    }
+
+    fn leak_log() -> Log<'static> {
+        // The function is never called since no message is printed.
+        let printf = unsafe {
+            std::mem::transmute::<
+                unsafe extern "C" fn(sys::LV2_Log_Handle, u32, *const c_char) -> c_int,
+                unsafe extern "C" fn(sys::LV2_Log_Handle, u32, *const c_char, ...) -> c_int,
+            >(print)
+        };
+        let raw: &'static sys::LV2_Log_Log = Box::leak(Box::new(sys::LV2_Log_Log {
+            handle: std::ptr::null_mut(),
+            printf: Some(printf),
+            vprintf: None,
+        }));
+        unsafe {
+            Log::from_feature_ptr(
+                raw as *const sys::LV2_Log_Log as *const _,
+                ThreadingClass::Instantiation,
+            )
+        }
+        .unwrap()
+    }
+
+    fn enabled(level: Level) -> bool {
+        LOGGER.enabled(&Metadata::builder().level(level).build())
+    }
+
+    #[test]
+    fn test_install_and_uninstall() {
+        let map = HashURIDMapper::new();
+        let urids: LogURIDCollection = map.populate_collection().unwrap();
+
+        let first = HostLogger::install(leak_log(), urids, LevelFilter::Info).unwrap();
+        assert_eq!(log::max_level(), LevelFilter::Info);
+        assert!(enabled(Level::Warn));
+        assert!(!enabled(Level::Debug));
+
+        // The most recently installed feature is used.
+        let second = HostLogger::install(leak_log(), urids, LevelFilter::Trace).unwrap();
+        assert_eq!(log::max_level(), LevelFilter::Trace);
+        assert!(enabled(Level::Debug));
+
+        drop(second);
+        assert_eq!(log::max_level(), LevelFilter::Info);
+        assert!(!enabled(Level::Debug));
+
+        drop(first);
+        assert_eq!(log::max_level(), LevelFilter::Off);
+        assert!(!enabled(Level::Error));
+    }
+}
@@ -0,0 +1,267 @@
+//! Deferred, realtime-safe logging.
+//!
+//! Printing through [`Log`](../struct.Log.html) is not realtime-safe, which means that a plugin can not print messages from its `run` method. Instead, it can push them into a [`RtLogBuffer`](struct.RtLogBuffer.html), which is a lock-free ring of fixed-size messages, and let a non-realtime thread (or the worker) drain the buffer and print the messages.
+//!
+//! The buffer is split into a producer and a consumer half, which can be moved to different threads:
+//!
+//! ```
+//! use lv2_log::rt_buffer::RtLogBuffer;
+//! use lv2_log::TraceClass;
+//! use urid::URID;
+//!
+//! # let trace: URID<TraceClass> = unsafe { URID::new_unchecked(1) };
+//! // Allocate the buffer outside of the audio thread.
+//! let (mut producer, mut consumer) = RtLogBuffer::new(16, 128).split();
+//!
+//! // In `run`:
+//! producer.push(trace, "Hello from the audio thread").unwrap();
+//!
+//! // In another thread, where you would call `consumer.drain(&log)`:
+//! let drained = consumer.drain_with(|_entry, message| {
+//!     assert_eq!(message.to_str().unwrap(), "Hello from the audio thread");
+//! });
+//! assert_eq!(drained, 1);
+//! ```
+use crate::{Entry, Log, PrintError};
+use std::cell::UnsafeCell;
+use std::error::Error;
+use std::ffi::CStr;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use urid::URID;
+
+/// Returned by [`RtLogProducer::push`](struct.RtLogProducer.html#method.push) if there is no free slot in the buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BufferFull;
+
+impl fmt::Display for BufferFull {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The log buffer is full")
+    }
+}
+
+impl Error for BufferFull {}
+
+struct Slot {
+    entry: u32,
+    len: usize,
+    bytes: Box<[u8]>,
+}
+
+/// A lock-free single-producer, single-consumer ring of fixed-size log messages.
+///
+/// All memory is allocated when the buffer is created. Afterwards, pushing and draining messages neither allocates nor blocks. Use [`split`](#method.split) to get the halves that push and drain the messages.
+pub struct RtLogBuffer {
+    slots: Box<[UnsafeCell<Slot>]>,
+    /// Index of the next slot to read, only written by the consumer.
+    head: AtomicUsize,
+    /// Index of the next slot to write, only written by the producer.
+    tail: AtomicUsize,
+}
+
+// The slots are only accessed by the single producer and the single consumer, which are synchronized by the `head` and `tail` indices.
+unsafe impl Sync for RtLogBuffer {}
+
+impl RtLogBuffer {
+    /// Create a new buffer for `capacity` messages of at most `message_size` bytes each, including the null terminator.
+    ///
+    /// This allocates memory and therefore should not be called from the audio thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `message_size` is zero.
+    pub fn new(capacity: usize, message_size: usize) -> Self {
+        assert!(capacity > 0, "The log buffer needs at least one slot");
+        assert!(message_size > 0, "The log messages need at least one byte");
+        let slots = (0..capacity)
+            .map(|_| {
+                UnsafeCell::new(Slot {
+                    entry: 0,
+                    len: 0,
+                    bytes: vec![0; message_size].into_boxed_slice(),
+                })
+            })
+            .collect();
+        Self {
+            slots,
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Return the number of messages the buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Split the buffer into its producing and consuming halves.
+    pub fn split(self) -> (RtLogProducer, RtLogConsumer) {
+        let buffer = Arc::new(self);
+        (
+            RtLogProducer {
+                buffer: buffer.clone(),
+            },
+            RtLogConsumer { buffer },
+        )
+    }
+}
+
+/// The pushing half of a [`RtLogBuffer`](struct.RtLogBuffer.html), meant to be used in the audio thread.
+pub struct RtLogProducer {
+    buffer: Arc<RtLogBuffer>,
+}
+
+impl RtLogProducer {
+    /// Push a message into the buffer.
+    ///
+    /// Messages that don't fit into a slot are truncated at a character boundary, and messages that contain a null character are cut off there. This method is realtime-safe.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferFull`](struct.BufferFull.html) if the consumer has not drained the buffer in time. The message is dropped in this case.
+    pub fn push(&mut self, entry: URID<impl Entry>, message: &str) -> Result<(), BufferFull> {
+        let buffer = &*self.buffer;
+        let tail = buffer.tail.load(Ordering::Relaxed);
+        let head = buffer.head.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) >= buffer.capacity() {
+            return Err(BufferFull);
+        }
+
+        // The consumer does not touch this slot until `tail` is advanced.
+        let slot = unsafe { &mut *buffer.slots[tail % buffer.capacity()].get() };
+
+        let message = message.split('\0').next().unwrap_or("");
+        let mut len = message.len().min(slot.bytes.len() - 1);
+        while !message.is_char_boundary(len) {
+            len -= 1;
+        }
+        slot.bytes[..len].copy_from_slice(&message.as_bytes()[..len]);
+        slot.bytes[len] = 0;
+        slot.len = len;
+        slot.entry = entry.get();
+
+        buffer.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+}
+
+/// The draining half of a [`RtLogBuffer`](struct.RtLogBuffer.html), meant to be used in a non-realtime thread.
+pub struct RtLogConsumer {
+    buffer: Arc<RtLogBuffer>,
+}
+
+impl RtLogConsumer {
+    /// Pass every pending message and the URID of its entry type to `f`, in the order they were pushed.
+    ///
+    /// Returns the number of drained messages.
+    pub fn drain_with<F: FnMut(URID, &CStr)>(&mut self, mut f: F) -> usize {
+        let buffer = &*self.buffer;
+        let mut drained = 0;
+        loop {
+            let head = buffer.head.load(Ordering::Relaxed);
+            let tail = buffer.tail.load(Ordering::Acquire);
+            if head == tail {
+                return drained;
+            }
+
+            // The producer does not touch this slot until `head` is advanced.
+            let slot = unsafe { &*buffer.slots[head % buffer.capacity()].get() };
+            // The producer always writes a URID and a null-terminated message without interior nulls.
+            let entry = unsafe { URID::new_unchecked(slot.entry) };
+            let message = unsafe { CStr::from_bytes_with_nul_unchecked(&slot.bytes[..=slot.len]) };
+            f(entry, message);
+
+            buffer.head.store(head.wrapping_add(1), Ordering::Release);
+            drained += 1;
+        }
+    }
+
+    /// Print every pending message through the host.
+    ///
+    /// Returns the number of printed messages. This method is not realtime-safe.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PrintError`](../struct.PrintError.html) if the host failed to print a message. The remaining messages are still drained, but not printed.
+    pub fn drain(&mut self, log: &Log) -> Result<usize, PrintError> {
+        let mut result = Ok(());
+        let drained = self.drain_with(|entry, message| {
+            if result.is_ok() {
+                result = log.print_raw(entry, message);
+            }
+        });
+        result.map(|_| drained)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoteClass, TraceClass};
+    use std::thread;
+
+    #[test]
+    fn test_push_and_drain_between_threads() {
+        const MESSAGES: u32 = 1000;
+
+        let trace: URID<TraceClass> = unsafe { URID::new_unchecked(1) };
+        let note: URID<NoteClass> = unsafe { URID::new_unchecked(2) };
+        let (mut producer, mut consumer) = RtLogBuffer::new(8, 32).split();
+
+        let producer_thread = thread::spawn(move || {
+            for i in 0..MESSAGES {
+                let message = format!("message {}", i);
+                loop {
+                    let result = if i % 2 == 0 {
+                        producer.push(trace, &message)
+                    } else {
+                        producer.push(note, &message)
+                    };
+                    if result.is_ok() {
+                        break;
+                    }
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut received = 0;
+        while received < MESSAGES {
+            consumer.drain_with(|entry, message| {
+                assert_eq!(entry, if received % 2 == 0 { 1 } else { 2 });
+                assert_eq!(
+                    message.to_str().unwrap(),
+                    format!("message {}", received).as_str()
+                );
+                received += 1;
+            });
+            thread::yield_now();
+        }
+
+        producer_thread.join().unwrap();
+        assert_eq!(consumer.drain_with(|_, _| panic!()), 0);
+    }
+
+    #[test]
+    fn test_full_buffer_and_truncation() {
+        let trace: URID<TraceClass> = unsafe { URID::new_unchecked(1) };
+        let (mut producer, mut consumer) = RtLogBuffer::new(2, 6).split();
+
+        producer.push(trace, "abcdefgh").unwrap();
+        producer.push(trace, "ab\0cd").unwrap();
+        assert_eq!(producer.push(trace, "lost"), Err(BufferFull));
+
+        let mut messages = Vec::new();
+        assert_eq!(
+            consumer.drain_with(|_, message| messages.push(message.to_owned())),
+            2
+        );
+        assert_eq!(messages[0].to_str().unwrap(), "abcde");
+        assert_eq!(messages[1].to_str().unwrap(), "ab");
+
+        // Truncation respects character boundaries.
+        producer.push(trace, "abcdé").unwrap();
+        consumer.drain_with(|_, message| assert_eq!(message.to_str().unwrap(), "abcd"));
+    }
+}
@@ -79,6 +79,7 @@ pub mod raw;
 pub mod wmidi_binding;
 
 /// Collection with the URIDs of all `UriBound`s in this crate.
+#[derive(Clone, Copy)]
 pub struct MidiURIDCollection {
     pub raw: URID<raw::MidiEvent>,
     #[cfg(feature = "wmidi")]
@@ -101,7 +102,9 @@ impl URIDCollection for MidiURIDCollection {
 
 /// Prelude for wildcard use, containing many important types.
 pub mod prelude {
-    pub use crate::raw::MidiEvent;
+    pub use crate::raw::{new_midi_event, MidiEvent};
+    #[cfg(feature = "wmidi")]
+    pub use crate::wmidi_binding::normalize_note;
     #[cfg(feature = "wmidi")]
     pub use crate::wmidi_binding::SystemExclusiveWMidiEvent;
     #[cfg(feature = "wmidi")]
@@ -4,11 +4,13 @@
 //!
 //! If you just want to use MIDI messages in your plugin, you should use the optional `wmidi` feature.
 use atom::atom_prelude::*;
+use atom::atoms::sequence::{SequenceUnit, SequenceWriter};
+use atom::space::SpaceWriterSplitAllocation;
 use urid::*;
 
 /// Midi Event.
 ///
-/// This low-level implementation is basically the same as a chunk atom: It reads a slice of bytes and writes with a `ByteWriter`.
+/// This low-level implementation is basically the same as a chunk atom: It reads a slice of bytes and writes with a [`MidiEventWriter`](struct.MidiEventWriter.html), which checks that the written bytes form a complete message.
 pub struct MidiEvent;
 
 unsafe impl UriBound for MidiEvent {
@@ -24,7 +26,7 @@ impl<'a> AtomHandle<'a> for MidiEventReadHandle {
 pub struct MidiEventWriteHandle;
 
 impl<'a> AtomHandle<'a> for MidiEventWriteHandle {
-    type Handle = AtomWriter<'a>;
+    type Handle = MidiEventWriter<'a>;
 }
 
 impl Atom for MidiEvent {
@@ -35,7 +37,233 @@ impl Atom for MidiEvent {
         Ok(body.as_bytes())
     }
 
-    fn write(frame: AtomWriter) -> Result<AtomWriter, AtomWriteError> {
-        Ok(frame)
+    fn write(frame: AtomWriter) -> Result<MidiEventWriter, AtomWriteError> {
+        Ok(MidiEventWriter { writer: frame })
+    }
+}
+
+/// Writing handle for raw MIDI events.
+///
+/// Messages written with [`set`](#method.set) are validated. The writer also implements [`SpaceWriter`](../../lv2_atom/space/trait.SpaceWriter.html), like the plain atom writer it wraps, but bytes written with its methods aren't checked.
+pub struct MidiEventWriter<'a> {
+    writer: AtomWriter<'a>,
+}
+
+impl<'a> MidiEventWriter<'a> {
+    /// Write a MIDI message.
+    ///
+    /// The message is checked with [`validate_message`](fn.validate_message.html) before it's written.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `IllegalOperation` error if the message is malformed. In this case, nothing is written.
+    ///
+    /// It will also return an error if there is not enough space in the underlying buffer, or if any other write error occurs.
+    pub fn set(mut self, bytes: &[u8]) -> Result<(), AtomWriteError> {
+        validate_message(bytes)?;
+        self.writer.write_bytes(bytes)?;
+        Ok(())
+    }
+}
+
+impl<'a> SpaceAllocator for MidiEventWriter<'a> {
+    #[inline]
+    fn allocate_and_split(
+        &mut self,
+        size: usize,
+    ) -> Result<SpaceWriterSplitAllocation<'_>, AtomWriteError> {
+        self.writer.allocate_and_split(size)
+    }
+
+    #[inline]
+    unsafe fn rewind(&mut self, byte_count: usize) -> Result<(), AtomWriteError> {
+        self.writer.rewind(byte_count)
+    }
+
+    #[inline]
+    fn allocated_bytes(&self) -> &[u8] {
+        self.writer.allocated_bytes()
+    }
+
+    #[inline]
+    unsafe fn allocated_bytes_mut(&mut self) -> &mut [u8] {
+        self.writer.allocated_bytes_mut()
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> &[u8] {
+        self.writer.remaining_bytes()
+    }
+}
+
+/// Return the length of a message with the given status byte, or `None` if the message has a variable length.
+fn message_length(status: u8) -> Option<usize> {
+    match status {
+        // Program Change and Channel Pressure have one data byte.
+        0xC0..=0xDF => Some(2),
+        // All other channel voice messages have two data bytes.
+        0x80..=0xEF => Some(3),
+        // System Exclusive messages, and their end, have an arbitrary length.
+        0xF0 | 0xF7 => None,
+        // MIDI Time Code Quarter Frame and Song Select.
+        0xF1 | 0xF3 => Some(2),
+        // Song Position Pointer.
+        0xF2 => Some(3),
+        // Tune Request, the undefined messages and all realtime messages.
+        _ => Some(1),
+    }
+}
+
+/// Check that the given bytes form a single, complete MIDI message.
+///
+/// The message has to start with a status byte, since LV2 MIDI events may not use running status. The length of the message has to match the length of the message type noted in the status byte, with the exception of System Exclusive messages, which may have an arbitrary length.
+///
+/// # Errors
+///
+/// This function returns an `IllegalOperation` error if the message is empty, doesn't start with a status byte, or has the wrong length for its type.
+pub fn validate_message(bytes: &[u8]) -> Result<(), AtomWriteError> {
+    let error_message = match bytes.first() {
+        None => "MIDI messages may not be empty",
+        Some(status) if status & 0x80 == 0 => "MIDI messages have to start with a status byte",
+        Some(status) => match message_length(*status) {
+            Some(length) if bytes.len() < length => "MIDI message is truncated",
+            Some(length) if bytes.len() > length => "MIDI message is too long for its type",
+            _ => return Ok(()),
+        },
+    };
+
+    Err(AtomWriteError::IllegalOperation {
+        writing_type_uri: MidiEvent::uri(),
+        error_message,
+    })
+}
+
+/// Write a MIDI event with the given raw bytes to a sequence.
+///
+/// This initializes the event with the given time stamp and copies the message into it in one step. The message has to be valid according to [`validate_message`](fn.validate_message.html).
+///
+/// # Errors
+///
+/// This function returns an `IllegalOperation` error if the message is malformed. In this case, nothing is written to the sequence.
+///
+/// It will also return an error if the time stamp is earlier than the one of the previous event, if there is not enough space in the underlying buffer, or if any other write error occurs.
+pub fn new_midi_event<U: SequenceUnit>(
+    sequence: &mut SequenceWriter<U>,
+    time_stamp: U::Value,
+    urid: URID<MidiEvent>,
+    bytes: &[u8],
+) -> Result<(), AtomWriteError> {
+    validate_message(bytes)?;
+    sequence.new_event(time_stamp, urid)?.set(bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::raw::*;
+    use atom::space::{AlignedVec, SpaceCursor};
+    use atom::AtomHeader;
+    use lv2_units::units::Frame;
+
+    #[test]
+    fn test_new_midi_event() {
+        let map = HashURIDMapper::new();
+        let atom_urids = AtomURIDCollection::from_map(&map).unwrap();
+        let urid = map.map_type::<MidiEvent>().unwrap();
+        let frame = map.map_type::<Frame>().unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut sequence = space
+                .write_atom(atom_urids.sequence)
+                .unwrap()
+                .with_unit(frame)
+                .unwrap();
+            new_midi_event(&mut sequence, 3, urid, &[0x90, 0x40, 0x7f]).unwrap();
+
+            // Messages have to start with a status byte and have to be complete.
+            assert!(new_midi_event(&mut sequence, 4, urid, &[0x40, 0x7f]).is_err());
+            assert!(new_midi_event(&mut sequence, 4, urid, &[]).is_err());
+            assert!(new_midi_event(&mut sequence, 4, urid, &[0x90, 0x40]).is_err());
+
+            new_midi_event(&mut sequence, 5, urid, &[0x80, 0x40, 0x00]).unwrap();
+        }
+
+        // reading
+        {
+            let events: Vec<(i64, &[u8])> = unsafe { raw_space.read().next_atom() }
+                .unwrap()
+                .read(atom_urids.sequence)
+                .unwrap()
+                .with_unit(frame)
+                .unwrap()
+                .map(|(stamp, atom)| (stamp, atom.read(urid).unwrap()))
+                .collect();
+            assert_eq!(
+                events,
+                [(3, &[0x90, 0x40, 0x7f][..]), (5, &[0x80, 0x40, 0x00][..])]
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_message() {
+        // Note On
+        assert!(validate_message(&[0x90, 0x40, 0x7f]).is_ok());
+        assert!(validate_message(&[0x90, 0x40]).is_err());
+        assert!(validate_message(&[0x90, 0x40, 0x7f, 0x00]).is_err());
+        // Program Change
+        assert!(validate_message(&[0xC3, 0x05]).is_ok());
+        assert!(validate_message(&[0xC3]).is_err());
+        // System Exclusive
+        assert!(validate_message(&[0xF0, 0x7E, 0x7F, 0x09, 0x01, 0xF7]).is_ok());
+        assert!(validate_message(&[0xF0]).is_ok());
+        // Timing Clock
+        assert!(validate_message(&[0xF8]).is_ok());
+        assert!(validate_message(&[0xF8, 0x00]).is_err());
+        // Running status and empty messages
+        assert!(validate_message(&[0x40, 0x7f]).is_err());
+        assert!(validate_message(&[]).is_err());
+    }
+
+    #[test]
+    fn test_midi_event_writer() {
+        let map = HashURIDMapper::new();
+        let urid = map.map_type::<MidiEvent>().unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(4);
+        let raw_space = raw_space.as_space_mut();
+
+        // A truncated Note On is rejected without writing anything.
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let writer = space.write_atom(urid).unwrap();
+            assert!(writer.set(&[0x90, 0x40]).is_err());
+        }
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        assert_eq!(atom.read(urid).unwrap(), &[]);
+
+        // A valid Program Change is written.
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let writer = space.write_atom(urid).unwrap();
+            writer.set(&[0xC0, 0x05]).unwrap();
+        }
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        assert_eq!(atom.read(urid).unwrap(), &[0xC0, 0x05]);
+
+        // Raw bytes can still be written without validation.
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = space.write_atom(urid).unwrap();
+            writer.write_bytes(&[0x90]).unwrap();
+            writer.write_bytes(&[0x40, 0x7f]).unwrap();
+        }
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        assert_eq!(atom.read(urid).unwrap(), &[0x90, 0x40, 0x7f]);
     }
 }
@@ -37,15 +37,31 @@ pub struct WMidiEventWriter<'a> {
 }
 
 impl<'a> WMidiEventWriter<'a> {
+    /// Write the message to the atom.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an [`OutOfSpace`](../../lv2_atom/space/error/enum.AtomWriteError.html#variant.OutOfSpace) error if the message doesn't fit into the buffer, for example if a system exclusive message is too long. The `requested` field of the error contains the size of the message. If the message could not be serialized by `wmidi`, an `IllegalOperation` error is returned.
     #[inline]
     pub fn set(mut self, message: wmidi::MidiMessage) -> Result<(), AtomWriteError> {
         let space = self.writer.allocate(message.bytes_size())?;
 
-        // The error shouldn't be happening, as we allocated just as many bytes as needed
-        message.copy_to_slice(space).expect(
-            "Could not copy MIDI message to allocated bytes. This is a bug, most likely due to an incorrect allocator implementation",
-        );
-        Ok(())
+        message
+            .copy_to_slice(space)
+            .map(|_| ())
+            .map_err(to_write_error)
+    }
+}
+
+/// Convert an error of `wmidi` into an atom writing error.
+///
+/// `AtomWriteError` is defined in `lv2-atom`, which doesn't depend on `wmidi`, so this can't be a `From` implementation.
+fn to_write_error(error: wmidi::ToSliceError) -> AtomWriteError {
+    match error {
+        wmidi::ToSliceError::BufferTooSmall => AtomWriteError::IllegalOperation {
+            writing_type_uri: WMidiEvent::uri(),
+            error_message: "MIDI message does not fit into the allocated space",
+        },
     }
 }
 
@@ -55,12 +71,7 @@ impl Atom for WMidiEvent {
 
     #[inline]
     unsafe fn read(space: &AtomSpace) -> Result<wmidi::MidiMessage, AtomReadError> {
-        wmidi::MidiMessage::try_from(space.as_bytes()).map_err(|_| {
-            AtomReadError::InvalidAtomValue {
-                reading_type_uri: Self::uri(),
-                error_message: "Invalid MIDI message",
-            }
-        })
+        parse_message(space.as_bytes())
     }
 
     #[inline]
@@ -69,6 +80,133 @@ impl Atom for WMidiEvent {
     }
 }
 
+/// Parse a complete MIDI message, which has to start with a status byte.
+fn parse_message(bytes: &[u8]) -> Result<wmidi::MidiMessage, AtomReadError> {
+    // `wmidi` doesn't expect messages to start with a data byte, so these are rejected here.
+    match bytes.first() {
+        Some(status) if status & 0x80 != 0 => {
+            wmidi::MidiMessage::try_from(bytes).map_err(|_| AtomReadError::InvalidAtomValue {
+                reading_type_uri: WMidiEvent::uri(),
+                error_message: "Invalid MIDI message",
+            })
+        }
+        _ => Err(AtomReadError::InvalidAtomValue {
+            reading_type_uri: WMidiEvent::uri(),
+            error_message: "MIDI message does not start with a status byte",
+        }),
+    }
+}
+
+/// A reader for MIDI events that may use running status.
+///
+/// Some hosts omit the status byte of a message if it's the same as the status byte of the previous message, which is called "running status". [`WMidiEvent`](struct.WMidiEvent.html) rejects such events, but this reader remembers the last status byte and uses it to reconstruct the full message.
+///
+/// This means that the reader is stateful: It has to see all events of a sequence in order, and you should create a new reader for every pass over a sequence. System common messages cancel the running status, while realtime messages leave it untouched.
+///
+/// ```
+/// # use lv2_atom::prelude::*;
+/// # use lv2_midi::wmidi_binding::{RunningStatusReader, WMidiEvent};
+/// # use urid::*;
+/// fn handle_messages(events: &[&UnidentifiedAtom], urid: URID<WMidiEvent>) {
+///     let mut reader = RunningStatusReader::new();
+///     for event in events {
+///         if let Ok(message) = reader.read_with_running_status(event, urid) {
+///             println!("{:?}", message);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Default, Debug)]
+pub struct RunningStatusReader {
+    status: Option<u8>,
+}
+
+impl RunningStatusReader {
+    /// Create a new reader without a running status.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a MIDI event, which may omit its status byte.
+    ///
+    /// If the event starts with a data byte, the status byte of the last channel message is prepended to it. Since the reconstructed message is copied out of the atom, it is returned as an owned message.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the atom isn't a MIDI event, if the event omits its status byte and there is no running status, or if the message is invalid.
+    pub fn read_with_running_status<'a>(
+        &mut self,
+        atom: &'a UnidentifiedAtom,
+        urid: URID<WMidiEvent>,
+    ) -> Result<wmidi::MidiMessage<'a>, AtomReadError> {
+        if atom.header().urid() != urid {
+            return Err(AtomReadError::AtomUridMismatch {
+                expected_uri: WMidiEvent::uri(),
+                expected_urid: urid.into_general(),
+                found_urid: atom.header().urid(),
+            });
+        }
+        let bytes = atom.body().as_bytes();
+
+        match bytes.first() {
+            Some(status) if status & 0x80 != 0 => {
+                let message = parse_message(bytes)?;
+                match status {
+                    0x80..=0xEF => self.status = Some(*status),
+                    0xF0..=0xF7 => self.status = None,
+                    _ => (),
+                }
+                Ok(message)
+            }
+            Some(_) => {
+                let status = self.status.ok_or(AtomReadError::InvalidAtomValue {
+                    reading_type_uri: WMidiEvent::uri(),
+                    error_message:
+                        "MIDI message uses running status without a preceding status byte",
+                })?;
+                if bytes.len() > 2 {
+                    return Err(AtomReadError::InvalidAtomValue {
+                        reading_type_uri: WMidiEvent::uri(),
+                        error_message: "MIDI message is too long for running status",
+                    });
+                }
+                let mut message = [status, 0, 0];
+                message[1..=bytes.len()].copy_from_slice(bytes);
+                Ok(parse_message(&message[..=bytes.len()])?.to_owned())
+            }
+            None => Err(AtomReadError::InvalidAtomValue {
+                reading_type_uri: WMidiEvent::uri(),
+                error_message: "Empty MIDI message",
+            }),
+        }
+    }
+}
+
+/// Normalize a note message.
+///
+/// Per MIDI convention, a `NoteOn` message with a velocity of zero is equivalent to a `NoteOff` message. This function rewrites such messages to `NoteOff` messages with a velocity of zero and returns all other messages unchanged.
+///
+/// You should normalize messages before you count active notes, since you will otherwise miss note releases and end up with stuck notes.
+///
+/// ```
+/// use lv2_midi::wmidi_binding::normalize_note;
+/// use wmidi::*;
+///
+/// let message = MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MIN);
+/// assert_eq!(
+///     MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7::MIN),
+///     normalize_note(message)
+/// );
+/// ```
+pub fn normalize_note(message: wmidi::MidiMessage) -> wmidi::MidiMessage {
+    match message {
+        wmidi::MidiMessage::NoteOn(channel, note, velocity) if velocity == wmidi::U7::MIN => {
+            wmidi::MidiMessage::NoteOff(channel, note, velocity)
+        }
+        _ => message,
+    }
+}
+
 /// System exclusive MIDI event.
 ///
 /// This atom is an alternative to [`WMidiEvent`](struct.WMidiEvent.html): It can only write system exclusive messages, but doesn't require the message to be already constructed in memory. Instead, it uses a writing handle similar to a chunk's writing handle.
@@ -178,6 +316,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_too_long_sysex() {
+        let map = HashURIDMapper::new();
+        let urid = map.map_type::<WMidiEvent>().unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(2);
+        let raw_space = raw_space.as_space_mut();
+
+        let data = [0x42; 32];
+        let message = MidiMessage::SysEx(U7::try_from_bytes(&data).unwrap());
+
+        let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+        let error = space.write_atom(urid).unwrap().set(message).unwrap_err();
+        assert_eq!(
+            error,
+            AtomWriteError::OutOfSpace {
+                used: 8,
+                capacity: 16,
+                requested: 34
+            }
+        );
+    }
+
+    #[test]
+    fn test_normalize_note() {
+        let note_off = MidiMessage::NoteOff(Channel::Ch3, Note::A4, U7::MIN);
+        assert_eq!(
+            note_off,
+            normalize_note(MidiMessage::NoteOn(Channel::Ch3, Note::A4, U7::MIN))
+        );
+        assert_eq!(note_off, normalize_note(note_off.clone()));
+
+        let note_on = MidiMessage::NoteOn(Channel::Ch3, Note::A4, U7::try_from(1).unwrap());
+        assert_eq!(note_on, normalize_note(note_on.clone()));
+    }
+
     #[test]
     fn test_sysex_event() {
         let map = HashURIDMapper::new();
@@ -212,4 +386,74 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_running_status() {
+        let map = HashURIDMapper::new();
+        let urid = map.map_type::<WMidiEvent>().unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let raw_space = raw_space.as_space_mut();
+
+        // writing
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            for bytes in [
+                &[0x42, 0x10][..],
+                &[0x91, 0x40, 0x7f],
+                &[0x41, 0x7f],
+                &[0xF8],
+                &[0x42, 0x00],
+                &[0xF2, 0x01, 0x02],
+                &[0x43, 0x7f],
+            ]
+            .iter()
+            {
+                space
+                    .write_value(lv2_sys::LV2_Atom {
+                        size: bytes.len() as u32,
+                        type_: urid.get(),
+                    })
+                    .unwrap();
+                space.write_bytes(bytes).unwrap();
+                space.write_bytes(&[0; 8][bytes.len()..]).unwrap();
+            }
+        }
+
+        // reading
+        {
+            let mut space_reader = raw_space.read();
+            let events: Vec<&UnidentifiedAtom> = (0..7)
+                .map(|_| unsafe { space_reader.next_atom() }.unwrap())
+                .collect();
+
+            // The strict reader rejects running status.
+            assert!(events[2].read(urid).is_err());
+
+            let mut reader = RunningStatusReader::new();
+            let velocity = U7::try_from(0x7f).unwrap();
+            // There is no running status yet.
+            assert!(reader.read_with_running_status(events[0], urid).is_err());
+            assert_eq!(
+                reader.read_with_running_status(events[1], urid).unwrap(),
+                MidiMessage::NoteOn(Channel::Ch2, Note::E3, velocity)
+            );
+            assert_eq!(
+                reader.read_with_running_status(events[2], urid).unwrap(),
+                MidiMessage::NoteOn(Channel::Ch2, Note::F3, velocity)
+            );
+            // Realtime messages don't cancel the running status.
+            assert_eq!(
+                reader.read_with_running_status(events[3], urid).unwrap(),
+                MidiMessage::TimingClock
+            );
+            assert_eq!(
+                reader.read_with_running_status(events[4], urid).unwrap(),
+                MidiMessage::NoteOn(Channel::Ch2, Note::FSharp3, U7::MIN)
+            );
+            // System common messages do.
+            reader.read_with_running_status(events[5], urid).unwrap();
+            assert!(reader.read_with_running_status(events[6], urid).is_err());
+        }
+    }
 }
@@ -0,0 +1,456 @@
+use crate::{OptionRequest, OptionValue, OptionsError};
+use core::extension::ExtensionDescriptor;
+use core::prelude::*;
+use std::marker::PhantomData;
+use urid::*;
+
+/// A plugin extension that lets the host get and set the plugin's options at runtime.
+///
+/// The host may request or set several options at once. The methods of this trait are called once for every option; The errors they return are combined and reported to the host.
+pub trait OptionsInterface: Plugin {
+    /// The URIs of all option keys the plugin supports.
+    ///
+    /// This list can be used by tooling to document the plugin's options. If it isn't empty and the plugin returns a [`SupportedOptions`](struct.SupportedOptions.html) collection from [`supported_options`](#method.supported_options), all other keys are rejected with [`BadKey`](enum.OptionsError.html#variant.BadKey) before `get` or `set` are called.
+    ///
+    /// **Setting this constant alone doesn't filter any keys.** The options interface can't map URIs, so the default implementation of `supported_options` returns `None` and `get` and `set` are called for every key. To enable the filtering, map the keys by embedding a `SupportedOptions<Self>` collection in the plugin's URID collection and return it from `supported_options`.
+    const SUPPORTED_OPTIONS: &'static [&'static Uri] = &[];
+
+    /// The mapped URIDs of the supported option keys.
+    ///
+    /// URIs can't be mapped in the options interface itself, so the plugin has to map them beforehand, usually as a part of its URID collection. The default implementation returns `None`, which disables the filtering of keys.
+    fn supported_options(&self) -> Option<&SupportedOptions<Self>> {
+        None
+    }
+
+    /// Answer the host's request for the value of an option.
+    ///
+    /// The value has to be borrowed from the plugin, since the host may read it until the next call to the options interface.
+    fn get<'a>(&'a self, request: OptionRequest<'a>) -> Result<(), OptionsError>;
+
+    /// Set the value of an option.
+    ///
+    /// Unknown keys should be rejected with [`BadKey`](enum.OptionsError.html#variant.BadKey), and values of known keys that are malformed or not acceptable should be rejected with [`BadValue`](enum.OptionsError.html#variant.BadValue).
+    ///
+    /// The default implementation rejects every option with [`OptionsError::read_only_set`](enum.OptionsError.html#method.read_only_set), which makes all options of the plugin read-only. Plugins that only expose static configuration therefore only have to implement `get`.
+    fn set(&mut self, _option: OptionValue) -> Result<(), OptionsError> {
+        OptionsError::read_only_set()
+    }
+}
+
+/// Return the URI of an option key in a constant context.
+///
+/// This can be used to declare the [`SUPPORTED_OPTIONS`](trait.OptionsInterface.html#associatedconstant.SUPPORTED_OPTIONS) of a plugin.
+pub const fn option_key<K: UriBound + ?Sized>() -> &'static Uri {
+    unsafe { Uri::from_bytes_with_nul_unchecked(K::URI) }
+}
+
+/// The mapped URIDs of the [`SUPPORTED_OPTIONS`](trait.OptionsInterface.html#associatedconstant.SUPPORTED_OPTIONS) of a plugin.
+///
+/// This is a [`URIDCollection`](../urid/trait.URIDCollection.html) and can therefore be embedded in the URID collection of the plugin.
+pub struct SupportedOptions<P: OptionsInterface> {
+    keys: Vec<URID>,
+    plugin: PhantomData<P>,
+}
+
+impl<P: OptionsInterface> SupportedOptions<P> {
+    /// Check whether the given key is supported by the plugin.
+    pub fn contains(&self, key: URID) -> bool {
+        self.keys.contains(&key)
+    }
+
+    /// The URIDs of all supported keys, in the order of `SUPPORTED_OPTIONS`.
+    pub fn keys(&self) -> &[URID] {
+        &self.keys
+    }
+
+    /// Check the key of an option against the supported keys.
+    fn check(this: Option<&Self>, key: u32) -> Result<(), OptionsError> {
+        match (this, URID::new(key)) {
+            (Some(supported), Some(key)) if !supported.keys.is_empty() => {
+                if supported.contains(key) {
+                    Ok(())
+                } else {
+                    Err(OptionsError::BadKey)
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<P: OptionsInterface> URIDCollection for SupportedOptions<P> {
+    fn from_map<M: Map + ?Sized>(map: &M) -> Option<Self> {
+        Some(Self {
+            keys: map.map_all(P::SUPPORTED_OPTIONS)?,
+            plugin: PhantomData,
+        })
+    }
+}
+
+/// Raw wrapper of the [`OptionsInterface`](trait.OptionsInterface.html) extension.
+///
+/// This is a marker type that has the required external methods for the extension.
+pub struct OptionsDescriptor<P: OptionsInterface> {
+    plugin: PhantomData<P>,
+}
+
+unsafe impl<P: OptionsInterface> UriBound for OptionsDescriptor<P> {
+    const URI: &'static [u8] = sys::LV2_OPTIONS__interface;
+}
+
+impl<P: OptionsInterface> OptionsDescriptor<P> {
+    /// Handle a get request by the host.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it is an interface for hosts written in C and since it dereferences raw pointers.
+    pub unsafe extern "C" fn extern_get(
+        instance: sys::LV2_Handle,
+        options: *mut sys::LV2_Options_Option,
+    ) -> u32 {
+        let plugin: &P = if let Some(plugin) = (instance as *const P).as_ref() {
+            plugin
+        } else {
+            return sys::LV2_Options_Status_LV2_OPTIONS_ERR_UNKNOWN;
+        };
+        if options.is_null() {
+            return sys::LV2_Options_Status_LV2_OPTIONS_ERR_UNKNOWN;
+        }
+
+        let mut status = sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS;
+        let mut option = options;
+        while (*option).key != 0 {
+            let supported = SupportedOptions::check(plugin.supported_options(), (*option).key);
+            status |= OptionsError::into_raw(
+                supported.and_then(|_| plugin.get(OptionRequest::from_raw(&mut *option))),
+            );
+            option = option.add(1);
+        }
+        status
+    }
+
+    /// Handle a set request by the host.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it is an interface for hosts written in C and since it dereferences raw pointers.
+    pub unsafe extern "C" fn extern_set(
+        instance: sys::LV2_Handle,
+        options: *const sys::LV2_Options_Option,
+    ) -> u32 {
+        let plugin: &mut P = if let Some(plugin) = (instance as *mut P).as_mut() {
+            plugin
+        } else {
+            return sys::LV2_Options_Status_LV2_OPTIONS_ERR_UNKNOWN;
+        };
+        if options.is_null() {
+            return sys::LV2_Options_Status_LV2_OPTIONS_ERR_UNKNOWN;
+        }
+
+        let mut status = sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS;
+        let mut option = options;
+        while (*option).key != 0 {
+            let supported = SupportedOptions::check(plugin.supported_options(), (*option).key);
+            status |= OptionsError::into_raw(
+                supported.and_then(|_| plugin.set(OptionValue::from_raw(&*option))),
+            );
+            option = option.add(1);
+        }
+        status
+    }
+}
+
+impl<P: OptionsInterface> ExtensionDescriptor for OptionsDescriptor<P> {
+    type ExtensionInterface = sys::LV2_Options_Interface;
+
+    const INTERFACE: &'static sys::LV2_Options_Interface = &sys::LV2_Options_Interface {
+        get: Some(Self::extern_get),
+        set: Some(Self::extern_set),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use atom::prelude::*;
+    use lv2_core::prelude::*;
+    use std::ffi::c_void;
+    use std::mem::size_of;
+    use urid::*;
+
+    #[uri("urn:lv2_options:test:gain")]
+    struct Gain;
+
+    #[uri("urn:lv2_options:test:unknown")]
+    struct Unknown;
+
+    #[uri("urn:lv2_options:test:amp")]
+    struct Amp {
+        gain: f32,
+        gain_urid: URID<Gain>,
+        urids: AtomURIDCollection,
+    }
+
+    impl Plugin for Amp {
+        type InitFeatures = ();
+        type AudioFeatures = ();
+        type Ports = ();
+
+        fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+            None
+        }
+
+        fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+    }
+
+    impl OptionsInterface for Amp {
+        fn get<'a>(&'a self, request: OptionRequest<'a>) -> Result<(), OptionsError> {
+            if request.key() != Some(self.gain_urid.into_general()) {
+                return Err(OptionsError::BadKey);
+            }
+            request.respond(self.urids.float, &self.gain);
+            Ok(())
+        }
+
+        fn set(&mut self, option: OptionValue) -> Result<(), OptionsError> {
+            if option.key() != Some(self.gain_urid.into_general()) {
+                return Err(OptionsError::BadKey);
+            }
+            let gain = option.as_float(self.urids.float)?;
+            if gain < 0.0 {
+                return Err(OptionsError::BadValue);
+            }
+            self.gain = gain;
+            Ok(())
+        }
+    }
+
+    fn option<T>(
+        key: URID<impl ?Sized>,
+        type_: URID<impl ?Sized>,
+        value: &T,
+    ) -> sys::LV2_Options_Option {
+        sys::LV2_Options_Option {
+            context: sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+            subject: 0,
+            key: key.get(),
+            size: size_of::<T>() as u32,
+            type_: type_.get(),
+            value: value as *const T as *const c_void,
+        }
+    }
+
+    fn terminator() -> sys::LV2_Options_Option {
+        sys::LV2_Options_Option {
+            context: 0,
+            subject: 0,
+            key: 0,
+            size: 0,
+            type_: 0,
+            value: std::ptr::null(),
+        }
+    }
+
+    #[test]
+    fn test_set_and_get() {
+        type Descriptor = OptionsDescriptor<Amp>;
+        let map = HashURIDMapper::new();
+        let mut plugin = Amp {
+            gain: 1.0,
+            gain_urid: map.map_type().unwrap(),
+            urids: map.populate_collection().unwrap(),
+        };
+        let unknown: URID<Unknown> = map.map_type().unwrap();
+        let instance = &mut plugin as *mut Amp as sys::LV2_Handle;
+
+        // A valid value is accepted.
+        let options = [
+            option(plugin.gain_urid, plugin.urids.float, &2.0f32),
+            terminator(),
+        ];
+        assert_eq!(sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS, unsafe {
+            Descriptor::extern_set(instance, options.as_ptr())
+        });
+        assert_eq!(2.0, plugin.gain);
+
+        // A known key with a value of the wrong type is rejected.
+        let options = [
+            option(plugin.gain_urid, plugin.urids.int, &3i32),
+            terminator(),
+        ];
+        let status = unsafe { Descriptor::extern_set(instance, options.as_ptr()) };
+        assert_eq!(sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE, status);
+        assert_eq!(Err(OptionsError::BadValue), OptionsError::from_raw(status));
+
+        // A known key with an unacceptable value is rejected.
+        let options = [
+            option(plugin.gain_urid, plugin.urids.float, &-1.0f32),
+            terminator(),
+        ];
+        assert_eq!(sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE, unsafe {
+            Descriptor::extern_set(instance, options.as_ptr())
+        });
+        assert_eq!(2.0, plugin.gain);
+
+        // Unknown keys are rejected, and the errors of several options are combined.
+        let options = [
+            option(unknown, plugin.urids.float, &1.0f32),
+            option(plugin.gain_urid, plugin.urids.float, &-1.0f32),
+            terminator(),
+        ];
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY
+                | sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE,
+            unsafe { Descriptor::extern_set(instance, options.as_ptr()) }
+        );
+
+        // The value can be requested by the host.
+        let mut options = [
+            sys::LV2_Options_Option {
+                key: plugin.gain_urid.get(),
+                ..terminator()
+            },
+            terminator(),
+        ];
+        assert_eq!(sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS, unsafe {
+            Descriptor::extern_get(instance, options.as_mut_ptr())
+        });
+        let value = unsafe { OptionValue::from_raw(&options[0]) };
+        assert_eq!(Some(Subject::Instance), value.subject());
+        assert_eq!(2.0, value.as_float(plugin.urids.float).unwrap());
+    }
+
+    #[uri("urn:lv2_options:test:permissive")]
+    struct Permissive {
+        gain: f32,
+        supported: SupportedOptions<Self>,
+        urids: AtomURIDCollection,
+    }
+
+    impl Plugin for Permissive {
+        type InitFeatures = ();
+        type AudioFeatures = ();
+        type Ports = ();
+
+        fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+            None
+        }
+
+        fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+    }
+
+    impl OptionsInterface for Permissive {
+        const SUPPORTED_OPTIONS: &'static [&'static Uri] = &[option_key::<Gain>()];
+
+        fn supported_options(&self) -> Option<&SupportedOptions<Self>> {
+            Some(&self.supported)
+        }
+
+        fn get<'a>(&'a self, request: OptionRequest<'a>) -> Result<(), OptionsError> {
+            // Every key is answered; Unsupported keys have to be filtered by the descriptor.
+            request.respond(self.urids.float, &self.gain);
+            Ok(())
+        }
+
+        fn set(&mut self, option: OptionValue) -> Result<(), OptionsError> {
+            self.gain = option.as_float(self.urids.float)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_supported_options() {
+        type Descriptor = OptionsDescriptor<Permissive>;
+        let map = HashURIDMapper::new();
+        let gain: URID<Gain> = map.map_type().unwrap();
+        let unknown: URID<Unknown> = map.map_type().unwrap();
+        let mut plugin = Permissive {
+            gain: 1.0,
+            supported: map.populate_collection().unwrap(),
+            urids: map.populate_collection().unwrap(),
+        };
+        assert!(plugin.supported.contains(gain.into_general()));
+        assert!(!plugin.supported.contains(unknown.into_general()));
+        let instance = &mut plugin as *mut Permissive as sys::LV2_Handle;
+
+        // Supported keys are passed to the plugin.
+        let options = [option(gain, plugin.urids.float, &2.0f32), terminator()];
+        assert_eq!(sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS, unsafe {
+            Descriptor::extern_set(instance, options.as_ptr())
+        });
+        assert_eq!(2.0, plugin.gain);
+
+        // Unsupported keys are rejected before they reach the plugin.
+        let options = [option(unknown, plugin.urids.float, &3.0f32), terminator()];
+        assert_eq!(sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY, unsafe {
+            Descriptor::extern_set(instance, options.as_ptr())
+        });
+        assert_eq!(2.0, plugin.gain);
+
+        let mut options = [
+            sys::LV2_Options_Option {
+                key: unknown.get(),
+                ..terminator()
+            },
+            terminator(),
+        ];
+        assert_eq!(sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY, unsafe {
+            Descriptor::extern_get(instance, options.as_mut_ptr())
+        });
+        assert!(options[0].value.is_null());
+    }
+
+    #[uri("urn:lv2_options:test:read-only")]
+    struct ReadOnly {
+        gain: f32,
+        urids: AtomURIDCollection,
+    }
+
+    impl Plugin for ReadOnly {
+        type InitFeatures = ();
+        type AudioFeatures = ();
+        type Ports = ();
+
+        fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+            None
+        }
+
+        fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+    }
+
+    impl OptionsInterface for ReadOnly {
+        fn get<'a>(&'a self, request: OptionRequest<'a>) -> Result<(), OptionsError> {
+            request.respond(self.urids.float, &self.gain);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_read_only_options() {
+        type Descriptor = OptionsDescriptor<ReadOnly>;
+        let map = HashURIDMapper::new();
+        let gain: URID<Gain> = map.map_type().unwrap();
+        let mut plugin = ReadOnly {
+            gain: 1.0,
+            urids: map.populate_collection().unwrap(),
+        };
+        let instance = &mut plugin as *mut ReadOnly as sys::LV2_Handle;
+
+        let options = [option(gain, plugin.urids.float, &2.0f32), terminator()];
+        assert_eq!(sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY, unsafe {
+            Descriptor::extern_set(instance, options.as_ptr())
+        });
+        assert_eq!(1.0, plugin.gain);
+
+        let mut options = [
+            sys::LV2_Options_Option {
+                key: gain.get(),
+                ..terminator()
+            },
+            terminator(),
+        ];
+        assert_eq!(sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS, unsafe {
+            Descriptor::extern_get(instance, options.as_mut_ptr())
+        });
+    }
+}
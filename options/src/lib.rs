@@ -0,0 +1,82 @@
+//! Extension for LV2 plugins to receive options from the host.
+//!
+//! Options are configuration values like the block length or the sample rate, which are identified by a key URID and have an atom value. The host can pass options to the plugin on instantiation, using the [`OptionsList`](struct.OptionsList.html) feature, and may get and set them at runtime if the plugin implements the [`OptionsInterface`](trait.OptionsInterface.html).
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_core::prelude::*;
+//! use lv2_options::prelude::*;
+//! use urid::*;
+//!
+//! #[uri("urn:lv2_options:gain")]
+//! struct Gain;
+//!
+//! #[uri("urn:lv2_options:amp")]
+//! struct Amp {
+//!     gain: f32,
+//!     gain_urid: URID<Gain>,
+//!     urids: AtomURIDCollection,
+//! }
+//!
+//! impl OptionsInterface for Amp {
+//!     fn get<'a>(&'a self, request: OptionRequest<'a>) -> Result<(), OptionsError> {
+//!         if request.key() != Some(self.gain_urid.into_general()) {
+//!             return Err(OptionsError::BadKey);
+//!         }
+//!         request.respond(self.urids.float, &self.gain);
+//!         Ok(())
+//!     }
+//!
+//!     fn set(&mut self, option: OptionValue) -> Result<(), OptionsError> {
+//!         if option.key() != Some(self.gain_urid.into_general()) {
+//!             return Err(OptionsError::BadKey);
+//!         }
+//!         let gain = option.as_float(self.urids.float)?;
+//!         // A negative gain is a known key with an unacceptable value.
+//!         if gain < 0.0 {
+//!             return Err(OptionsError::BadValue);
+//!         }
+//!         self.gain = gain;
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # impl Plugin for Amp {
+//! #     type Ports = ();
+//! #     type InitFeatures = ();
+//! #     type AudioFeatures = ();
+//! #
+//! #     fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+//! #         None
+//! #     }
+//! #
+//! #     fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+//! #
+//!     fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+//!         // Export the options extension. Otherwise, the host won't use it.
+//!         match_extensions!(uri, OptionsDescriptor<Self>)
+//!     }
+//! # }
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_core as core;
+extern crate lv2_sys as sys;
+
+mod extensions;
+pub use extensions::*;
+
+mod list;
+pub use list::{OptionEntry, OptionsIter, OptionsList};
+
+pub mod option;
+pub use option::*;
+
+/// Prelude of `lv2_options` for wildcard usage.
+pub mod prelude {
+    pub use crate::{
+        option_key, OptionEntry, OptionRequest, OptionValue, OptionsDescriptor, OptionsError,
+        OptionsInterface, OptionsList, Subject, SupportedOptions,
+    };
+}
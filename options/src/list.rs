@@ -0,0 +1,178 @@
+use crate::{OptionValue, Subject};
+use core::feature::{Feature, ThreadingClass};
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use urid::*;
+
+/// Feature containing the options the host passes to a plugin on instantiation.
+///
+/// The options are stored in an array that is terminated by a zeroed option.
+pub struct OptionsList<'a> {
+    options: &'a sys::LV2_Options_Option,
+}
+
+unsafe impl<'a> UriBound for OptionsList<'a> {
+    const URI: &'static [u8] = sys::LV2_OPTIONS__options;
+}
+
+unsafe impl<'a> Feature for OptionsList<'a> {
+    unsafe fn from_feature_ptr(feature: *const c_void, _class: ThreadingClass) -> Option<Self> {
+        (feature as *const sys::LV2_Options_Option)
+            .as_ref()
+            .map(|options| Self { options })
+    }
+}
+
+impl<'a> OptionsList<'a> {
+    /// Look up the first option with the given key.
+    pub fn get<T: ?Sized>(&self, key: URID<T>) -> Option<OptionValue<'a>> {
+        let mut option: *const sys::LV2_Options_Option = self.options;
+        // SAFETY: The host guarantees that the list is terminated by a zeroed option and that the values are valid.
+        unsafe {
+            while (*option).key != 0 {
+                if (*option).key == key.get() {
+                    return Some(OptionValue::from_raw(&*option));
+                }
+                option = option.add(1);
+            }
+        }
+        None
+    }
+
+    /// Iterate over all options in the list.
+    pub fn iter(&self) -> OptionsIter<'a> {
+        OptionsIter {
+            option: self.options,
+            lifetime: PhantomData,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &OptionsList<'a> {
+    type Item = OptionEntry<'a>;
+    type IntoIter = OptionsIter<'a>;
+
+    fn into_iter(self) -> OptionsIter<'a> {
+        self.iter()
+    }
+}
+
+/// A decoded entry of an [`OptionsList`](struct.OptionsList.html).
+#[derive(Clone, Copy)]
+pub struct OptionEntry<'a> {
+    /// The thing the option applies to.
+    pub subject: Subject,
+    /// The key of the option.
+    pub key: URID,
+    /// The option itself, including its value.
+    pub value: OptionValue<'a>,
+}
+
+/// Iterator over the entries of an [`OptionsList`](struct.OptionsList.html).
+///
+/// The iterator stops at the zeroed option that terminates the list. Malformed options, i.e. options with an invalid subject or key, or with a value pointer but a size of zero, are skipped.
+pub struct OptionsIter<'a> {
+    option: *const sys::LV2_Options_Option,
+    lifetime: PhantomData<&'a sys::LV2_Options_Option>,
+}
+
+impl<'a> Iterator for OptionsIter<'a> {
+    type Item = OptionEntry<'a>;
+
+    fn next(&mut self) -> Option<OptionEntry<'a>> {
+        loop {
+            // SAFETY: The host guarantees that the list is terminated by a zeroed option and that the values are valid. The iterator never moves past the terminator.
+            let option: &'a sys::LV2_Options_Option = unsafe { &*self.option };
+            if option.key == 0 {
+                return None;
+            }
+            self.option = unsafe { self.option.add(1) };
+
+            if !option.value.is_null() && option.size == 0 {
+                continue;
+            }
+            let subject = Subject::from_raw(option.context, option.subject);
+            if let (Some(subject), Some(key)) = (subject, URID::new(option.key)) {
+                return Some(OptionEntry {
+                    subject,
+                    key,
+                    value: unsafe { OptionValue::from_raw(option) },
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use core::feature::{Feature, ThreadingClass};
+    use std::ffi::c_void;
+    use std::mem::size_of;
+    use std::ptr::null;
+
+    fn option(
+        context: sys::LV2_Options_Context,
+        subject: u32,
+        key: u32,
+        value: Option<&i32>,
+    ) -> sys::LV2_Options_Option {
+        sys::LV2_Options_Option {
+            context,
+            subject,
+            key,
+            size: value.map_or(0, |_| size_of::<i32>() as u32),
+            type_: 1,
+            value: value.map_or(null(), |value| value as *const i32 as *const c_void),
+        }
+    }
+
+    #[test]
+    fn test_iterate_options() {
+        let a = 17;
+        let b = 42;
+        let mut malformed = option(
+            sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+            0,
+            3,
+            Some(&a),
+        );
+        malformed.size = 0;
+        let options = [
+            option(
+                sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                0,
+                1,
+                Some(&a),
+            ),
+            malformed,
+            option(sys::LV2_Options_Context_LV2_OPTIONS_PORT, 2, 2, Some(&b)),
+            option(sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE, 0, 4, None),
+            option(0, 0, 0, None),
+            option(
+                sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                0,
+                5,
+                Some(&a),
+            ),
+        ];
+        let list = unsafe {
+            OptionsList::from_feature_ptr(options.as_ptr() as *const c_void, ThreadingClass::Other)
+        }
+        .unwrap();
+
+        let entries: Vec<OptionEntry> = (&list).into_iter().collect();
+        assert_eq!(3, entries.len());
+
+        assert_eq!(Subject::Instance, entries[0].subject);
+        assert_eq!(1, entries[0].key);
+        assert_eq!(Some(&a.to_ne_bytes()[..]), entries[0].value.data());
+
+        assert_eq!(Subject::Port(2), entries[1].subject);
+        assert_eq!(2, entries[1].key);
+        assert_eq!(Some(&b.to_ne_bytes()[..]), entries[1].value.data());
+
+        assert_eq!(4, entries[2].key);
+        assert_eq!(None, entries[2].value.data());
+    }
+}
@@ -0,0 +1,10 @@
+//! Single options and requests for options.
+mod error;
+mod request;
+mod subject;
+mod value;
+
+pub use error::OptionsError;
+pub use request::OptionRequest;
+pub use subject::Subject;
+pub use value::OptionValue;
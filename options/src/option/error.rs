@@ -0,0 +1,100 @@
+use std::error::Error;
+use std::fmt;
+
+/// Kinds of errors that may occur when getting or setting options.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionsError {
+    /// The kind of the error is unknown or doesn't have a representation.
+    Unknown,
+    /// The subject of the option is invalid or not supported.
+    BadSubject,
+    /// The key of the option is unknown or not supported.
+    BadKey,
+    /// The key of the option is known, but its value is malformed or not acceptable.
+    BadValue,
+}
+
+impl OptionsError {
+    /// Convert a raw status flag to a result or possible error value.
+    ///
+    /// The status flags of the options interface may be combined. In this case, the most specific error is returned.
+    pub fn from_raw(value: sys::LV2_Options_Status) -> Result<(), OptionsError> {
+        if value == sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS {
+            Ok(())
+        } else if value & sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE != 0 {
+            Err(OptionsError::BadValue)
+        } else if value & sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY != 0 {
+            Err(OptionsError::BadKey)
+        } else if value & sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_SUBJECT != 0 {
+            Err(OptionsError::BadSubject)
+        } else {
+            Err(OptionsError::Unknown)
+        }
+    }
+
+    /// The result of setting an option that can't be set.
+    ///
+    /// Setting an option that is read-only is treated like setting an unknown option, so this always returns [`BadKey`](#variant.BadKey). It's the default behaviour of [`OptionsInterface::set`](trait.OptionsInterface.html#method.set) and can also be used in custom implementations for read-only keys.
+    pub fn read_only_set() -> Result<(), OptionsError> {
+        Err(OptionsError::BadKey)
+    }
+
+    /// Convert a result to a raw status flag.
+    pub fn into_raw(result: Result<(), OptionsError>) -> sys::LV2_Options_Status {
+        match result {
+            Ok(()) => sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS,
+            Err(OptionsError::Unknown) => sys::LV2_Options_Status_LV2_OPTIONS_ERR_UNKNOWN,
+            Err(OptionsError::BadSubject) => sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_SUBJECT,
+            Err(OptionsError::BadKey) => sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY,
+            Err(OptionsError::BadValue) => sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE,
+        }
+    }
+}
+
+impl fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            OptionsError::Unknown => "Unknown error while getting or setting an option",
+            OptionsError::BadSubject => "Invalid or unsupported option subject",
+            OptionsError::BadKey => "Invalid or unsupported option key",
+            OptionsError::BadValue => "Invalid or unsupported option value",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl Error for OptionsError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::OptionsError;
+
+    #[test]
+    fn test_options_error_conversion() {
+        let errors = [
+            OptionsError::Unknown,
+            OptionsError::BadSubject,
+            OptionsError::BadKey,
+            OptionsError::BadValue,
+        ];
+        for error in errors.iter() {
+            assert_eq!(
+                Err(*error),
+                OptionsError::from_raw(OptionsError::into_raw(Err(*error)))
+            );
+        }
+        assert_eq!(
+            Ok(()),
+            OptionsError::from_raw(OptionsError::into_raw(Ok(())))
+        );
+
+        // Combined flags are reduced to the most specific error.
+        assert_eq!(
+            Err(OptionsError::BadValue),
+            OptionsError::from_raw(
+                sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY
+                    | sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE
+            )
+        );
+    }
+}
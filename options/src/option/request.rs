@@ -0,0 +1,41 @@
+use crate::Subject;
+use atom::atoms::scalar::ScalarAtom;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use urid::URID;
+
+/// A request of the host for the value of an option.
+///
+/// The plugin answers the request by [responding](#method.respond) with a value that lives at least as long as the plugin itself, since the host may read it until the next call to the options interface.
+pub struct OptionRequest<'a> {
+    inner: &'a mut sys::LV2_Options_Option,
+    lifetime: PhantomData<&'a [u8]>,
+}
+
+impl<'a> OptionRequest<'a> {
+    /// Wrap a raw option that should be filled by the plugin.
+    pub fn from_raw(inner: &'a mut sys::LV2_Options_Option) -> Self {
+        Self {
+            inner,
+            lifetime: PhantomData,
+        }
+    }
+
+    /// The subject of the requested option, or `None` if the subject is invalid.
+    pub fn subject(&self) -> Option<Subject> {
+        Subject::from_raw(self.inner.context, self.inner.subject)
+    }
+
+    /// The key of the requested option, or `None` if the key is invalid.
+    pub fn key(&self) -> Option<URID> {
+        URID::new(self.inner.key)
+    }
+
+    /// Answer the request with a scalar value.
+    pub fn respond<A: ScalarAtom>(self, urid: URID<A>, value: &'a A::InternalType) {
+        self.inner.type_ = urid.get();
+        self.inner.size = size_of::<A::InternalType>() as u32;
+        self.inner.value = value as *const A::InternalType as *const c_void;
+    }
+}
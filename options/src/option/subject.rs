@@ -0,0 +1,41 @@
+use urid::URID;
+
+/// The subject of an option, i.e. the thing the option applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Subject {
+    /// The option applies to the plugin instance itself.
+    Instance,
+    /// The option applies to the resource with the given URID.
+    Resource(URID),
+    /// The option applies to the blank node with the given ID.
+    Blank(u32),
+    /// The option applies to the port with the given index.
+    Port(u32),
+}
+
+impl Subject {
+    /// Create a subject from the raw context and subject fields of an option.
+    ///
+    /// Returns `None` if the context is unknown or if the resource URID is invalid.
+    pub fn from_raw(context: sys::LV2_Options_Context, subject: u32) -> Option<Self> {
+        match context {
+            sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE => Some(Subject::Instance),
+            sys::LV2_Options_Context_LV2_OPTIONS_RESOURCE => {
+                URID::new(subject).map(Subject::Resource)
+            }
+            sys::LV2_Options_Context_LV2_OPTIONS_BLANK => Some(Subject::Blank(subject)),
+            sys::LV2_Options_Context_LV2_OPTIONS_PORT => Some(Subject::Port(subject)),
+            _ => None,
+        }
+    }
+
+    /// Convert the subject to the raw context and subject fields of an option.
+    pub fn into_raw(self) -> (sys::LV2_Options_Context, u32) {
+        match self {
+            Subject::Instance => (sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE, 0),
+            Subject::Resource(urid) => (sys::LV2_Options_Context_LV2_OPTIONS_RESOURCE, urid.get()),
+            Subject::Blank(id) => (sys::LV2_Options_Context_LV2_OPTIONS_BLANK, id),
+            Subject::Port(index) => (sys::LV2_Options_Context_LV2_OPTIONS_PORT, index),
+        }
+    }
+}
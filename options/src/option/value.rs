@@ -0,0 +1,242 @@
+use crate::{OptionsError, Subject};
+use atom::atoms::scalar::{AtomURID, Bool, Double, Float, Int, Long, ScalarAtom};
+use atom::prelude::*;
+use atom::space::AtomSpace;
+use atom::AtomHandle;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use urid::URID;
+
+/// An option passed by the host, consisting of a subject, a key and a value.
+///
+/// The value is an atom body without the atom header; Its type and size are stored in the option itself.
+#[derive(Clone, Copy)]
+pub struct OptionValue<'a> {
+    inner: &'a sys::LV2_Options_Option,
+    lifetime: PhantomData<&'a [u8]>,
+}
+
+impl<'a> OptionValue<'a> {
+    /// Wrap a raw option.
+    ///
+    /// # Safety
+    ///
+    /// The value pointer of the option has to be null or point to at least `size` readable bytes that live as long as `'a`.
+    pub unsafe fn from_raw(inner: &'a sys::LV2_Options_Option) -> Self {
+        Self {
+            inner,
+            lifetime: PhantomData,
+        }
+    }
+
+    /// The subject of the option, or `None` if the subject is invalid.
+    pub fn subject(&self) -> Option<Subject> {
+        Subject::from_raw(self.inner.context, self.inner.subject)
+    }
+
+    /// The key of the option, or `None` if the key is invalid.
+    pub fn key(&self) -> Option<URID> {
+        URID::new(self.inner.key)
+    }
+
+    /// The type of the option's value, or `None` if the type is invalid.
+    pub fn value_type(&self) -> Option<URID> {
+        URID::new(self.inner.type_)
+    }
+
+    /// The raw bytes of the option's value, or `None` if there is no value.
+    pub fn data(&self) -> Option<&'a [u8]> {
+        if self.inner.value.is_null() {
+            None
+        } else {
+            // SAFETY: The validity of the pointer and the size is guaranteed by the constructor.
+            Some(unsafe {
+                std::slice::from_raw_parts(self.inner.value as *const u8, self.inner.size as usize)
+            })
+        }
+    }
+
+    /// Read the value of the option as a scalar of the given type.
+    ///
+    /// Hosts usually pass options as simple scalars, which are not necessarily aligned like atom bodies. Therefore, the value is copied instead of borrowed.
+    ///
+    /// For the common scalar types, the safe methods [`as_int`](#method.as_int), [`as_long`](#method.as_long), [`as_float`](#method.as_float), [`as_double`](#method.as_double), [`as_bool`](#method.as_bool) and [`as_urid`](#method.as_urid) should be preferred.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionsError::BadValue`](enum.OptionsError.html#variant.BadValue) if the value is missing, does not have the given type, or has the wrong size.
+    ///
+    /// # Safety
+    ///
+    /// The bytes of the value are provided by the host and are copied into the internal type of the atom without any further validation. Therefore, every bit pattern of the right size has to be a valid value of the internal type. This isn't the case for [`AtomURID`](../../lv2_atom/atoms/scalar/struct.AtomURID.html), since a URID may not be zero; Use [`as_urid`](#method.as_urid) for URIDs instead.
+    pub unsafe fn read_scalar<A: ScalarAtom>(
+        &self,
+        urid: URID<A>,
+    ) -> Result<A::InternalType, OptionsError> {
+        if self.inner.type_ != urid {
+            return Err(OptionsError::BadValue);
+        }
+        let data = self.data().ok_or(OptionsError::BadValue)?;
+        if data.len() != size_of::<A::InternalType>() {
+            return Err(OptionsError::BadValue);
+        }
+
+        // The type and the size of the value were checked above and the caller guarantees that the bytes are a valid value.
+        Ok(std::ptr::read_unaligned(
+            data.as_ptr() as *const A::InternalType
+        ))
+    }
+
+    /// Read the value of the option as an `Int`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionsError::BadValue`](enum.OptionsError.html#variant.BadValue) if the value isn't an `Int`.
+    pub fn as_int(&self, urid: URID<Int>) -> Result<i32, OptionsError> {
+        // SAFETY: Every bit pattern is a valid number.
+        unsafe { self.read_scalar(urid) }
+    }
+
+    /// Read the value of the option as a `Long`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionsError::BadValue`](enum.OptionsError.html#variant.BadValue) if the value isn't a `Long`.
+    pub fn as_long(&self, urid: URID<Long>) -> Result<i64, OptionsError> {
+        // SAFETY: Every bit pattern is a valid number.
+        unsafe { self.read_scalar(urid) }
+    }
+
+    /// Read the value of the option as a `Float`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionsError::BadValue`](enum.OptionsError.html#variant.BadValue) if the value isn't a `Float`.
+    pub fn as_float(&self, urid: URID<Float>) -> Result<f32, OptionsError> {
+        // SAFETY: Every bit pattern is a valid number.
+        unsafe { self.read_scalar(urid) }
+    }
+
+    /// Read the value of the option as a `Double`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionsError::BadValue`](enum.OptionsError.html#variant.BadValue) if the value isn't a `Double`.
+    pub fn as_double(&self, urid: URID<Double>) -> Result<f64, OptionsError> {
+        // SAFETY: Every bit pattern is a valid number.
+        unsafe { self.read_scalar(urid) }
+    }
+
+    /// Read the value of the option as a `Bool`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionsError::BadValue`](enum.OptionsError.html#variant.BadValue) if the value isn't a `Bool`.
+    pub fn as_bool(&self, urid: URID<Bool>) -> Result<bool, OptionsError> {
+        // SAFETY: Every bit pattern is a valid `i32`.
+        unsafe { self.read_scalar(urid) }.map(|value| value != 0)
+    }
+
+    /// Read the value of the option as a `URID`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionsError::BadValue`](enum.OptionsError.html#variant.BadValue) if the value isn't a `URID` atom or if the URID is 0.
+    pub fn as_urid(&self, urid: URID<AtomURID>) -> Result<URID, OptionsError> {
+        if self.inner.type_ != urid {
+            return Err(OptionsError::BadValue);
+        }
+        let data = self.data().ok_or(OptionsError::BadValue)?;
+        if data.len() != size_of::<u32>() {
+            return Err(OptionsError::BadValue);
+        }
+
+        // The raw value is read first, since zero isn't a valid URID.
+        let value = u32::from_ne_bytes([data[0], data[1], data[2], data[3]]);
+        URID::new(value).ok_or(OptionsError::BadValue)
+    }
+
+    /// Read the value of the option as an atom of the given type.
+    ///
+    /// The value is borrowed from the host and therefore has to be aligned like an atom body. Use [`as_float`](#method.as_float) and its siblings to read scalars regardless of their alignment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionsError::BadValue`](enum.OptionsError.html#variant.BadValue) if the value is missing, does not have the given type, is not aligned, or is malformed.
+    pub fn read<A: Atom>(
+        &self,
+        urid: URID<A>,
+    ) -> Result<<A::ReadHandle as AtomHandle<'a>>::Handle, OptionsError> {
+        if self.inner.type_ != urid {
+            return Err(OptionsError::BadValue);
+        }
+        let data = self.data().ok_or(OptionsError::BadValue)?;
+        let space = AtomSpace::from_bytes(data).map_err(|_| OptionsError::BadValue)?;
+
+        // SAFETY: The type of the value was checked above.
+        unsafe { A::read(space) }.map_err(|_| OptionsError::BadValue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use atom::prelude::*;
+    use std::ffi::c_void;
+    use std::mem::size_of;
+    use urid::*;
+
+    fn option<T>(type_: URID<impl ?Sized>, value: &T) -> sys::LV2_Options_Option {
+        sys::LV2_Options_Option {
+            context: sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+            subject: 0,
+            key: 1,
+            size: size_of::<T>() as u32,
+            type_: type_.get(),
+            value: value as *const T as *const c_void,
+        }
+    }
+
+    #[test]
+    fn test_typed_accessors() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+
+        let raw = option(urids.int, &42i32);
+        let value = unsafe { OptionValue::from_raw(&raw) };
+        assert_eq!(Ok(42), value.as_int(urids.int));
+        assert_eq!(Err(OptionsError::BadValue), value.as_float(urids.float));
+
+        let raw = option(urids.long, &-42i64);
+        let value = unsafe { OptionValue::from_raw(&raw) };
+        assert_eq!(Ok(-42), value.as_long(urids.long));
+        assert_eq!(Err(OptionsError::BadValue), value.as_int(urids.int));
+
+        let raw = option(urids.float, &0.5f32);
+        let value = unsafe { OptionValue::from_raw(&raw) };
+        assert_eq!(Ok(0.5), value.as_float(urids.float));
+        assert_eq!(Err(OptionsError::BadValue), value.as_double(urids.double));
+
+        let raw = option(urids.double, &1.5f64);
+        let value = unsafe { OptionValue::from_raw(&raw) };
+        assert_eq!(Ok(1.5), value.as_double(urids.double));
+
+        let raw = option(urids.bool, &1i32);
+        let value = unsafe { OptionValue::from_raw(&raw) };
+        assert_eq!(Ok(true), value.as_bool(urids.bool));
+
+        let raw = option(urids.urid, &urids.float.get());
+        let value = unsafe { OptionValue::from_raw(&raw) };
+        assert_eq!(Ok(urids.float.into_general()), value.as_urid(urids.urid));
+
+        // Zero isn't a valid URID.
+        let raw = option(urids.urid, &0u32);
+        let value = unsafe { OptionValue::from_raw(&raw) };
+        assert_eq!(Err(OptionsError::BadValue), value.as_urid(urids.urid));
+
+        // Values of the wrong size are rejected.
+        let raw = option(urids.int, &42i64);
+        let value = unsafe { OptionValue::from_raw(&raw) };
+        assert_eq!(Err(OptionsError::BadValue), value.as_int(urids.int));
+    }
+}
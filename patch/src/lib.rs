@@ -0,0 +1,470 @@
+//! LV2 specification to access and manipulate properties of plugins with messages.
+//!
+//! The original [specification](https://lv2plug.in/ns/ext/patch/patch.html) describes a protocol of atom objects to get and set properties. This implementation binds the URIs of the specification to marker types and supports the parameter protocol: A plugin announces its parameters with a [`Parameters`](struct.Parameters.html) description and the host changes them with `patch:Set` messages, which are read as [`SetMessage`](struct.SetMessage.html)s.
+//!
+//! # Example
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_atom::space::*;
+//! use lv2_atom::space::error::AtomWriteError;
+//! use lv2_patch::*;
+//! use urid::*;
+//!
+//! #[uri("urn:my-plugin:gain")]
+//! struct Gain;
+//!
+//! fn handle_message(
+//!     message: &UnidentifiedAtom,
+//!     gain: URID<Gain>,
+//!     atom_urids: &AtomURIDCollection,
+//!     urids: &PatchURIDCollection,
+//! ) -> Option<f32> {
+//!     let set = SetMessage::read(message, atom_urids, urids).ok()?;
+//!     if set.property == gain {
+//!         set.value.read(atom_urids.float).ok().copied()
+//!     } else {
+//!         None
+//!     }
+//! }
+//!
+//! fn announce_parameters(
+//!     output: &mut impl SpaceWriter,
+//!     gain: URID<Gain>,
+//!     atom_urids: &AtomURIDCollection,
+//!     urids: &PatchURIDCollection,
+//! ) -> Result<(), AtomWriteError> {
+//!     Parameters::new()
+//!         .writable(gain)
+//!         .write(output, None, atom_urids, urids)
+//! }
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_sys as sys;
+
+use atom::atoms::object::{ObjectHeader, ObjectReader};
+use atom::atoms::scalar::ScalarAtom;
+use atom::prelude::*;
+use atom::space::error::{AtomReadError, AtomWriteError};
+use atom::space::SpaceWriter;
+use urid::*;
+
+/// All patch URI bounds
+///
+/// All Struct suffixed by `Class` are patch classes, others are patch properties.
+pub mod patch {
+    use urid::UriBound;
+
+    /// A request for a description of the subject.
+    pub struct GetClass;
+    unsafe impl UriBound for GetClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Get;
+    }
+
+    /// A set of changes to the subject: The properties of `add` are added and the properties of `remove` are removed.
+    pub struct PatchClass;
+    unsafe impl UriBound for PatchClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Patch;
+    }
+
+    /// A request to set the `property` of the subject to `value`.
+    pub struct SetClass;
+    unsafe impl UriBound for SetClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Set;
+    }
+
+    /// The properties that are added to the subject by a patch.
+    pub struct Add;
+    unsafe impl UriBound for Add {
+        const URI: &'static [u8] = sys::LV2_PATCH__add;
+    }
+
+    /// The property that is set by a `Set` message.
+    pub struct Property;
+    unsafe impl UriBound for Property {
+        const URI: &'static [u8] = sys::LV2_PATCH__property;
+    }
+
+    /// A property that the subject has and that may only be read.
+    pub struct Readable;
+    unsafe impl UriBound for Readable {
+        const URI: &'static [u8] = sys::LV2_PATCH__readable;
+    }
+
+    /// The properties that are removed from the subject by a patch.
+    pub struct Remove;
+    unsafe impl UriBound for Remove {
+        const URI: &'static [u8] = sys::LV2_PATCH__remove;
+    }
+
+    /// The subject of a message, which is the plugin itself if it's omitted.
+    pub struct Subject;
+    unsafe impl UriBound for Subject {
+        const URI: &'static [u8] = sys::LV2_PATCH__subject;
+    }
+
+    /// The new value of the property that is set by a `Set` message.
+    pub struct Value;
+    unsafe impl UriBound for Value {
+        const URI: &'static [u8] = sys::LV2_PATCH__value;
+    }
+
+    /// A property that the subject has and that may be written.
+    pub struct Writable;
+    unsafe impl UriBound for Writable {
+        const URI: &'static [u8] = sys::LV2_PATCH__writable;
+    }
+
+    /// The class of LV2 plugins, used as the type of parameter descriptions.
+    pub struct PluginClass;
+    unsafe impl UriBound for PluginClass {
+        const URI: &'static [u8] = sys::LV2_CORE__Plugin;
+    }
+}
+
+use patch::*;
+
+/// A URID cache containing the patch URIDs used by this crate.
+#[derive(Clone, Copy)]
+pub struct PatchURIDCollection {
+    pub get: URID<GetClass>,
+    pub patch: URID<PatchClass>,
+    pub set: URID<SetClass>,
+    pub add: URID<Add>,
+    pub property: URID<Property>,
+    pub readable: URID<Readable>,
+    pub remove: URID<Remove>,
+    pub subject: URID<Subject>,
+    pub value: URID<Value>,
+    pub writable: URID<Writable>,
+    pub plugin: URID<PluginClass>,
+}
+
+impl URIDCollection for PatchURIDCollection {
+    fn from_map<M: Map + ?Sized>(map: &M) -> Option<Self> {
+        Some(Self {
+            get: map.map_type()?,
+            patch: map.map_type()?,
+            set: map.map_type()?,
+            add: map.map_type()?,
+            property: map.map_type()?,
+            readable: map.map_type()?,
+            remove: map.map_type()?,
+            subject: map.map_type()?,
+            value: map.map_type()?,
+            writable: map.map_type()?,
+            plugin: map.map_type()?,
+        })
+    }
+}
+
+/// Read an atom as an object, accepting both objects and blanks.
+fn read_object<'a>(
+    atom: &'a UnidentifiedAtom,
+    atom_urids: &AtomURIDCollection,
+) -> Result<(ObjectHeader, ObjectReader<'a>), AtomReadError> {
+    if atom.header().urid() == atom_urids.blank {
+        atom.read(atom_urids.blank)
+    } else {
+        atom.read(atom_urids.object)
+    }
+}
+
+/// Builder and reader of parameter descriptions.
+///
+/// A parameter description is a `patch:Patch` message that adds the `patch:writable` and `patch:readable` properties of the subject, which is the plugin itself if it's omitted. The properties are listed as URIDs.
+///
+/// Since the parameters are stored in vectors, building a description allocates.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Parameters {
+    writable: Vec<URID>,
+    readable: Vec<URID>,
+}
+
+impl Parameters {
+    /// Create a new, empty parameter description.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter that can be read and written.
+    pub fn writable<K: ?Sized>(mut self, key: URID<K>) -> Self {
+        self.writable.push(key.into_general());
+        self
+    }
+
+    /// Add a parameter that can only be read.
+    pub fn readable<K: ?Sized>(mut self, key: URID<K>) -> Self {
+        self.readable.push(key.into_general());
+        self
+    }
+
+    /// The writable parameters, in the order they were added.
+    pub fn writable_parameters(&self) -> &[URID] {
+        &self.writable
+    }
+
+    /// The read-only parameters, in the order they were added.
+    pub fn readable_parameters(&self) -> &[URID] {
+        &self.readable
+    }
+
+    /// Write the description as a `patch:Patch` object.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if there is not enough space in the underlying buffer,
+    /// or if any other write error occurs.
+    pub fn write(
+        &self,
+        space: &mut impl SpaceWriter,
+        subject: Option<URID>,
+        atom_urids: &AtomURIDCollection,
+        urids: &PatchURIDCollection,
+    ) -> Result<(), AtomWriteError> {
+        let mut patch = space
+            .write_atom(atom_urids.object)?
+            .write_header(ObjectHeader {
+                id: None,
+                otype: urids.patch.into_general(),
+            })?;
+        if let Some(subject) = subject {
+            patch
+                .new_property(urids.subject, atom_urids.urid)?
+                .set(subject)?;
+        }
+
+        let mut add = patch
+            .new_property(urids.add, atom_urids.object)?
+            .write_header(ObjectHeader {
+                id: None,
+                otype: urids.plugin.into_general(),
+            })?;
+        for key in self.writable.iter() {
+            add.new_property(urids.writable, atom_urids.urid)?
+                .set(*key)?;
+        }
+        for key in self.readable.iter() {
+            add.new_property(urids.readable, atom_urids.urid)?
+                .set(*key)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read a description from a `patch:Patch` object.
+    ///
+    /// Properties that aren't part of a parameter description are ignored. The subject of the description is returned too.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the atom isn't a `patch:Patch` object or if it's malformed.
+    pub fn read(
+        atom: &UnidentifiedAtom,
+        atom_urids: &AtomURIDCollection,
+        urids: &PatchURIDCollection,
+    ) -> Result<(Option<URID>, Self), AtomReadError> {
+        let (header, reader) = read_object(atom, atom_urids)?;
+        if header.otype != urids.patch {
+            return Err(AtomReadError::InvalidAtomValue {
+                reading_type_uri: PatchClass::uri(),
+                error_message: "The object isn't a patch",
+            });
+        }
+
+        let mut subject = None;
+        let mut parameters = Self::new();
+        for (property, value) in reader.clone() {
+            if property.key == urids.subject {
+                subject = Some(*value.read(atom_urids.urid)?);
+            } else if property.key == urids.add {
+                let (_, add) = reader.read_object(value, atom_urids)?;
+                for (property, value) in add {
+                    if property.key == urids.writable {
+                        parameters.writable.push(*value.read(atom_urids.urid)?);
+                    } else if property.key == urids.readable {
+                        parameters.readable.push(*value.read(atom_urids.urid)?);
+                    }
+                }
+            }
+        }
+
+        Ok((subject, parameters))
+    }
+}
+
+/// A `patch:Set` message, which sets a property of the subject to a new value.
+pub struct SetMessage<'a> {
+    /// The subject of the message, which is the plugin itself if it's `None`.
+    pub subject: Option<URID>,
+    /// The property to set.
+    pub property: URID,
+    /// The new value of the property.
+    pub value: &'a UnidentifiedAtom,
+}
+
+impl<'a> SetMessage<'a> {
+    /// Read a `patch:Set` message.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the atom isn't a `patch:Set` object, if the property or the value are missing, or if the message is malformed.
+    pub fn read(
+        atom: &'a UnidentifiedAtom,
+        atom_urids: &AtomURIDCollection,
+        urids: &PatchURIDCollection,
+    ) -> Result<Self, AtomReadError> {
+        let invalid = |error_message| AtomReadError::InvalidAtomValue {
+            reading_type_uri: SetClass::uri(),
+            error_message,
+        };
+
+        let (header, reader) = read_object(atom, atom_urids)?;
+        if header.otype != urids.set {
+            return Err(invalid("The object isn't a set message"));
+        }
+
+        let mut subject = None;
+        let mut property = None;
+        let mut value = None;
+        for (header, atom) in reader {
+            if header.key == urids.subject {
+                subject = Some(*atom.read(atom_urids.urid)?);
+            } else if header.key == urids.property {
+                property = Some(*atom.read(atom_urids.urid)?);
+            } else if header.key == urids.value {
+                value = Some(atom);
+            }
+        }
+
+        Ok(Self {
+            subject,
+            property: property.ok_or_else(|| invalid("The set message has no property"))?,
+            value: value.ok_or_else(|| invalid("The set message has no value"))?,
+        })
+    }
+
+    /// Write a `patch:Set` message with a scalar value.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if there is not enough space in the underlying buffer,
+    /// or if any other write error occurs.
+    pub fn write<K: ?Sized, A: ScalarAtom>(
+        space: &mut impl SpaceWriter,
+        subject: Option<URID>,
+        property: URID<K>,
+        value_type: URID<A>,
+        value: A::InternalType,
+        atom_urids: &AtomURIDCollection,
+        urids: &PatchURIDCollection,
+    ) -> Result<(), AtomWriteError> {
+        let mut set = space
+            .write_atom(atom_urids.object)?
+            .write_header(ObjectHeader {
+                id: None,
+                otype: urids.set.into_general(),
+            })?;
+        if let Some(subject) = subject {
+            set.new_property(urids.subject, atom_urids.urid)?
+                .set(subject)?;
+        }
+        set.new_property(urids.property, atom_urids.urid)?
+            .set(property.into_general())?;
+        set.new_property(urids.value, value_type)?.set(value)?;
+        Ok(())
+    }
+}
+
+/// Prelude of `lv2_patch` for wildcard usage.
+pub mod prelude {
+    pub use crate::{Parameters, PatchURIDCollection, SetMessage};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use atom::space::{AlignedVec, SpaceCursor};
+    use atom::AtomHeader;
+
+    struct Gain;
+    unsafe impl UriBound for Gain {
+        const URI: &'static [u8] = b"urn:my-plugin:gain\0";
+    }
+
+    struct Level;
+    unsafe impl UriBound for Level {
+        const URI: &'static [u8] = b"urn:my-plugin:level\0";
+    }
+
+    #[test]
+    fn test_parameters() {
+        let map = HashURIDMapper::new();
+        let atom_urids = AtomURIDCollection::from_map(&map).unwrap();
+        let urids = PatchURIDCollection::from_map(&map).unwrap();
+        let gain = map.map_type::<Gain>().unwrap();
+        let level = map.map_type::<Level>().unwrap();
+        let plugin = map.map_str("urn:my-plugin").unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(32);
+        let raw_space = raw_space.as_space_mut();
+
+        let parameters = Parameters::new().writable(gain).readable(level);
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            parameters
+                .write(&mut space, Some(plugin), &atom_urids, &urids)
+                .unwrap();
+        }
+
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        let (subject, read_parameters) = Parameters::read(atom, &atom_urids, &urids).unwrap();
+        assert_eq!(subject, Some(plugin));
+        assert_eq!(read_parameters, parameters);
+        assert_eq!(
+            read_parameters.writable_parameters(),
+            &[gain.into_general()]
+        );
+        assert_eq!(
+            read_parameters.readable_parameters(),
+            &[level.into_general()]
+        );
+
+        // A description is not a set message.
+        assert!(SetMessage::read(atom, &atom_urids, &urids).is_err());
+    }
+
+    #[test]
+    fn test_set_message() {
+        let map = HashURIDMapper::new();
+        let atom_urids = AtomURIDCollection::from_map(&map).unwrap();
+        let urids = PatchURIDCollection::from_map(&map).unwrap();
+        let gain = map.map_type::<Gain>().unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(16);
+        let raw_space = raw_space.as_space_mut();
+
+        {
+            let mut space = SpaceCursor::new(raw_space.as_bytes_mut());
+            SetMessage::write(
+                &mut space,
+                None,
+                gain,
+                atom_urids.float,
+                0.5,
+                &atom_urids,
+                &urids,
+            )
+            .unwrap();
+        }
+
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        let message = SetMessage::read(atom, &atom_urids, &urids).unwrap();
+        assert_eq!(message.subject, None);
+        assert_eq!(message.property, gain);
+        assert_eq!(*message.value.read(atom_urids.float).unwrap(), 0.5);
+
+        // A set message is not a parameter description.
+        assert!(Parameters::read(atom, &atom_urids, &urids).is_err());
+    }
+}
@@ -97,9 +97,13 @@
 //!
 //! * `lv2-atom`: General data IO.
 //! * `lv2-core`: Implementation of the core LV2 specification.
+//! * `lv2-log`: Printing log messages through the host, optionally deferred from the audio thread.
 //! * `lv2-midi`: MIDI message extension for `lv2-midi`. Support for the [`wmidi` crate](https://crates.io/crates/wmidi) can be enabled with the `wmidi` feature.
+//! * `lv2-options`: Extension for LV2 plugins to receive options from the host.
+//! * `lv2-patch`: Messages to describe and change the parameters of a plugin.
 //! * `lv2-state`: Extension for LV2 plugins to store their state.
 //! * `lv2-time`: Specification to describe position in time and passage of time, in both real and musical terms.
+//! * `lv2-ui`: Host features for the user interfaces of plugins.
 //! * `lv2-units`: Measuring unit definitions.
 //! * `lv2-urid`: LV2 integration of the URID concept.
 //! * `lv2-worker`: Work scheduling library that allows real-time capable LV2 plugins to execute non-real-time actions.
@@ -122,12 +126,20 @@ pub mod prelude {
     pub use ::lv2_atom::prelude::*;
     #[cfg(feature = "lv2-core")]
     pub use ::lv2_core::prelude::*;
+    #[cfg(feature = "lv2-log")]
+    pub use ::lv2_log::prelude::*;
     #[cfg(feature = "lv2-midi")]
     pub use ::lv2_midi::prelude::*;
+    #[cfg(feature = "lv2-options")]
+    pub use ::lv2_options::prelude::*;
+    #[cfg(feature = "lv2-patch")]
+    pub use ::lv2_patch::prelude::*;
     #[cfg(feature = "lv2-state")]
     pub use ::lv2_state::*;
     #[cfg(feature = "lv2-time")]
     pub use ::lv2_time::prelude::*;
+    #[cfg(feature = "lv2-ui")]
+    pub use ::lv2_ui::prelude::*;
     #[cfg(feature = "lv2-units")]
     pub use ::lv2_units::prelude::*;
     #[cfg(feature = "lv2-urid")]
@@ -144,9 +156,18 @@ pub extern crate lv2_atom;
 #[cfg(feature = "lv2-core")]
 pub extern crate lv2_core;
 
+#[cfg(feature = "lv2-log")]
+pub extern crate lv2_log;
+
 #[cfg(feature = "lv2-midi")]
 pub extern crate lv2_midi;
 
+#[cfg(feature = "lv2-options")]
+pub extern crate lv2_options;
+
+#[cfg(feature = "lv2-patch")]
+pub extern crate lv2_patch;
+
 #[cfg(feature = "lv2-state")]
 pub extern crate lv2_state;
 
@@ -159,6 +180,9 @@ pub extern crate lv2_time;
 #[cfg(feature = "urid")]
 pub extern crate urid;
 
+#[cfg(feature = "lv2-ui")]
+pub extern crate lv2_ui;
+
 #[cfg(feature = "lv2-units")]
 pub extern crate lv2_units;
 
@@ -0,0 +1,117 @@
+use crate::path::{FreePath, MakePath, MapPath};
+use core::feature::*;
+use std::ffi::c_void;
+use urid::*;
+
+/// Marker feature to signal that the plugin's state may be restored from any thread.
+///
+/// A plugin that lists `state:threadSafeRestore` as a feature in its description promises that [`restore`](trait.State.html#tymethod.restore) may be called concurrently with `run`, e.g. from a background thread. A host that supports this passes the feature to the plugin.
+pub struct ThreadSafeRestore;
+
+unsafe impl UriBound for ThreadSafeRestore {
+    const URI: &'static [u8] = sys::LV2_STATE__threadSafeRestore;
+}
+
+unsafe impl Feature for ThreadSafeRestore {
+    unsafe fn from_feature_ptr(_feature: *const c_void, _: ThreadingClass) -> Option<Self> {
+        Some(Self)
+    }
+}
+
+/// Marker feature to signal that the host loads the default state of the plugin after it's been instantiated.
+///
+/// The default state is described by the `state:state` property of the plugin in its description.
+pub struct LoadDefaultState;
+
+unsafe impl UriBound for LoadDefaultState {
+    const URI: &'static [u8] = sys::LV2_STATE__loadDefaultState;
+}
+
+unsafe impl Feature for LoadDefaultState {
+    unsafe fn from_feature_ptr(_feature: *const c_void, _: ThreadingClass) -> Option<Self> {
+        Some(Self)
+    }
+}
+
+/// Report of the state-related features provided by the host.
+///
+/// This is a feature collection that never fails to populate and doesn't remove any features from the cache. It can be used to check which features the host supports before the actual features are retrieved, or as the [`StateFeatures`](trait.State.html#associatedtype.StateFeatures) of a plugin that only needs to know about them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StateFeatures {
+    /// The host may restore the state from any thread.
+    pub thread_safe_restore: bool,
+    /// The host loads the default state after instantiation.
+    pub load_default_state: bool,
+    /// The host provides the [`MakePath`](path/struct.MakePath.html) feature.
+    pub make_path: bool,
+    /// The host provides the [`MapPath`](path/struct.MapPath.html) feature.
+    pub map_path: bool,
+    /// The host provides the [`FreePath`](path/struct.FreePath.html) feature.
+    pub free_path: bool,
+}
+
+impl StateFeatures {
+    /// Check which state-related features are contained in the cache.
+    pub fn from_features(cache: &FeatureCache) -> Self {
+        Self {
+            thread_safe_restore: cache.contains::<ThreadSafeRestore>(),
+            load_default_state: cache.contains::<LoadDefaultState>(),
+            make_path: cache.contains::<MakePath>(),
+            map_path: cache.contains::<MapPath>(),
+            free_path: cache.contains::<FreePath>(),
+        }
+    }
+}
+
+impl<'a> FeatureCollection<'a> for StateFeatures {
+    fn from_cache(
+        cache: &mut FeatureCache<'a>,
+        _: ThreadingClass,
+    ) -> Result<Self, MissingFeatureError> {
+        Ok(Self::from_features(cache))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use core::feature::*;
+    use std::os::raw::c_char;
+    use urid::UriBound;
+
+    #[test]
+    fn test_state_features() {
+        let thread_safe_restore = sys::LV2_Feature {
+            URI: ThreadSafeRestore::URI.as_ptr() as *const c_char,
+            data: std::ptr::null_mut(),
+        };
+        let features_list: &[*const sys::LV2_Feature] = &[&thread_safe_restore, std::ptr::null()];
+        let mut cache = unsafe { FeatureCache::from_raw(features_list.as_ptr()) };
+
+        let features = StateFeatures::from_cache(&mut cache, ThreadingClass::Other).unwrap();
+        assert_eq!(
+            features,
+            StateFeatures {
+                thread_safe_restore: true,
+                ..Default::default()
+            }
+        );
+
+        // The features are still available.
+        assert!(cache
+            .retrieve_feature::<ThreadSafeRestore, Option<ThreadSafeRestore>>(ThreadingClass::Other)
+            .unwrap()
+            .is_some());
+        assert!(cache
+            .retrieve_feature::<LoadDefaultState, Option<LoadDefaultState>>(ThreadingClass::Other)
+            .unwrap()
+            .is_none());
+
+        let empty: &[*const sys::LV2_Feature] = &[std::ptr::null()];
+        let cache = unsafe { FeatureCache::from_raw(empty.as_ptr()) };
+        assert_eq!(
+            StateFeatures::from_features(&cache),
+            StateFeatures::default()
+        );
+    }
+}
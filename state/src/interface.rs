@@ -58,13 +58,13 @@ impl<P: State> StateDescriptor<P> {
         let flags: u32 =
             (sys::LV2_State_Flags::from(flags) & sys::LV2_State_Flags::LV2_STATE_IS_POD).into();
         if flags == 0 {
-            return sys::LV2_State_Status_LV2_STATE_ERR_BAD_FLAGS;
+            return StateErr::into(Err(StateErr::BadFlags));
         }
 
         let plugin: &P = if let Some(plugin) = (instance as *const P).as_ref() {
             plugin
         } else {
-            return sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN;
+            return StateErr::into(Err(StateErr::Unknown));
         };
 
         let store = StoreHandle::new(store, handle);
@@ -75,7 +75,7 @@ impl<P: State> StateDescriptor<P> {
         {
             features
         } else {
-            return sys::LV2_State_Status_LV2_STATE_ERR_NO_FEATURE;
+            return StateErr::into(Err(StateErr::NoFeature));
         };
 
         StateErr::into(plugin.save(store, features))
@@ -98,13 +98,13 @@ impl<P: State> StateDescriptor<P> {
         let flags: u32 =
             (sys::LV2_State_Flags::from(flags) & sys::LV2_State_Flags::LV2_STATE_IS_POD).into();
         if flags == 0 {
-            return sys::LV2_State_Status_LV2_STATE_ERR_BAD_FLAGS;
+            return StateErr::into(Err(StateErr::BadFlags));
         }
 
         let plugin: &mut P = if let Some(plugin) = (instance as *mut P).as_mut() {
             plugin
         } else {
-            return sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN;
+            return StateErr::into(Err(StateErr::Unknown));
         };
 
         let store = RetrieveHandle::new(retrieve, handle);
@@ -115,7 +115,7 @@ impl<P: State> StateDescriptor<P> {
         {
             features
         } else {
-            return sys::LV2_State_Status_LV2_STATE_ERR_NO_FEATURE;
+            return StateErr::into(Err(StateErr::NoFeature));
         };
 
         StateErr::into(plugin.restore(store, features))
@@ -239,4 +239,107 @@ mod tests {
             )
         });
     }
+
+    #[uri("urn:failing")]
+    struct Failing {
+        result: Result<(), StateErr>,
+    }
+
+    impl Plugin for Failing {
+        type InitFeatures = ();
+        type AudioFeatures = ();
+        type Ports = ();
+
+        fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+            None
+        }
+
+        fn run(&mut self, _: &mut (), _: &mut (), _: u32) {}
+    }
+
+    impl State for Failing {
+        type StateFeatures = ();
+
+        fn save(&self, _: StoreHandle, _: ()) -> Result<(), StateErr> {
+            self.result
+        }
+
+        fn restore(&mut self, _: RetrieveHandle, _: ()) -> Result<(), StateErr> {
+            self.result
+        }
+    }
+
+    #[test]
+    fn test_error_propagation() {
+        type Descriptor = StateDescriptor<Failing>;
+
+        let cases = [
+            (Ok(()), sys::LV2_State_Status_LV2_STATE_SUCCESS),
+            (
+                Err(StateErr::Unknown),
+                sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN,
+            ),
+            (
+                Err(StateErr::BadCallback),
+                sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN,
+            ),
+            (
+                Err(StateErr::BadData),
+                sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN,
+            ),
+            (
+                Err(StateErr::BadType),
+                sys::LV2_State_Status_LV2_STATE_ERR_BAD_TYPE,
+            ),
+            (
+                Err(StateErr::BadFlags),
+                sys::LV2_State_Status_LV2_STATE_ERR_BAD_FLAGS,
+            ),
+            (
+                Err(StateErr::NoFeature),
+                sys::LV2_State_Status_LV2_STATE_ERR_NO_FEATURE,
+            ),
+            (
+                Err(StateErr::NoProperty),
+                sys::LV2_State_Status_LV2_STATE_ERR_NO_PROPERTY,
+            ),
+            (
+                Err(StateErr::NoSpace),
+                sys::LV2_State_Status_LV2_STATE_ERR_NO_SPACE,
+            ),
+            (
+                Err(StateErr::PathNotUTF8),
+                sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN,
+            ),
+            (
+                Err(StateErr::HostError),
+                sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN,
+            ),
+        ];
+
+        for (result, status) in cases.iter() {
+            let mut plugin = Failing { result: *result };
+            let instance = &mut plugin as *mut Failing as sys::LV2_Handle;
+
+            assert_eq!(*status, unsafe {
+                Descriptor::extern_save(
+                    instance,
+                    None,
+                    std::ptr::null_mut(),
+                    sys::LV2_State_Flags::LV2_STATE_IS_POD.into(),
+                    std::ptr::null_mut(),
+                )
+            });
+
+            assert_eq!(*status, unsafe {
+                Descriptor::extern_restore(
+                    instance,
+                    None,
+                    std::ptr::null_mut(),
+                    sys::LV2_State_Flags::LV2_STATE_IS_POD.into(),
+                    std::ptr::null_mut(),
+                )
+            });
+        }
+    }
 }
@@ -83,6 +83,9 @@ extern crate lv2_atom as atom;
 extern crate lv2_core as core;
 extern crate lv2_sys as sys;
 
+mod features;
+pub use features::*;
+
 mod interface;
 pub use interface::*;
 
@@ -1,5 +1,7 @@
 use crate::StateErr;
+use atom::atoms::sequence::{SequenceIterator, SequenceUnit, SequenceWriter};
 use atom::prelude::*;
+use atom::space::error::AtomWriteError;
 use atom::space::*;
 use atom::AtomHandle;
 use atom::AtomHeader;
@@ -46,6 +48,35 @@ impl<'a> StoreHandle<'a> {
         StatePropertyWriter::new(space.write())
     }
 
+    /// Draft a new property containing a sequence of events.
+    ///
+    /// This initializes the property as a sequence with the given time stamp unit and calls `write_events` with the sequence writer to add the events. Like any other drafted property, the sequence has to be committed to be stored.
+    ///
+    /// If `write_events` or the initialization of the sequence fails, the property is discarded and `Err(StateErr::Unknown)` is returned.
+    pub fn draft_sequence<K: ?Sized, U: SequenceUnit, F>(
+        &mut self,
+        property_key: URID<K>,
+        sequence_urid: URID<Sequence>,
+        unit_urid: URID<U>,
+        write_events: F,
+    ) -> Result<(), StateErr>
+    where
+        F: FnOnce(&mut SequenceWriter<U>) -> Result<(), AtomWriteError>,
+    {
+        let result = {
+            let mut writer = self.draft(property_key);
+            writer
+                .init(sequence_urid)
+                .and_then(|header| header.with_unit(unit_urid).map_err(|_| StateErr::Unknown))
+                .and_then(|mut sequence| write_events(&mut sequence).map_err(|_| StateErr::Unknown))
+        };
+
+        if result.is_err() {
+            self.discard(property_key);
+        }
+        result
+    }
+
     /// Internal helper function to store a property.
     fn commit_pair<K: ?Sized>(
         store_fn: sys::LV2_State_Store_Function,
@@ -166,12 +197,12 @@ impl<'a> RetrieveHandle<'a> {
             )
         };
 
-        let type_ = URID::new(type_).ok_or(StateErr::Unknown)?;
         let space = if !property_ptr.is_null() {
             unsafe { std::slice::from_raw_parts(property_ptr as *const u8, size) }
         } else {
             return Err(StateErr::NoProperty);
         };
+        let type_ = URID::new(type_).ok_or(StateErr::Unknown)?;
 
         Ok(StatePropertyReader::new(
             type_,
@@ -180,6 +211,25 @@ impl<'a> RetrieveHandle<'a> {
     }
 }
 
+impl<'a> RetrieveHandle<'a> {
+    /// Try to retrieve a property containing a sequence of events.
+    ///
+    /// This retrieves the property and reads it as a sequence whose time stamps are measured in the given unit. A sequence without a unit is treated as being measured in frames.
+    ///
+    /// If there's no property with the given URID, `Err(StateErr::NoProperty)` is returned. If the property isn't a sequence or its time stamps are measured in another unit, `Err(StateErr::BadType)` is returned.
+    pub fn retrieve_sequence<K: ?Sized, U: SequenceUnit>(
+        &self,
+        key: URID<K>,
+        sequence_urid: URID<Sequence>,
+        unit_urid: URID<U>,
+    ) -> Result<SequenceIterator<U>, StateErr> {
+        self.retrieve(key)?
+            .read(sequence_urid)?
+            .with_unit(unit_urid)
+            .map_err(|_| StateErr::BadType)
+    }
+}
+
 /// Reading handle for properties.
 ///
 /// This handle contains the type and the data of a property retrieved from the [`RetrieveHandle`](struct.RetrieveHandle.html).
@@ -336,4 +386,79 @@ mod tests {
 
         retrieve(&mut storage, &urids);
     }
+
+    #[test]
+    fn test_sequence_property() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+        let frame = map.map_type::<lv2_units::units::Frame>().unwrap();
+        let beat = map.map_type::<lv2_units::units::Beat>().unwrap();
+        let sequence_key = URID::new(1).unwrap();
+        let int_key = URID::new(2).unwrap();
+
+        let mut storage = Storage::default();
+
+        {
+            let mut store_handle = storage.store_handle();
+            store_handle
+                .draft_sequence(sequence_key, urids.sequence, beat, |sequence| {
+                    sequence.new_event(0.5, urids.int)?.set(1)?;
+                    sequence.new_event(1.5, urids.int)?.set(2)?;
+                    Ok(())
+                })
+                .unwrap();
+            store_handle
+                .draft(int_key)
+                .init(urids.int)
+                .unwrap()
+                .set(3)
+                .unwrap();
+
+            // Failed sequences are discarded.
+            assert_eq!(
+                Err(StateErr::Unknown),
+                store_handle.draft_sequence(
+                    URID::new(3).unwrap(),
+                    urids.sequence,
+                    beat,
+                    |sequence| {
+                        sequence.new_event(1.0, urids.int)?.set(1)?;
+                        sequence.new_event(0.0, urids.int)?.set(2)?;
+                        Ok(())
+                    }
+                )
+            );
+
+            store_handle.commit_all().unwrap();
+        }
+
+        let retrieve_handle = storage.retrieve_handle();
+        let events: Vec<(f64, i32)> = retrieve_handle
+            .retrieve_sequence(sequence_key, urids.sequence, beat)
+            .unwrap()
+            .map(|(stamp, atom)| (stamp, *atom.read(urids.int).unwrap()))
+            .collect();
+        assert_eq!(events, [(0.5, 1), (1.5, 2)]);
+
+        // The sequence is measured in beats, not frames.
+        assert_eq!(
+            Err(StateErr::BadType),
+            retrieve_handle
+                .retrieve_sequence(sequence_key, urids.sequence, frame)
+                .map(|_| ())
+        );
+        // The property isn't a sequence at all.
+        assert_eq!(
+            Err(StateErr::BadType),
+            retrieve_handle
+                .retrieve_sequence(int_key, urids.sequence, frame)
+                .map(|_| ())
+        );
+        assert_eq!(
+            Err(StateErr::NoProperty),
+            retrieve_handle
+                .retrieve_sequence(URID::new(3).unwrap(), urids.sequence, beat)
+                .map(|_| ())
+        );
+    }
 }
@@ -67,6 +67,18 @@ impl Storage {
             .map(|(urid, data)| (*urid, data.as_ref()))
     }
 
+    /// Return the keys of all stored properties, in arbitrary order.
+    ///
+    /// This can be used to find properties that aren't known to the plugin, e.g. because they were saved by a newer version.
+    pub fn keys(&self) -> impl Iterator<Item = URID> + '_ {
+        self.items.keys().copied()
+    }
+
+    /// Check whether a property with the given key is stored.
+    pub fn contains<K: ?Sized>(&self, key: URID<K>) -> bool {
+        self.items.contains_key(&key.into_general())
+    }
+
     /// External version of [`retrieve`](#method.retrieve).
     ///
     /// This function has the appropriate signature to be used as a storage callback.
@@ -122,3 +134,27 @@ impl DerefMut for Storage {
         &mut self.items
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::Storage;
+    use urid::URID;
+
+    #[test]
+    fn test_keys() {
+        let type_ = URID::new(10).unwrap();
+        let mut storage = Storage::default();
+        assert_eq!(storage.keys().count(), 0);
+
+        storage.store(URID::new(1).unwrap(), type_, &[1]);
+        storage.store(URID::new(3).unwrap(), type_, &[3]);
+
+        let mut keys: Vec<u32> = storage.keys().map(URID::get).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, [1, 3]);
+
+        assert!(storage.contains(URID::new(1).unwrap()));
+        assert!(!storage.contains(URID::new(2).unwrap()));
+        assert!(storage.contains(URID::new(3).unwrap()));
+    }
+}
@@ -1,3 +1,4 @@
+// Every supported architecture has its own binding, since the layout of some types differs between them. Most notably, `__int64_t` and `va_list` aren't the same on 32-bit ARM and on AArch64, which is why `aarch64` can't reuse the `arm` binding. Architectures without a binding fall through to `unsupported.rs`, which fails the build with a descriptive error.
 #[cfg_attr(target_arch = "x86", path = "x86.rs")]
 #[cfg_attr(target_arch = "x86_64", path = "x86_64.rs")]
 #[cfg_attr(target_arch = "arm", path = "arm.rs")]
@@ -0,0 +1,167 @@
+//! Structural comparison of generated bindings.
+use quote::ToTokens;
+use std::collections::BTreeMap;
+use std::fmt;
+use syn::{ForeignItem, Item};
+
+/// The result of comparing an item of two bindings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CmpResult {
+    /// The item only exists in the left bindings.
+    OnlyLeft(String),
+    /// The item only exists in the right bindings.
+    OnlyRight(String),
+    /// The item exists in both bindings, but its definition differs.
+    Differs(String),
+}
+
+impl fmt::Display for CmpResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CmpResult::OnlyLeft(item) => write!(f, "< {}", item),
+            CmpResult::OnlyRight(item) => write!(f, "> {}", item),
+            CmpResult::Differs(item) => write!(f, "! {}", item),
+        }
+    }
+}
+
+/// Collect the items of a bindings file, keyed by their kind and name.
+///
+/// The values are the token streams of the items without their attributes, since the documentation doesn't change the structure of an item.
+fn collect_items(source: &str) -> syn::Result<BTreeMap<String, String>> {
+    let file = syn::parse_file(source)?;
+    let mut items = BTreeMap::new();
+
+    for item in file.items {
+        let (key, tokens) = match item {
+            Item::Const(mut item) => {
+                item.attrs.clear();
+                (format!("const {}", item.ident), item.to_token_stream())
+            }
+            Item::Enum(mut item) => {
+                item.attrs.clear();
+                (format!("enum {}", item.ident), item.to_token_stream())
+            }
+            Item::Static(mut item) => {
+                item.attrs.clear();
+                (format!("static {}", item.ident), item.to_token_stream())
+            }
+            Item::Struct(mut item) => {
+                item.attrs.clear();
+                (format!("struct {}", item.ident), item.to_token_stream())
+            }
+            Item::Type(mut item) => {
+                item.attrs.clear();
+                (format!("type {}", item.ident), item.to_token_stream())
+            }
+            Item::Union(mut item) => {
+                item.attrs.clear();
+                (format!("union {}", item.ident), item.to_token_stream())
+            }
+            Item::Fn(mut item) => {
+                item.attrs.clear();
+                (format!("fn {}", item.sig.ident), item.to_token_stream())
+            }
+            Item::ForeignMod(item) => {
+                for foreign_item in item.items {
+                    let (key, tokens) = match foreign_item {
+                        ForeignItem::Fn(mut item) => {
+                            item.attrs.clear();
+                            (format!("fn {}", item.sig.ident), item.to_token_stream())
+                        }
+                        ForeignItem::Static(mut item) => {
+                            item.attrs.clear();
+                            (format!("static {}", item.ident), item.to_token_stream())
+                        }
+                        ForeignItem::Type(mut item) => {
+                            item.attrs.clear();
+                            (format!("type {}", item.ident), item.to_token_stream())
+                        }
+                        _ => continue,
+                    };
+                    items.insert(key, tokens.to_string());
+                }
+                continue;
+            }
+            // Implementations are derived from the types and are therefore skipped.
+            _ => continue,
+        };
+        items.insert(key, tokens.to_string());
+    }
+
+    Ok(items)
+}
+
+/// Compare the items of two bindings files.
+///
+/// The results are sorted by the kind and name of the items. An empty result means that both bindings are structurally equivalent.
+///
+/// # Errors
+///
+/// Returns an error if one of the sources can't be parsed.
+pub fn compare(left: &str, right: &str) -> syn::Result<Vec<CmpResult>> {
+    let left = collect_items(left)?;
+    let right = collect_items(right)?;
+    let mut results = Vec::new();
+
+    for (key, left_tokens) in left.iter() {
+        match right.get(key) {
+            None => results.push(CmpResult::OnlyLeft(key.clone())),
+            Some(right_tokens) if right_tokens != left_tokens => {
+                results.push(CmpResult::Differs(key.clone()))
+            }
+            Some(_) => (),
+        }
+    }
+    for key in right.keys() {
+        if !left.contains_key(key) {
+            results.push(CmpResult::OnlyRight(key.clone()));
+        }
+    }
+
+    results.sort_by(|a, b| cmp_key(a).cmp(cmp_key(b)));
+    Ok(results)
+}
+
+fn cmp_key(result: &CmpResult) -> &str {
+    match result {
+        CmpResult::OnlyLeft(key) | CmpResult::OnlyRight(key) | CmpResult::Differs(key) => key,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare() {
+        let left = r#"
+            pub const LV2_A: u32 = 1;
+            #[doc = "Left documentation"]
+            pub type __int64_t = ::std::os::raw::c_longlong;
+            #[repr(C)]
+            pub struct LV2_Struct { pub a: u32 }
+            extern "C" { pub fn lv2_left(); }
+        "#;
+        let right = r#"
+            pub const LV2_A: u32 = 1;
+            #[doc = "Right documentation"]
+            pub type __int64_t = ::std::os::raw::c_long;
+            #[repr(C)]
+            pub struct LV2_Struct { pub a: u32 }
+            impl LV2_Struct {}
+            extern "C" { pub fn lv2_right(); }
+        "#;
+
+        assert_eq!(
+            vec![
+                CmpResult::OnlyLeft("fn lv2_left".to_owned()),
+                CmpResult::OnlyRight("fn lv2_right".to_owned()),
+                CmpResult::Differs("type __int64_t".to_owned()),
+            ],
+            compare(left, right).unwrap()
+        );
+        assert!(compare(left, left).unwrap().is_empty());
+        assert!(compare("pub struct", left).is_err());
+    }
+}
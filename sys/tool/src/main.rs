@@ -1,7 +1,68 @@
 extern crate bindgen;
+mod compare;
+
+use bindgen::callbacks::ParseCallbacks;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Parse callbacks that give anonymous types valid, deterministic names.
+///
+/// Newer versions of clang spell anonymous types like `union (unnamed at lv2/atom/atom.h:214:2)`, which bindgen tries to turn into an identifier and panics. This replaces every such part with `_bindgen_ty_<n>`, where `n` counts the anonymous types of the parent, starting at 1. These are the names older versions of clang lead to, which keeps the generated bindings stable; For example, the timestamp union of `LV2_Atom_Event` is still called `LV2_Atom_Event__bindgen_ty_1`.
+#[derive(Debug, Default)]
+struct AnonymousTypeNames {
+    /// The indices of the anonymous types, by parent and by clang's spelling.
+    indices: Mutex<HashMap<String, HashMap<String, usize>>>,
+}
+
+impl AnonymousTypeNames {
+    fn sanitize(&self, name: &str) -> Option<String> {
+        if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return None;
+        }
+
+        let mut indices = self.indices.lock().unwrap();
+        let mut sanitized = String::new();
+        let mut rest = name;
+        while let Some(start) = rest.find('(') {
+            let end = rest[start..]
+                .find(')')
+                .map(|end| start + end + 1)
+                .unwrap_or_else(|| rest.len());
+
+            // Remove the kind of the type, e.g. `union `, which precedes the parenthesis.
+            let head = rest[..start].trim_end();
+            let head = match head.rfind('_') {
+                Some(separator) => &head[..=separator],
+                None => "",
+            };
+            sanitized.push_str(head);
+
+            let siblings = indices.entry(sanitized.clone()).or_default();
+            let next_index = siblings.len() + 1;
+            let index = *siblings.entry(rest[..end].to_owned()).or_insert(next_index);
+            sanitized.push_str(&format!("_bindgen_ty_{}", index));
+
+            rest = &rest[end..];
+        }
+        sanitized.push_str(rest);
+
+        Some(
+            sanitized
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect(),
+        )
+    }
+}
+
+impl ParseCallbacks for AnonymousTypeNames {
+    fn item_name(&self, original_item_name: &str) -> Option<String> {
+        self.sanitize(original_item_name)
+    }
+}
 
 /// Generate lv2-sys bindings and write them to out.
 pub fn generate_bindings<I>(source_dir: &Path, out: &Path, clang_args: I)
@@ -15,7 +76,8 @@ where
         .whitelist_function("LV2.*")
         .whitelist_var("LV2.*")
         .layout_tests(false)
-        .bitfield_enum("LV2_State_Flags");
+        .bitfield_enum("LV2_State_Flags")
+        .parse_callbacks(Box::new(AnonymousTypeNames::default()));
     bindings = bindings.clang_args(clang_args);
 
     // Adding the headers to the include path of clang.
@@ -63,6 +125,7 @@ fn main() {
         .author("© 2020 Amaury 'Yruama_Lairba' Abrial, Jan-Oliver 'Janonard' Opdenhövel")
         .about("Generate Rust bindings of the LV2 C API")
         .version("0.1.0")
+        .setting(clap::AppSettings::SubcommandsNegateReqs)
         .arg(
             clap::Arg::with_name("LV2")
                 .help("The path to the LV2 C API")
@@ -89,11 +152,105 @@ fn main() {
                 .multiple(true)
                 .last(true),
         )
+        .subcommand(
+            clap::SubCommand::with_name("compare")
+                .about("Compare the items of two generated bindings")
+                .arg(
+                    clap::Arg::with_name("left")
+                        .help("The first bindings file")
+                        .required(true)
+                        .value_name("LEFT"),
+                )
+                .arg(
+                    clap::Arg::with_name("right")
+                        .help("The second bindings file")
+                        .required(true)
+                        .value_name("RIGHT"),
+                ),
+        )
         .get_matches();
 
+    if let Some(matches) = matches.subcommand_matches("compare") {
+        let read = |name: &str| {
+            let path = matches.value_of(name).unwrap();
+            fs::read_to_string(path).unwrap_or_else(|e| panic!("Couldn't read {}: {}", path, e))
+        };
+        let results =
+            compare::compare(&read("left"), &read("right")).expect("Couldn't parse bindings");
+        for result in results.iter() {
+            println!("{}", result);
+        }
+        if !results.is_empty() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let headers = PathBuf::from(".").join(matches.value_of("LV2").unwrap());
     let out = PathBuf::from(".").join(matches.value_of("out").unwrap());
     let clang_args = matches.values_of("clang args").unwrap();
 
     generate_bindings(&headers, &out, clang_args);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anonymous_type_names() {
+        let names = AnonymousTypeNames::default();
+
+        assert_eq!(None, names.sanitize("LV2_Atom_Event"));
+        assert_eq!(
+            Some("LV2_Atom_Event__bindgen_ty_1".to_owned()),
+            names.sanitize("LV2_Atom_Event_union (unnamed at lv2/atom/atom.h:214:2)")
+        );
+        assert_eq!(
+            Some("LV2_Atom_Event__bindgen_ty_2".to_owned()),
+            names.sanitize("LV2_Atom_Event_struct (unnamed at lv2/atom/atom.h:220:2)")
+        );
+        // The same type always gets the same name.
+        assert_eq!(
+            Some("LV2_Atom_Event__bindgen_ty_1".to_owned()),
+            names.sanitize("LV2_Atom_Event_union (unnamed at lv2/atom/atom.h:214:2)")
+        );
+        // Nested anonymous types are counted per parent.
+        assert_eq!(
+            Some("LV2_Atom_Event__bindgen_ty_1__bindgen_ty_1".to_owned()),
+            names.sanitize(
+                "LV2_Atom_Event_union (unnamed at lv2/atom/atom.h:214:2)_struct (anonymous at lv2/atom/atom.h:215:3)"
+            )
+        );
+        assert_eq!(
+            Some("_bindgen_ty_1".to_owned()),
+            names.sanitize("enum (unnamed at lv2/core/lv2.h:10:1)")
+        );
+    }
+
+    /// Generate bindings for a header with an anonymous union.
+    ///
+    /// This test needs libclang and is therefore ignored by default. Run it with `cargo test -p systool -- --ignored`.
+    #[test]
+    #[ignore]
+    fn test_generate_anonymous_union() {
+        let root = std::env::temp_dir().join(format!("systool-test-{}", std::process::id()));
+        let source_dir = root.join("lv2");
+        let spec_dir = source_dir.join("test");
+        fs::create_dir_all(&spec_dir).unwrap();
+        fs::write(
+            spec_dir.join("test.h"),
+            "typedef struct {\n    union {\n        int frames;\n        double beats;\n    } time;\n} LV2_Test_Event;\n",
+        )
+        .unwrap();
+
+        let out = root.join("bindings.rs");
+        generate_bindings(&source_dir, &out, Vec::<String>::new());
+        let bindings = fs::read_to_string(&out).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(bindings.contains("pub union LV2_Test_Event__bindgen_ty_1"));
+        assert!(bindings.contains("pub time: LV2_Test_Event__bindgen_ty_1"));
+        assert!(!bindings.contains("unnamed"));
+    }
+}
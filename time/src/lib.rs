@@ -4,8 +4,14 @@
 //! The original [specification](https://lv2plug.in/ns/ext/time/time.html) contains means to
 //! describe time for LV2 values in RDF files. This implementation is focused on the stock time
 //! descriptions defined by the specification by binding them to marker types.
+//!
+//! Hosts usually send the transport state as `time:Position` objects, which can be decoded with the [`Position`](struct.Position.html) struct.
+extern crate lv2_atom as atom;
 extern crate lv2_sys as sys;
 
+use atom::atoms::object::ObjectReader;
+use atom::prelude::*;
+use atom::read_atom;
 use urid::*;
 
 /// All time URI bounds
@@ -110,7 +116,7 @@ pub mod time {
 use time::*;
 
 /// A URID cache containing all time properties.
-#[derive(URIDCollection)]
+#[derive(URIDCollection, Clone, Copy)]
 pub struct TimeURIDCollection {
     pub position_class: URID<PositionClass>,
     pub rate_class: URID<RateClass>,
@@ -123,12 +129,229 @@ pub struct TimeURIDCollection {
     pub beats_per_minute: URID<BeatsPerMinute>,
     pub frame: URID<Frame>,
     pub frames_per_second: URID<FramesPerSecond>,
-    pub position: URID<Position>,
+    pub position: URID<time::Position>,
     pub speed: URID<Speed>,
 }
 
+/// A decoded `time:Position` object.
+///
+/// A position describes a point in time as well as the speed at which time is passing. Hosts send positions to plugins with an `atom:Sequence` input port that supports `time:Position`, usually whenever the transport state changes. Every property is optional and only the properties that are present in the object are set; Hosts usually only send the properties that have changed.
+///
+/// Numeric properties are accepted as any numeric atom type and converted to the type of the field, since hosts don't agree on the types of all properties.
+///
+/// # Example
+///
+/// ```
+/// use lv2_atom::prelude::*;
+/// use lv2_time::*;
+///
+/// fn handle_event(
+///     atom: &UnidentifiedAtom,
+///     atom_urids: &AtomURIDCollection,
+///     time_urids: &TimeURIDCollection,
+///     bpm: &mut f32,
+/// ) {
+///     if let Some(position) = Position::from_atom(atom, atom_urids, time_urids) {
+///         if let Some(beats_per_minute) = position.beats_per_minute {
+///             *bpm = beats_per_minute;
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Position {
+    /// The frame number, i.e. the position in audio frames since the origin of the timeline.
+    pub frame: Option<i64>,
+    /// The rate of the progress of time as a fraction of normal speed.
+    pub speed: Option<f32>,
+    /// The bar number, starting at 0.
+    pub bar: Option<i64>,
+    /// The beat number within the bar.
+    pub bar_beat: Option<f32>,
+    /// The global running beat number.
+    pub beat: Option<f64>,
+    /// The note value that counts as one beat.
+    pub beat_unit: Option<i32>,
+    /// The number of beats per bar.
+    pub beats_per_bar: Option<f32>,
+    /// The tempo in beats per minute.
+    pub beats_per_minute: Option<f32>,
+    /// The frame rate in frames per second.
+    pub frames_per_second: Option<f32>,
+}
+
+impl Position {
+    /// Decode the properties of a position object.
+    ///
+    /// Properties that aren't part of a position or that can't be read as a number are ignored. This method doesn't check the type of the object, which is done by [`from_atom`](#method.from_atom).
+    pub fn read(
+        reader: ObjectReader,
+        atom_urids: &AtomURIDCollection,
+        time_urids: &TimeURIDCollection,
+    ) -> Self {
+        let mut position = Self::default();
+        for (header, value) in reader {
+            let key = header.key;
+            if key == time_urids.frame {
+                position.frame = read_integer(value, atom_urids);
+            } else if key == time_urids.speed {
+                position.speed = read_float(value, atom_urids).map(|v| v as f32);
+            } else if key == time_urids.bar {
+                position.bar = read_integer(value, atom_urids);
+            } else if key == time_urids.bar_beat {
+                position.bar_beat = read_float(value, atom_urids).map(|v| v as f32);
+            } else if key == time_urids.beat {
+                position.beat = read_float(value, atom_urids);
+            } else if key == time_urids.beat_unit {
+                position.beat_unit = read_integer(value, atom_urids).map(|v| v as i32);
+            } else if key == time_urids.beats_per_bar {
+                position.beats_per_bar = read_float(value, atom_urids).map(|v| v as f32);
+            } else if key == time_urids.beats_per_minute {
+                position.beats_per_minute = read_float(value, atom_urids).map(|v| v as f32);
+            } else if key == time_urids.frames_per_second {
+                position.frames_per_second = read_float(value, atom_urids).map(|v| v as f32);
+            }
+        }
+        position
+    }
+
+    /// Decode a position from an atom.
+    ///
+    /// The atom has to be an object or a blank of the type `time:Position`, otherwise `None` is returned.
+    pub fn from_atom(
+        atom: &UnidentifiedAtom,
+        atom_urids: &AtomURIDCollection,
+        time_urids: &TimeURIDCollection,
+    ) -> Option<Self> {
+        let (header, reader) = atom
+            .read(atom_urids.object)
+            .or_else(|_| atom.read(atom_urids.blank))
+            .ok()?;
+        if header.has_type(time_urids.position_class) {
+            Some(Self::read(reader, atom_urids, time_urids))
+        } else {
+            None
+        }
+    }
+}
+
+/// Read a numeric atom as an integer, truncating floating-point values.
+fn read_integer(atom: &UnidentifiedAtom, urids: &AtomURIDCollection) -> Option<i64> {
+    read_atom!(atom, {
+        urids.long => |v| Some(*v),
+        urids.int => |v| Some(*v as i64),
+        urids.double => |v| Some(*v as i64),
+        urids.float => |v| Some(*v as i64),
+        _ => None,
+    })
+}
+
+/// Read a numeric atom as a floating-point value.
+fn read_float(atom: &UnidentifiedAtom, urids: &AtomURIDCollection) -> Option<f64> {
+    read_atom!(atom, {
+        urids.double => |v| Some(*v),
+        urids.float => |v| Some(*v as f64),
+        urids.long => |v| Some(*v as f64),
+        urids.int => |v| Some(*v as f64),
+        _ => None,
+    })
+}
+
 /// Prelude of `lv2_time` for wildcard usage.
 pub mod prelude {
     pub use crate::time::*;
     pub use crate::TimeURIDCollection;
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use atom::atoms::object::{ObjectHeader, TypeUrid};
+    use atom::space::*;
+    use atom::AtomHeader;
+
+    #[test]
+    fn test_position() {
+        let map = HashURIDMapper::new();
+        let atom_urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let time_urids: TimeURIDCollection = map.populate_collection().unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(32);
+        let raw_space = raw_space.as_space_mut();
+
+        {
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            let mut writer = cursor
+                .write_atom(atom_urids.object)
+                .unwrap()
+                .write_header(ObjectHeader::new(
+                    None,
+                    TypeUrid::new(time_urids.position_class),
+                ))
+                .unwrap();
+            writer
+                .new_property(time_urids.frame, atom_urids.long)
+                .unwrap()
+                .set(48000)
+                .unwrap();
+            writer
+                .new_property(time_urids.speed, atom_urids.float)
+                .unwrap()
+                .set(1.0)
+                .unwrap();
+            writer
+                .new_property(time_urids.bar, atom_urids.long)
+                .unwrap()
+                .set(3)
+                .unwrap();
+            // Some hosts send the beat as a float, not as a double.
+            writer
+                .new_property(time_urids.beat, atom_urids.float)
+                .unwrap()
+                .set(12.5)
+                .unwrap();
+            writer
+                .new_property(time_urids.beats_per_minute, atom_urids.double)
+                .unwrap()
+                .set(120.0)
+                .unwrap();
+            writer
+                .new_property(time_urids.beat_unit, atom_urids.int)
+                .unwrap()
+                .set(4)
+                .unwrap();
+        }
+
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        let position = Position::from_atom(atom, &atom_urids, &time_urids).unwrap();
+        assert_eq!(
+            position,
+            Position {
+                frame: Some(48000),
+                speed: Some(1.0),
+                bar: Some(3),
+                bar_beat: None,
+                beat: Some(12.5),
+                beat_unit: Some(4),
+                beats_per_bar: None,
+                beats_per_minute: Some(120.0),
+                frames_per_second: None,
+            }
+        );
+
+        // Objects of other types aren't positions.
+        {
+            let mut cursor = SpaceCursor::new(raw_space.as_bytes_mut());
+            cursor
+                .write_atom(atom_urids.object)
+                .unwrap()
+                .write_header(ObjectHeader::new(
+                    None,
+                    TypeUrid::new(time_urids.rate_class),
+                ))
+                .unwrap();
+        }
+        let atom = unsafe { raw_space.read().next_atom() }.unwrap();
+        assert!(Position::from_atom(atom, &atom_urids, &time_urids).is_none());
+    }
+}
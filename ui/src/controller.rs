@@ -0,0 +1,151 @@
+//! Writing values to the ports of the plugin.
+use crate::port_event::PortProtocol;
+use std::ffi::c_void;
+use urid::URID;
+
+/// The handle to write values to the ports of the plugin.
+///
+/// The host passes a write function and an opaque controller pointer to the user interface when it's instantiated. The user interface uses them to send new values to the ports of the plugin, for example when a knob is turned.
+///
+/// Values are written with a [`PortProtocol`](../port_event/trait.PortProtocol.html), which provides the bytes that are transmitted to the host. Protocols with values of variable length, like [`AtomTransfer`](../port_event/struct.AtomTransfer.html), transmit the complete value and not only a fixed-size part of it.
+pub struct UiController {
+    write_function: unsafe extern "C" fn(
+        controller: sys::LV2UI_Controller,
+        port_index: u32,
+        buffer_size: u32,
+        port_protocol: u32,
+        buffer: *const c_void,
+    ),
+    controller: sys::LV2UI_Controller,
+}
+
+impl UiController {
+    /// Create a new controller from the write function and controller passed by the host.
+    ///
+    /// Returns `None` if the write function is null.
+    ///
+    /// # Safety
+    ///
+    /// The write function and the controller have to be the ones the host passed to the `instantiate` function of the user interface and they have to stay valid while the controller is in use.
+    pub unsafe fn new(
+        write_function: sys::LV2UI_Write_Function,
+        controller: sys::LV2UI_Controller,
+    ) -> Option<Self> {
+        Some(Self {
+            write_function: write_function?,
+            controller,
+        })
+    }
+
+    /// Write a value to the port with the given index.
+    ///
+    /// The value is transmitted with the given protocol, which has to be supported by the port. The host copies the transmitted bytes and therefore, the value doesn't need to outlive this call.
+    pub fn write_to_port<P: PortProtocol>(
+        &self,
+        port_index: u32,
+        protocol: URID<P>,
+        value: &P::WriteParameter,
+    ) {
+        let bytes = P::write_bytes(value);
+        unsafe {
+            (self.write_function)(
+                self.controller,
+                port_index,
+                bytes.len() as u32,
+                P::write_format(protocol),
+                bytes.as_ptr() as *const c_void,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::*;
+    use crate::port_event::*;
+    use atom::prelude::*;
+    use atom::space::*;
+    use atom::{AtomHeader, OwnedAtom};
+    use urid::*;
+
+    struct Write {
+        port_index: u32,
+        port_protocol: u32,
+        buffer: Vec<u8>,
+    }
+
+    unsafe extern "C" fn write_function(
+        controller: sys::LV2UI_Controller,
+        port_index: u32,
+        buffer_size: u32,
+        port_protocol: u32,
+        buffer: *const c_void,
+    ) {
+        let writes = &mut *(controller as *mut Vec<Write>);
+        writes.push(Write {
+            port_index,
+            port_protocol,
+            buffer: std::slice::from_raw_parts(buffer as *const u8, buffer_size as usize).to_vec(),
+        });
+    }
+
+    #[test]
+    fn test_write_to_port() {
+        let map = HashURIDMapper::new();
+        let urids: PortProtocolURIDCollection = map.populate_collection().unwrap();
+        let atom_urids: AtomURIDCollection = map.populate_collection().unwrap();
+
+        let mut writes: Vec<Write> = Vec::new();
+        let controller = unsafe {
+            UiController::new(
+                Some(write_function),
+                &mut writes as *mut Vec<Write> as sys::LV2UI_Controller,
+            )
+        }
+        .unwrap();
+
+        controller.write_to_port(0, urids.float_protocol, &0.5);
+
+        let mut owned = OwnedAtom::new();
+        owned
+            .write()
+            .write_atom(atom_urids.string)
+            .unwrap()
+            .append("A variable-length payload")
+            .unwrap();
+        let atom = owned.atom().unwrap();
+        controller.write_to_port(1, urids.atom_transfer, atom);
+
+        assert_eq!(writes.len(), 2);
+
+        assert_eq!(writes[0].port_index, 0);
+        assert_eq!(writes[0].port_protocol, 0);
+        assert_eq!(writes[0].buffer, 0.5f32.to_ne_bytes());
+
+        // The complete atom is transmitted, including the header.
+        assert_eq!(writes[1].port_index, 1);
+        assert_eq!(writes[1].port_protocol, urids.atom_transfer.get());
+        assert_eq!(writes[1].buffer.len(), 8 + 26);
+        assert_eq!(writes[1].buffer, atom.atom_space().as_bytes());
+
+        // The host can decode the transmitted atom.
+        let mut buffer = AlignedVec::<AtomHeader>::new_with_capacity(8);
+        buffer.as_space_mut().as_bytes_mut()[..writes[1].buffer.len()]
+            .copy_from_slice(&writes[1].buffer);
+        let event = unsafe {
+            PortEvent::from_raw(
+                writes[1].port_index,
+                writes[1].buffer.len() as u32,
+                writes[1].port_protocol,
+                buffer.as_space().as_bytes().as_ptr() as *const c_void,
+            )
+        };
+        let atom = event.read(urids.atom_transfer).unwrap();
+        assert_eq!(
+            atom.read(atom_urids.string).unwrap(),
+            "A variable-length payload"
+        );
+
+        assert!(unsafe { UiController::new(None, std::ptr::null_mut()) }.is_none());
+    }
+}
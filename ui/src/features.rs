@@ -0,0 +1,291 @@
+//! Host features for user interfaces.
+use core::feature::*;
+use std::error::Error;
+use std::ffi::c_void;
+use std::fmt;
+use std::os::raw::c_int;
+use urid::{Uri, UriBound};
+
+/// Feature that provides the parent widget of the user interface.
+///
+/// The parent is a toolkit-specific handle of the widget the user interface should be embedded into, for example an X11 `Window` or a `HWND` on Windows. The pointer isn't interpreted in any way and it's up to you to cast it to the correct type.
+pub struct Parent {
+    widget: *mut c_void,
+}
+
+unsafe impl UriBound for Parent {
+    const URI: &'static [u8] = sys::LV2_UI__parent;
+}
+
+unsafe impl Feature for Parent {
+    unsafe fn from_feature_ptr(feature: *const c_void, _: ThreadingClass) -> Option<Self> {
+        Some(Self {
+            widget: feature as *mut c_void,
+        })
+    }
+}
+
+impl Parent {
+    /// Return the handle of the parent widget.
+    pub fn widget(&self) -> *mut c_void {
+        self.widget
+    }
+}
+
+/// An error that occurs when the host rejects a size request.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ResizeError(pub c_int);
+
+impl fmt::Display for ResizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The host rejected the size request with code {}", self.0)
+    }
+}
+
+impl Error for ResizeError {}
+
+/// Feature to request a new size of the user interface from the host.
+pub struct Resize<'a> {
+    internal: &'a sys::LV2UI_Resize,
+}
+
+unsafe impl<'a> UriBound for Resize<'a> {
+    const URI: &'static [u8] = sys::LV2_UI__resize;
+}
+
+unsafe impl<'a> Feature for Resize<'a> {
+    unsafe fn from_feature_ptr(feature: *const c_void, _: ThreadingClass) -> Option<Self> {
+        let internal = (feature as *const sys::LV2UI_Resize).as_ref()?;
+        internal.ui_resize?;
+        Some(Self { internal })
+    }
+}
+
+impl<'a> Resize<'a> {
+    /// Inform the host about the new size of the user interface, in pixels.
+    ///
+    /// Returns a [`ResizeError`](struct.ResizeError.html) with the status code of the host if it rejected the size.
+    pub fn resize(&self, width: i32, height: i32) -> Result<(), ResizeError> {
+        // The function pointer was checked when the feature was created.
+        let ui_resize = self.internal.ui_resize.unwrap();
+        match unsafe { (ui_resize)(self.internal.handle, width, height) } {
+            0 => Ok(()),
+            code => Err(ResizeError(code)),
+        }
+    }
+}
+
+/// Feature that provides the handle of the plugin instance.
+///
+/// The handle is the pointer the plugin's `instantiate` function returned. A user interface can only use it if it runs in the same process as the plugin and if it knows the concrete type of the plugin, which is why hosts only provide this feature to in-process user interfaces that require it.
+///
+/// For plugins created with `rust-lv2`, the handle points to a [`PluginInstance`](../../lv2_core/plugin/struct.PluginInstance.html), which can also be interpreted as a pointer to the plugin itself.
+///
+/// # Safety caveats
+///
+/// Retrieving the handle is safe, but using it is not:
+///
+/// * The handle is only valid if the user interface runs in the same process as the plugin. Hosts that run user interfaces out of process don't provide this feature, so it should be optional if the user interface also works without it.
+/// * The user interface has to know the exact type of the plugin. Casting the handle to another type is undefined behaviour.
+/// * The plugin runs in the audio thread while the user interface runs in the UI thread. Accessing the plugin is therefore a data race unless the accessed data is synchronized, for example with atomics. Ports should be used to communicate with the plugin whenever possible.
+/// * The handle is invalid after the host has cleaned up the plugin instance, which may happen before the user interface is destroyed.
+pub struct InstanceAccess {
+    handle: *mut c_void,
+}
+
+unsafe impl UriBound for InstanceAccess {
+    const URI: &'static [u8] = sys::LV2_INSTANCE_ACCESS_URI;
+}
+
+unsafe impl Feature for InstanceAccess {
+    unsafe fn from_feature_ptr(feature: *const c_void, _: ThreadingClass) -> Option<Self> {
+        if feature.is_null() {
+            None
+        } else {
+            Some(Self {
+                handle: feature as *mut c_void,
+            })
+        }
+    }
+}
+
+impl InstanceAccess {
+    /// Return the raw handle of the plugin instance.
+    ///
+    /// Read the [safety caveats](#safety-caveats) before dereferencing the handle.
+    pub fn handle(&self) -> *const c_void {
+        self.handle
+    }
+}
+
+/// Feature to retrieve the extension data of the plugin instance.
+///
+/// Like [`InstanceAccess`](struct.InstanceAccess.html), this feature is only provided to user interfaces that run in the same process as their plugin.
+///
+/// # Safety caveats
+///
+/// The returned extension data is a raw pointer to the interface of the requested extension, for example an `LV2_State_Interface`. The user interface has to cast it to the correct type, which isn't checked in any way. The functions of these interfaces usually require the handle of the plugin instance, which is provided by the [`InstanceAccess`](struct.InstanceAccess.html) feature, and the same caveats about threading and the lifetime of the instance apply to them.
+pub struct DataAccess<'a> {
+    internal: &'a sys::LV2_Extension_Data_Feature,
+}
+
+unsafe impl<'a> UriBound for DataAccess<'a> {
+    const URI: &'static [u8] = sys::LV2_DATA_ACCESS_URI;
+}
+
+unsafe impl<'a> Feature for DataAccess<'a> {
+    unsafe fn from_feature_ptr(feature: *const c_void, _: ThreadingClass) -> Option<Self> {
+        let internal = (feature as *const sys::LV2_Extension_Data_Feature).as_ref()?;
+        internal.data_access?;
+        Some(Self { internal })
+    }
+}
+
+impl<'a> DataAccess<'a> {
+    /// Call the `extension_data` function of the plugin with the given URI.
+    ///
+    /// Returns a null pointer if the plugin doesn't provide the extension. Read the [safety caveats](#safety-caveats) before dereferencing the pointer.
+    pub fn data_access(&self, uri: &Uri) -> *const c_void {
+        // The function pointer was checked when the feature was created.
+        let data_access = self.internal.data_access.unwrap();
+        unsafe { (data_access)(uri.as_ptr()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::*;
+    use core::plugin::FeatureCollection;
+    use std::cell::Cell;
+    use std::ffi::c_void;
+    use std::os::raw::{c_char, c_int};
+    use std::ptr::null;
+    use urid::UriBound;
+
+    thread_local! {
+        static REQUESTED_SIZE: Cell<(c_int, c_int)> = const { Cell::new((0, 0)) };
+    }
+
+    unsafe extern "C" fn ui_resize(handle: *mut c_void, width: c_int, height: c_int) -> c_int {
+        REQUESTED_SIZE.with(|size| size.set((width, height)));
+        *(handle as *const c_int)
+    }
+
+    static DATA: u32 = 17;
+
+    unsafe extern "C" fn data_access(uri: *const c_char) -> *const c_void {
+        if uri == sys::LV2_UI__parent.as_ptr() as *const c_char {
+            &DATA as *const u32 as *const c_void
+        } else {
+            null()
+        }
+    }
+
+    fn feature<F: UriBound>(data: *mut c_void) -> sys::LV2_Feature {
+        sys::LV2_Feature {
+            URI: F::URI.as_ptr() as *const c_char,
+            data,
+        }
+    }
+
+    #[test]
+    fn test_ui_features() {
+        let mut widget: u64 = 0;
+        let mut instance: u64 = 0;
+        let status: Cell<c_int> = Cell::new(0);
+        let mut resize = sys::LV2UI_Resize {
+            handle: status.as_ptr() as *mut c_void,
+            ui_resize: Some(ui_resize),
+        };
+        let mut extension_data = sys::LV2_Extension_Data_Feature {
+            data_access: Some(data_access),
+        };
+
+        let parent_feature = feature::<Parent>(&mut widget as *mut u64 as *mut c_void);
+        let resize_feature = feature::<Resize>(&mut resize as *mut _ as *mut c_void);
+        let instance_feature = feature::<InstanceAccess>(&mut instance as *mut u64 as *mut c_void);
+        let data_feature = feature::<DataAccess>(&mut extension_data as *mut _ as *mut c_void);
+        let features_list: &[*const sys::LV2_Feature] = &[
+            &parent_feature,
+            &resize_feature,
+            &instance_feature,
+            &data_feature,
+            null(),
+        ];
+        let mut cache = unsafe { FeatureCache::from_raw(features_list.as_ptr()) };
+
+        let parent: Parent = cache.retrieve_feature(ThreadingClass::Other).unwrap();
+        assert_eq!(parent.widget(), &mut widget as *mut u64 as *mut c_void);
+
+        let resize: Resize = cache.retrieve_feature(ThreadingClass::Other).unwrap();
+        assert_eq!(Ok(()), resize.resize(640, 480));
+        assert_eq!((640, 480), REQUESTED_SIZE.with(Cell::get));
+        status.set(1);
+        assert_eq!(Err(ResizeError(1)), resize.resize(320, 240));
+        assert_eq!((320, 240), REQUESTED_SIZE.with(Cell::get));
+
+        let instance_access: InstanceAccess =
+            cache.retrieve_feature(ThreadingClass::Other).unwrap();
+        assert_eq!(
+            instance_access.handle(),
+            &instance as *const u64 as *const c_void
+        );
+
+        let data: DataAccess = cache.retrieve_feature(ThreadingClass::Other).unwrap();
+        assert_eq!(
+            data.data_access(Parent::uri()),
+            &DATA as *const u32 as *const c_void
+        );
+        assert!(data.data_access(Resize::uri()).is_null());
+    }
+
+    #[test]
+    fn test_invalid_ui_features() {
+        let resize = sys::LV2UI_Resize {
+            handle: std::ptr::null_mut(),
+            ui_resize: None,
+        };
+        let extension_data = sys::LV2_Extension_Data_Feature { data_access: None };
+
+        unsafe {
+            assert!(Resize::from_feature_ptr(null(), ThreadingClass::Other).is_none());
+            assert!(Resize::from_feature_ptr(
+                &resize as *const _ as *const c_void,
+                ThreadingClass::Other
+            )
+            .is_none());
+            assert!(InstanceAccess::from_feature_ptr(null(), ThreadingClass::Other).is_none());
+            assert!(DataAccess::from_feature_ptr(
+                &extension_data as *const _ as *const c_void,
+                ThreadingClass::Other
+            )
+            .is_none());
+        }
+    }
+
+    #[derive(FeatureCollection)]
+    struct InProcessFeatures<'a> {
+        instance_access: InstanceAccess,
+        data_access: Option<DataAccess<'a>>,
+    }
+
+    #[test]
+    fn test_instance_access_collection() {
+        let mut instance: u64 = 0;
+        let instance_feature = feature::<InstanceAccess>(&mut instance as *mut u64 as *mut c_void);
+
+        // The collection can't be created without the instance access.
+        let features_list: &[*const sys::LV2_Feature] = &[null()];
+        let mut cache = unsafe { FeatureCache::from_raw(features_list.as_ptr()) };
+        assert!(InProcessFeatures::from_cache(&mut cache, ThreadingClass::Other).is_err());
+
+        let features_list: &[*const sys::LV2_Feature] = &[&instance_feature, null()];
+        let mut cache = unsafe { FeatureCache::from_raw(features_list.as_ptr()) };
+        let features = InProcessFeatures::from_cache(&mut cache, ThreadingClass::Other).unwrap();
+        assert_eq!(
+            features.instance_access.handle(),
+            &instance as *const u64 as *const c_void
+        );
+        assert!(features.data_access.is_none());
+    }
+}
@@ -0,0 +1,40 @@
+//! Library to create user interfaces for LV2 plugins.
+//!
+//! The original [specification](https://lv2plug.in/ns/extensions/ui/ui.html) describes how a user interface, which lives in its own binary, is instantiated by the host and embedded into the host's window. This crate currently provides the host features a user interface needs for this:
+//!
+//! * [`Parent`](features/struct.Parent.html) provides the widget the user interface should be embedded into.
+//! * [`Resize`](features/struct.Resize.html) lets the user interface request a new size from the host.
+//! * [`InstanceAccess`](features/struct.InstanceAccess.html) and [`DataAccess`](features/struct.DataAccess.html) give a user interface, which runs in the same process as its plugin, direct access to the plugin instance.
+//!
+//! All of them are [`Feature`](../lv2_core/feature/trait.Feature.html)s and can therefore be used in a [`FeatureCollection`](../lv2_core/feature/trait.FeatureCollection.html):
+//!
+//! ```
+//! use lv2_core::feature::*;
+//! use lv2_core::plugin::*;
+//! use lv2_ui::prelude::*;
+//!
+//! #[derive(FeatureCollection)]
+//! struct UiFeatures<'a> {
+//!     parent: Parent,
+//!     resize: Option<Resize<'a>>,
+//! }
+//! ```
+//!
+//! The [`port_event`](port_event/index.html) module decodes the data the host sends to the user interface when something happens at a plugin port, and the [`UiController`](controller/struct.UiController.html) writes values to the ports of the plugin.
+extern crate lv2_atom as atom;
+extern crate lv2_core as core;
+extern crate lv2_sys as sys;
+
+pub mod controller;
+pub mod features;
+pub mod port_event;
+
+/// Prelude of `lv2_ui` for wildcard usage.
+pub mod prelude {
+    pub use crate::controller::UiController;
+    pub use crate::features::{DataAccess, InstanceAccess, Parent, Resize};
+    pub use crate::port_event::{
+        AtomTransfer, EventTransfer, FloatProtocol, PortEvent, PortProtocol,
+        PortProtocolURIDCollection,
+    };
+}
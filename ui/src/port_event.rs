@@ -0,0 +1,331 @@
+//! Data that the host sends to the user interface when something happens at a plugin port.
+//!
+//! The host notifies the user interface about port changes by calling its `port_event` function with the index of the port, a raw buffer and the format of the buffer. The format is either 0, which denotes the [`FloatProtocol`](struct.FloatProtocol.html), or the URID of a [`PortProtocol`](trait.PortProtocol.html).
+//!
+//! A [`PortEvent`](struct.PortEvent.html) wraps these parameters and decodes the buffer with a protocol:
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_ui::port_event::*;
+//! use urid::*;
+//!
+//! fn handle_event(event: &PortEvent, ui_urids: &PortProtocolURIDCollection, atom_urids: &AtomURIDCollection) {
+//!     if let Some(value) = event.read(ui_urids.float_protocol) {
+//!         println!("Port {} has the value {}", event.port_index(), value);
+//!     } else if let Some(atom) = event.read(ui_urids.event_transfer) {
+//!         if let Ok(value) = atom.read(atom_urids.int) {
+//!             println!("Port {} received the integer {}", event.port_index(), value);
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The same protocols are used by the [`UiController`](../controller/struct.UiController.html) to write values to the ports of the plugin.
+use atom::space::AtomSpace;
+use atom::UnidentifiedAtom;
+use std::ffi::c_void;
+use urid::*;
+
+/// A family of types that are created by a [`PortProtocol`](trait.PortProtocol.html) and may borrow the event buffer.
+pub trait ProtocolHandle<'a> {
+    type Handle: 'a;
+}
+
+/// A protocol to transfer data between a plugin port and the user interface.
+pub trait PortProtocol: UriBound + Sized {
+    /// The type of the decoded events.
+    ///
+    /// It may contain a reference to the event buffer and therefore may not outlive it.
+    type ReadHandle: for<'a> ProtocolHandle<'a>;
+
+    /// Check whether an event with the given format uses this protocol.
+    ///
+    /// By default, the format has to be the URID of the protocol.
+    fn matches_format(format: u32, protocol: URID<Self>) -> bool {
+        format == protocol.get()
+    }
+
+    /// Decode the buffer of an event.
+    ///
+    /// Returns `None` if the buffer can't be decoded, for example because it's too small.
+    ///
+    /// # Safety
+    ///
+    /// The buffer has to contain data in the format of this protocol.
+    unsafe fn read_event<'a>(
+        buffer: &'a [u8],
+    ) -> Option<<Self::ReadHandle as ProtocolHandle<'a>>::Handle>;
+
+    /// The type of the values that are written to a port with this protocol.
+    type WriteParameter: ?Sized;
+
+    /// The format that is passed to the host when a value is written to a port.
+    ///
+    /// By default, this is the URID of the protocol.
+    fn write_format(protocol: URID<Self>) -> u32 {
+        protocol.get()
+    }
+
+    /// Return the bytes that are transmitted to the host when the value is written to a port.
+    ///
+    /// The bytes have to contain the complete value, which may be of variable length.
+    fn write_bytes(parameter: &Self::WriteParameter) -> &[u8];
+}
+
+/// The protocol for control ports, where the buffer contains a single `f32`.
+///
+/// Events of this protocol have the special format 0.
+pub struct FloatProtocol;
+
+unsafe impl UriBound for FloatProtocol {
+    const URI: &'static [u8] = sys::LV2_UI__floatProtocol;
+}
+
+impl<'a> ProtocolHandle<'a> for FloatProtocol {
+    type Handle = f32;
+}
+
+impl PortProtocol for FloatProtocol {
+    type ReadHandle = Self;
+
+    fn matches_format(format: u32, protocol: URID<Self>) -> bool {
+        format == 0 || format == protocol.get()
+    }
+
+    unsafe fn read_event(buffer: &[u8]) -> Option<f32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(buffer.get(..4)?);
+        Some(f32::from_ne_bytes(bytes))
+    }
+
+    type WriteParameter = f32;
+
+    fn write_format(_: URID<Self>) -> u32 {
+        0
+    }
+
+    fn write_bytes(parameter: &f32) -> &[u8] {
+        // SAFETY: Every initialized `f32` is also a valid sequence of four bytes.
+        unsafe {
+            std::slice::from_raw_parts(
+                parameter as *const f32 as *const u8,
+                std::mem::size_of::<f32>(),
+            )
+        }
+    }
+}
+
+/// The protocol for atom ports, where the buffer contains a single atom.
+///
+/// The host sends the value of an atom port with this protocol, if the user interface subscribed to the port with it.
+pub struct AtomTransfer;
+
+unsafe impl UriBound for AtomTransfer {
+    const URI: &'static [u8] = sys::LV2_ATOM__atomTransfer;
+}
+
+impl<'a> ProtocolHandle<'a> for AtomTransfer {
+    type Handle = &'a UnidentifiedAtom;
+}
+
+impl PortProtocol for AtomTransfer {
+    type ReadHandle = Self;
+
+    unsafe fn read_event(buffer: &[u8]) -> Option<&UnidentifiedAtom> {
+        read_atom_buffer(buffer)
+    }
+
+    type WriteParameter = UnidentifiedAtom;
+
+    fn write_bytes(parameter: &UnidentifiedAtom) -> &[u8] {
+        parameter.atom_space().as_bytes()
+    }
+}
+
+/// The protocol for atom ports, where the buffer contains a single atom that was sent as an event.
+///
+/// This is the protocol hosts use to forward the events a plugin writes to its output sequences, which is the usual way for plugins to push updates to their user interfaces.
+pub struct EventTransfer;
+
+unsafe impl UriBound for EventTransfer {
+    const URI: &'static [u8] = sys::LV2_ATOM__eventTransfer;
+}
+
+impl<'a> ProtocolHandle<'a> for EventTransfer {
+    type Handle = &'a UnidentifiedAtom;
+}
+
+impl PortProtocol for EventTransfer {
+    type ReadHandle = Self;
+
+    unsafe fn read_event(buffer: &[u8]) -> Option<&UnidentifiedAtom> {
+        read_atom_buffer(buffer)
+    }
+
+    type WriteParameter = UnidentifiedAtom;
+
+    fn write_bytes(parameter: &UnidentifiedAtom) -> &[u8] {
+        parameter.atom_space().as_bytes()
+    }
+}
+
+/// Read the atom in the buffer of an atom-based protocol.
+///
+/// Returns `None` if the buffer is misaligned or too small for the atom header or the atom body.
+unsafe fn read_atom_buffer(buffer: &[u8]) -> Option<&UnidentifiedAtom> {
+    AtomSpace::from_bytes(buffer).ok()?.read().next_atom().ok()
+}
+
+/// A URID cache containing all port protocols.
+#[derive(URIDCollection, Clone, Copy)]
+pub struct PortProtocolURIDCollection {
+    pub float_protocol: URID<FloatProtocol>,
+    pub atom_transfer: URID<AtomTransfer>,
+    pub event_transfer: URID<EventTransfer>,
+}
+
+/// A notification of the host about something that happened at a plugin port.
+pub struct PortEvent<'a> {
+    port_index: u32,
+    format: u32,
+    buffer: &'a [u8],
+}
+
+impl<'a> PortEvent<'a> {
+    /// Create a port event from the parameters of the raw `port_event` function.
+    ///
+    /// # Safety
+    ///
+    /// If the buffer isn't null, it has to point to `buffer_size` bytes in the given format, which have to be valid for the lifetime `'a`.
+    pub unsafe fn from_raw(
+        port_index: u32,
+        buffer_size: u32,
+        format: u32,
+        buffer: *const c_void,
+    ) -> Self {
+        let buffer = if buffer.is_null() {
+            &[]
+        } else {
+            std::slice::from_raw_parts(buffer as *const u8, buffer_size as usize)
+        };
+        Self {
+            port_index,
+            format,
+            buffer,
+        }
+    }
+
+    /// The index of the port.
+    pub fn port_index(&self) -> u32 {
+        self.port_index
+    }
+
+    /// The format of the buffer; Either 0 or the URID of a port protocol.
+    pub fn format(&self) -> u32 {
+        self.format
+    }
+
+    /// The raw buffer of the event.
+    pub fn buffer(&self) -> &'a [u8] {
+        self.buffer
+    }
+
+    /// Try to decode the event with the given protocol.
+    ///
+    /// Returns `None` if the event uses another protocol or if the buffer can't be decoded, for example because it's smaller than an atom header.
+    pub fn read<P: PortProtocol>(
+        &self,
+        protocol: URID<P>,
+    ) -> Option<<P::ReadHandle as ProtocolHandle<'a>>::Handle> {
+        if P::matches_format(self.format, protocol) {
+            // SAFETY: The format is checked above and the buffer is valid according to the constructor.
+            unsafe { P::read_event(self.buffer) }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::port_event::*;
+    use atom::prelude::*;
+    use atom::space::*;
+    use atom::AtomHeader;
+
+    #[test]
+    fn test_float_protocol() {
+        let map = HashURIDMapper::new();
+        let urids: PortProtocolURIDCollection = map.populate_collection().unwrap();
+
+        let value: f32 = 0.5;
+        let event = unsafe { PortEvent::from_raw(3, 4, 0, &value as *const f32 as *const c_void) };
+        assert_eq!(event.port_index(), 3);
+        assert_eq!(event.read(urids.float_protocol), Some(0.5));
+        assert!(event.read(urids.event_transfer).is_none());
+
+        let event = unsafe {
+            PortEvent::from_raw(
+                3,
+                4,
+                urids.float_protocol.get(),
+                &value as *const f32 as *const c_void,
+            )
+        };
+        assert_eq!(event.read(urids.float_protocol), Some(0.5));
+    }
+
+    #[test]
+    fn test_atom_transfer() {
+        let map = HashURIDMapper::new();
+        let urids: PortProtocolURIDCollection = map.populate_collection().unwrap();
+        let atom_urids: AtomURIDCollection = map.populate_collection().unwrap();
+
+        let mut raw_space = AlignedVec::<AtomHeader>::new_with_capacity(4);
+        let raw_space = raw_space.as_space_mut();
+        SpaceCursor::new(raw_space.as_bytes_mut())
+            .write_atom(atom_urids.int)
+            .unwrap()
+            .set(42)
+            .unwrap();
+        let bytes = raw_space.as_bytes();
+
+        for format in [urids.atom_transfer.get(), urids.event_transfer.get()] {
+            let event =
+                unsafe { PortEvent::from_raw(1, 16, format, bytes.as_ptr() as *const c_void) };
+            assert!(event.read(urids.float_protocol).is_none());
+            let atom = event
+                .read(urids.atom_transfer)
+                .or_else(|| event.read(urids.event_transfer))
+                .unwrap();
+            assert_eq!(*atom.read(atom_urids.int).unwrap(), 42);
+        }
+
+        // The buffer is smaller than an atom header.
+        let event = unsafe {
+            PortEvent::from_raw(
+                1,
+                4,
+                urids.event_transfer.get(),
+                bytes.as_ptr() as *const c_void,
+            )
+        };
+        assert!(event.read(urids.event_transfer).is_none());
+
+        // The buffer is smaller than the atom body.
+        let event = unsafe {
+            PortEvent::from_raw(
+                1,
+                10,
+                urids.event_transfer.get(),
+                bytes.as_ptr() as *const c_void,
+            )
+        };
+        assert!(event.read(urids.event_transfer).is_none());
+
+        // There is no buffer at all.
+        let event =
+            unsafe { PortEvent::from_raw(1, 0, urids.event_transfer.get(), std::ptr::null()) };
+        assert!(event.read(urids.event_transfer).is_none());
+    }
+}
@@ -128,11 +128,20 @@ pub mod units {
     unsafe impl UriBound for Semitone {
         const URI: &'static [u8] = sys::LV2_UNITS__semitone12TET;
     }
+
+    /// The `units:unit` property, which relates a value to its unit.
+    ///
+    /// This isn't a unit itself, but the key used to annotate values with one of the other units, so that hosts and GUIs can display them with the correct suffix.
+    pub struct Unit;
+    unsafe impl UriBound for Unit {
+        const URI: &'static [u8] = sys::LV2_UNITS__unit;
+    }
 }
 
 use units::*;
 
 /// A URID cache containing all units.
+#[derive(Clone, Copy)]
 pub struct UnitURIDCollection {
     pub bar: URID<Bar>,
     pub beat: URID<Beat>,
@@ -158,6 +167,7 @@ pub struct UnitURIDCollection {
     pub percent: URID<Percent>,
     pub s: URID<Second>,
     pub semitone: URID<Semitone>,
+    pub unit: URID<Unit>,
 }
 
 impl URIDCollection for UnitURIDCollection {
@@ -187,10 +197,37 @@ impl URIDCollection for UnitURIDCollection {
             percent: map.map_type()?,
             s: map.map_type()?,
             semitone: map.map_type()?,
+            unit: map.map_type()?,
         })
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::UnitURIDCollection;
+    use urid::*;
+
+    #[test]
+    fn test_clone_collection() {
+        let map = HashURIDMapper::new();
+        let urids: UnitURIDCollection = map.populate_collection().unwrap();
+
+        fn clone_collection<T: Clone>(collection: &T) -> T {
+            collection.clone()
+        }
+
+        let cloned = clone_collection(&urids);
+        assert_eq!(urids.bar, cloned.bar);
+        assert_eq!(urids.frame, cloned.frame);
+        assert_eq!(urids.semitone, cloned.semitone);
+
+        let copied = urids;
+        assert_eq!(urids.beat, copied.beat);
+        assert_eq!(urids.hz, copied.hz);
+        assert_eq!(urids.unit, copied.unit);
+    }
+}
+
 /// Prelude of `lv2_units` for wildcard usage.
 pub mod prelude {
     pub use crate::units::*;
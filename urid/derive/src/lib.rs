@@ -7,7 +7,7 @@ mod urid_collection_derive;
 
 use proc_macro::TokenStream;
 
-#[proc_macro_derive(URIDCollection)]
+#[proc_macro_derive(URIDCollection, attributes(urid))]
 pub fn urid_collection_derive(input: TokenStream) -> TokenStream {
     urid_collection_derive::urid_collection_derive_impl(input)
 }
@@ -1,24 +1,45 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Data, DataStruct, DeriveInput};
+use syn::{parse_macro_input, Attribute, Data, DataStruct, DeriveInput, Meta, NestedMeta};
+
+/// Check whether a field is marked with `#[urid(default)]`.
+///
+/// Such fields aren't populated by the map, but are initialized with `Default::default()`.
+fn is_default(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("urid"))
+        .any(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => path.is_ident("default"),
+                _ => panic!("Unknown `urid` attribute argument, expected `default`"),
+            }),
+            _ => panic!("Invalid `urid` attribute, expected `#[urid(default)]`"),
+        })
+}
 
 pub fn urid_collection_derive_impl(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input);
 
     let struct_name = input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
 
     let fields = match input.data {
         Data::Struct(DataStruct { fields, .. }) => fields,
         _ => panic!("Only structs can implement `URIDCollection`"),
     };
 
-    let field_inits = fields
-        .iter()
-        .map(|field| field.ident.as_ref().unwrap())
-        .map(|ident| quote! {#ident: map.populate_collection()?,});
+    let field_inits = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        if is_default(&field.attrs) {
+            quote! {#ident: ::std::default::Default::default(),}
+        } else {
+            quote! {#ident: map.populate_collection()?,}
+        }
+    });
 
     let implementation = quote! {
-        impl URIDCollection for #struct_name {
+        impl #impl_generics URIDCollection for #struct_name #type_generics #where_clause {
             fn from_map<M: Map + ?Sized>(map: &M) -> Option<Self> {
                 Some(Self {
                     #(#field_inits)*
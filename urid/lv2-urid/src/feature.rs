@@ -5,11 +5,19 @@ use std::ffi::c_void;
 use urid::*;
 
 /// Host feature to map URIs to integers
+///
+/// The mapping may be shared with and used by other non-realtime threads, for example the worker thread.
 #[repr(transparent)]
 pub struct LV2Map<'a> {
     internal: &'a sys::LV2_URID_Map,
 }
 
+// SAFETY: The specification requires the host's map function to be callable from any thread, with
+// concurrent calls being safe. The wrapper only contains a shared reference to the interface.
+// Interfaces created by `HostMap` uphold this since they require a `Send + Sync` mapper.
+unsafe impl<'a> Send for LV2Map<'a> {}
+unsafe impl<'a> Sync for LV2Map<'a> {}
+
 unsafe impl<'a> UriBound for LV2Map<'a> {
     const URI: &'static [u8] = sys::LV2_URID_MAP_URI;
 }
@@ -41,11 +49,17 @@ impl<'a> Map for LV2Map<'a> {
 }
 
 /// Host feature to revert the URI -> URID mapping.
+///
+/// Like [`LV2Map`](struct.LV2Map.html), the unmapping may be shared with and used by other non-realtime threads.
 #[repr(transparent)]
 pub struct LV2Unmap<'a> {
     internal: &'a sys::LV2_URID_Unmap,
 }
 
+// SAFETY: The same thread-safety requirements as for the map function apply to the unmap function.
+unsafe impl<'a> Send for LV2Unmap<'a> {}
+unsafe impl<'a> Sync for LV2Unmap<'a> {}
+
 unsafe impl<'a> UriBound for LV2Unmap<'a> {
     const URI: &'static [u8] = sys::LV2_URID_UNMAP_URI;
 }
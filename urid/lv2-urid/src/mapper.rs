@@ -37,7 +37,21 @@ impl<M: Map + Unmap + Unpin> HostMap<M> {
     }
 
     /// Create a raw map interface.
-    pub fn make_map_interface(self: Pin<&mut Self>) -> sys::LV2_URID_Map {
+    ///
+    /// The specification allows plugins to use the interface from any non-realtime thread, which is why the mapper has to be `Send` and `Sync`. Mappers that can only be used from one thread are rejected:
+    ///
+    /// ```compile_fail
+    /// use lv2_urid::*;
+    /// use std::pin::Pin;
+    /// use urid::*;
+    ///
+    /// let mut host_map: Pin<Box<HostMap<LocalURIDMapper>>> = Box::pin(LocalURIDMapper::new().into());
+    /// let map_interface = host_map.as_mut().make_map_interface();
+    /// ```
+    pub fn make_map_interface(self: Pin<&mut Self>) -> sys::LV2_URID_Map
+    where
+        M: Send + Sync,
+    {
         sys::LV2_URID_Map {
             handle: self.get_mut() as *mut Self as *mut c_void,
             map: Some(Self::extern_map),
@@ -66,8 +80,11 @@ impl<M: Map + Unmap + Unpin> HostMap<M> {
 
     /// Create an unmap interface.
     ///
-    /// This method clones the mapper and creates a self-contained `UnmapInterface`.
-    pub fn make_unmap_interface(self: Pin<&mut Self>) -> sys::LV2_URID_Unmap {
+    /// This method clones the mapper and creates a self-contained `UnmapInterface`. Like for the map interface, the mapper has to be `Send` and `Sync`.
+    pub fn make_unmap_interface(self: Pin<&mut Self>) -> sys::LV2_URID_Unmap
+    where
+        M: Send + Sync,
+    {
         sys::LV2_URID_Unmap {
             handle: self.get_mut() as *mut Self as *mut c_void,
             unmap: Some(Self::extern_unmap),
@@ -43,6 +43,37 @@ fn test_unmap() {
     assert_eq!(MyTypeB::uri(), unmap.unmap(type_b).unwrap());
 }
 
+#[test]
+fn test_map_from_other_thread() {
+    let mut host_map: Pin<Box<HostMap<HashURIDMapper>>> = Box::pin(HashURIDMapper::new().into());
+    let map_interface = host_map.as_mut().make_map_interface();
+    let unmap_interface = host_map.as_mut().make_unmap_interface();
+    let map = LV2Map::new(&map_interface);
+    let unmap = LV2Unmap::new(&unmap_interface);
+
+    let type_a = map.map_type::<MyTypeA>().unwrap();
+
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                assert_eq!(type_a, map.map_type::<MyTypeA>().unwrap());
+                let type_b = map.map_type::<MyTypeB>().unwrap();
+                assert_eq!(MyTypeB::uri(), unmap.unmap(type_b).unwrap());
+            })
+            .join()
+            .unwrap();
+    });
+
+    // The mapping done in the other thread is visible in this one.
+    assert_eq!(2, map.map_type::<MyTypeB>().unwrap());
+
+    // An owned wrapper can be moved to the other thread.
+    let moved_map = LV2Map::new(&map_interface);
+    std::thread::scope(|scope| {
+        scope.spawn(move || assert_eq!(type_a, moved_map.map_type::<MyTypeA>().unwrap()));
+    });
+}
+
 #[derive(URIDCollection)]
 struct MyURIDCollection {
     type_a: URID<MyTypeA>,
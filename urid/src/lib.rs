@@ -47,9 +47,9 @@
 //! test_unmapper(&map, urids.my_struct_b);
 //! test_unmapper(&map, urid_a);
 //! ```
+use std::cell::RefCell;
 use std::cmp::{Ordering, PartialEq, PartialOrd};
 use std::collections::HashMap;
-use std::convert::TryInto;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
@@ -158,6 +158,28 @@ where
 ///     // Asserting.
 ///     assert_eq!(1, collection.my_type_a);
 ///     assert_eq!(2, collection.my_type_b);
+///
+/// Fields that aren't URIDs or collections can be marked with `#[urid(default)]`; They are initialized with `Default::default()` instead of being populated by the mapper. The derive macro also supports generic structs:
+///
+///     use urid::*;
+///     use std::marker::PhantomData;
+///
+///     #[uri("urn:my-type-a")]
+///     struct MyTypeA;
+///
+///     #[derive(URIDCollection)]
+///     struct MyGenericCollection<T: UriBound> {
+///         my_type: URID<T>,
+///         #[urid(default)]
+///         initialized: bool,
+///         #[urid(default)]
+///         phantom: PhantomData<T>,
+///     }
+///
+///     let map = HashURIDMapper::new();
+///     let collection = MyGenericCollection::<MyTypeA>::from_map(&map).unwrap();
+///     assert_eq!(1, collection.my_type);
+///     assert!(!collection.initialized);
 pub trait URIDCollection: Sized {
     /// Construct the collection from the mapper.
     fn from_map<M: Map + ?Sized>(map: &M) -> Option<Self>;
@@ -171,6 +193,21 @@ impl URID<()> {
     pub fn new(raw_urid: u32) -> Option<Self> {
         NonZeroU32::new(raw_urid).map(|inner| Self(inner, PhantomData))
     }
+
+    /// Create a new URID from a raw number and check that it is known to the unmapper.
+    ///
+    /// This is useful to validate URIDs from untrusted sources, like a stored state or a network message, before they are used. If the given number is zero or the unmapper doesn't know it, `None` is returned.
+    pub fn from_unmap<U: Unmap + ?Sized>(raw_urid: u32, unmap: &U) -> Option<Self> {
+        let urid = Self::new(raw_urid)?;
+        unmap.unmap(urid).map(|_| urid)
+    }
+
+    /// Parse a URID from its decimal representation, as printed by its `Display` implementation.
+    ///
+    /// Since the string carries no type information, the parsed URID is untyped. If the string isn't a decimal number, the number doesn't fit into a `u32` or the number is zero, `None` is returned.
+    pub fn parse(s: &str) -> Option<Self> {
+        s.parse().ok().and_then(Self::new)
+    }
 }
 
 impl<T: ?Sized> URID<T> {
@@ -210,6 +247,13 @@ impl<T: ?Sized> fmt::Debug for URID<T> {
     }
 }
 
+/// Prints the raw URID number.
+impl<T: ?Sized> fmt::Display for URID<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 impl<T: ?Sized> Clone for URID<T> {
     fn clone(&self) -> Self {
         Self(self.0, PhantomData)
@@ -277,6 +321,15 @@ impl std::convert::TryFrom<u32> for URID {
     }
 }
 
+impl std::str::FromStr for URID {
+    type Err = ();
+
+    #[inline]
+    fn from_str(s: &str) -> Result<URID, ()> {
+        URID::parse(s).ok_or(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::URID;
@@ -290,6 +343,26 @@ mod tests {
         assert_eq!(size, size_of::<URID>());
         assert_eq!(size, size_of::<Option<URID>>());
     }
+
+    #[test]
+    fn test_urid_display_and_parse() {
+        let urid = URID::new(42).unwrap();
+        assert_eq!("42", urid.to_string());
+        assert_eq!(Some(urid), URID::parse(&urid.to_string()));
+        assert_eq!(Ok(urid), "42".parse());
+
+        let max = URID::new(u32::MAX).unwrap();
+        assert_eq!(Some(max), URID::parse(&max.to_string()));
+
+        // Zero is not a valid URID.
+        assert_eq!(None, URID::parse("0"));
+        // The number doesn't fit into a `u32`.
+        assert_eq!(None, URID::parse("4294967296"));
+        assert_eq!(None, URID::parse("-1"));
+        assert_eq!(None, URID::parse(""));
+        assert_eq!(None, URID::parse("0x2a"));
+        assert_eq!(Err(()), "0".parse::<URID>());
+    }
 }
 
 /// A handle to map URIs to URIDs.
@@ -313,16 +386,20 @@ pub trait Map {
     /// This function copies the string into a vector, adds a null terminator and calls [`map_uri`](#tymethod.map_uri) with it. Therefore, the rules of `map_uri` apply here too.
     ///
     /// # Additional Errors
-    /// This method has the same error cases as `map_uri`, but also returns `None` if the string isn't an ASCII string or if the string can not be converted to a `Uri`.
+    /// This method has the same error cases as `map_uri`, but also returns `None` if the string contains a null byte or if the string can not be converted to a `Uri`. Any other UTF-8 string is accepted, since URIs may contain internationalized characters.
     fn map_str(&self, uri: &str) -> Option<URID> {
-        if !uri.is_ascii() {
-            return None;
-        }
         let mut bytes: Vec<u8> = uri.as_bytes().to_owned();
         bytes.push(0);
         self.map_uri(Uri::from_bytes_with_nul(bytes.as_ref()).ok()?)
     }
 
+    /// Map several URIs at once.
+    ///
+    /// Returns the URIDs in the same order as the URIs, or `None` if one of the URIs could not be mapped. The default implementation simply calls [`map_uri`](#tymethod.map_uri) for every URI, but implementations may override it to map all URIs in one go, e.g. by locking a mutex only once. The rules of `map_uri` apply here too.
+    fn map_all(&self, uris: &[&Uri]) -> Option<Vec<URID>> {
+        uris.iter().map(|uri| self.map_uri(uri)).collect()
+    }
+
     /// Retrieve the URI of the bound and map it to a URID.
     ///
     /// The rules of [`map_uri`](#tymethod.map_uri) apply here too.
@@ -350,49 +427,93 @@ pub trait Unmap {
     fn unmap<T: ?Sized>(&self, urid: URID<T>) -> Option<&Uri>;
 }
 
-/// A simple URI → URID mapper, backed by a standard `HashMap` and a `Mutex` for multi-thread
-/// access.
-#[derive(Default)]
-pub struct HashURIDMapper(Mutex<HashMap<UriBuf, URID>>);
+/// The storage of the hash-based mappers.
+///
+/// New URIDs are assigned consecutively, following the highest URID in the store. Since a restored store may contain gaps, the URIs are kept in a reverse index keyed by URID, which makes unmapping O(1).
+#[derive(Default, Clone)]
+struct URIDStore {
+    map: HashMap<UriBuf, URID>,
+    uris: HashMap<u32, UriBuf>,
+    last: u32,
+}
 
-impl Map for HashURIDMapper {
-    fn map_uri(&self, uri: &Uri) -> Option<URID<()>> {
-        let mut map = self.0.lock().ok()?; // Fail if the Mutex got poisoned
-        match map.get(uri) {
+impl URIDStore {
+    fn map_uri(&mut self, uri: &Uri) -> Option<URID> {
+        match self.map.get(uri) {
             Some(urid) => Some(*urid),
             None => {
-                let map_length: u32 = map.len().try_into().ok()?; // Fail if there are more items into the HashMap than an u32 can hold
-                let next_urid = map_length.checked_add(1)?; // Fail on overflow when adding 1 for the next URID
+                let next_urid = self.last.checked_add(1)?; // Fail on overflow when adding 1 for the next URID
 
-                // This is safe, because we just added 1 to the length and checked for overflow, therefore the number can never be 0.
+                // This is safe, because we just added 1 to the last URID and checked for overflow, therefore the number can never be 0.
                 let next_urid = unsafe { URID::new_unchecked(next_urid) };
-                map.insert(uri.into(), next_urid);
+                self.map.insert(uri.into(), next_urid);
+                self.uris.insert(next_urid.get(), uri.into());
+                self.last = next_urid.get();
                 Some(next_urid)
             }
         }
     }
-}
 
-impl Unmap for HashURIDMapper {
-    fn unmap<T: ?Sized>(&self, urid: URID<T>) -> Option<&Uri> {
-        let map = self.0.lock().ok()?;
-        for (uri, contained_urid) in map.iter() {
-            if *contained_urid == urid {
-                // Here we jump through some hoops to return a reference that bypasses the mutex.
-                // This is safe because the only way this reference might become invalid is if an
-                // entry gets overwritten, which is not something that we allow through this
-                // interface.
-                return Some(unsafe {
-                    let bytes = uri.as_bytes_with_nul();
-                    Uri::from_bytes_with_nul_unchecked(std::slice::from_raw_parts(
-                        bytes.as_ptr(),
-                        bytes.len(),
-                    ))
-                });
+    fn dump(&self) -> Vec<(UriBuf, u32)> {
+        let mut entries: Vec<(UriBuf, u32)> = self
+            .map
+            .iter()
+            .map(|(uri, urid)| (uri.clone(), urid.get()))
+            .collect();
+        entries.sort_by_key(|(_, urid)| *urid);
+        entries
+    }
+
+    fn load(entries: Vec<(UriBuf, u32)>) -> Option<Self> {
+        let mut store = Self::default();
+        for (uri, urid) in entries {
+            let urid = URID::new(urid)?; // Fail if the URID is zero
+            if store.uris.contains_key(&urid.get()) || store.map.contains_key(&uri) {
+                return None; // Fail if the URID or the URI is already taken
             }
+            store.map.insert(uri.clone(), urid);
+            store.uris.insert(urid.get(), uri);
+            store.last = store.last.max(urid.get());
         }
+        Some(store)
+    }
 
-        None
+    /// Look up the URI of the URID.
+    ///
+    /// The returned reference bypasses the borrow of the store, which may be guarded by a mutex or a cell. This is safe because the only way this reference might become invalid is if an entry gets overwritten or removed, which is not something that we allow through this interface. Moving the `UriBuf`s when the index grows doesn't move the URIs themselves, since they are stored on the heap.
+    fn unmap<'a>(&self, urid: u32) -> Option<&'a Uri> {
+        let uri = self.uris.get(&urid)?;
+        Some(unsafe {
+            let bytes = uri.as_bytes_with_nul();
+            Uri::from_bytes_with_nul_unchecked(std::slice::from_raw_parts(
+                bytes.as_ptr(),
+                bytes.len(),
+            ))
+        })
+    }
+}
+
+/// A simple URI → URID mapper, backed by a standard `HashMap` and a `Mutex` for multi-thread
+/// access.
+///
+/// The mapper also keeps a reverse index, so that unmapping a URID doesn't need to search the whole map.
+#[derive(Default)]
+pub struct HashURIDMapper(Mutex<URIDStore>);
+
+impl Map for HashURIDMapper {
+    fn map_uri(&self, uri: &Uri) -> Option<URID<()>> {
+        self.0.lock().ok()?.map_uri(uri) // Fail if the Mutex got poisoned
+    }
+
+    fn map_all(&self, uris: &[&Uri]) -> Option<Vec<URID>> {
+        let mut store = self.0.lock().ok()?;
+        uris.iter().map(|uri| store.map_uri(uri)).collect()
+    }
+}
+
+impl Unmap for HashURIDMapper {
+    fn unmap<T: ?Sized>(&self, urid: URID<T>) -> Option<&Uri> {
+        self.0.lock().ok()?.unmap(urid.get())
     }
 }
 
@@ -401,4 +522,196 @@ impl HashURIDMapper {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Return all URI-URID pairs of the mapper, sorted by URID.
+    ///
+    /// The returned entries can be stored and passed to [`load`](#method.load) later to restore the mapper, for example to keep URIDs stable across runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex of the mapper got poisoned.
+    pub fn dump(&self) -> Vec<(UriBuf, u32)> {
+        self.0.lock().unwrap().dump()
+    }
+
+    /// Restore a mapper from URI-URID pairs, as returned by [`dump`](#method.dump).
+    ///
+    /// The identity of the restored mapper doesn't depend on the order of the entries: Every URI is mapped to exactly the URID it is paired with, and new URIs are mapped to URIDs following the highest URID of the entries.
+    ///
+    /// Returns `None` if one of the URIDs is zero, or if a URID or a URI occurs more than once.
+    pub fn load(entries: Vec<(UriBuf, u32)>) -> Option<Self> {
+        URIDStore::load(entries).map(|store| Self(Mutex::new(store)))
+    }
+
+    /// Remove all mappings, so that the next mapped URI is assigned the URID 1 again.
+    ///
+    /// This is useful to reuse the same mapper for several plugin instances that run one after another. All URIDs retrieved from the mapper before become invalid: They may be assigned to different URIs afterwards.
+    ///
+    /// This method takes a mutable reference since the URIs returned by [`unmap`](trait.Unmap.html#tymethod.unmap) borrow the mapper and would dangle otherwise. A poisoned mutex is cleared too.
+    pub fn clear(&mut self) {
+        let store = match self.0.get_mut() {
+            Ok(store) => store,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *store = URIDStore::default();
+    }
+}
+
+/// Cloning a mapper creates a new, independent mapper with the same mappings.
+///
+/// The mutex of the source mapper is locked while its mappings are copied, which blocks concurrent mappings. Afterwards, the two mappers diverge: A URI that is mapped by only one of them may be assigned a different URID by the other. A poisoned mutex is ignored and the mappings are copied anyway.
+impl Clone for HashURIDMapper {
+    fn clone(&self) -> Self {
+        let store = match self.0.lock() {
+            Ok(store) => store,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        Self(Mutex::new(store.clone()))
+    }
+}
+
+/// A simple URI → URID mapper for single-threaded use, backed by a standard `HashMap` and a `RefCell`.
+///
+/// This mapper behaves exactly like the [`HashURIDMapper`](struct.HashURIDMapper.html), but it doesn't lock a mutex for every mapping. In exchange, it is not `Sync` and therefore can't be shared between threads. This makes it a good fit for test harnesses and offline renderers that only map URIs from one thread.
+#[derive(Default)]
+pub struct LocalURIDMapper(RefCell<URIDStore>);
+
+impl Map for LocalURIDMapper {
+    fn map_uri(&self, uri: &Uri) -> Option<URID<()>> {
+        self.0.borrow_mut().map_uri(uri)
+    }
+
+    fn map_all(&self, uris: &[&Uri]) -> Option<Vec<URID>> {
+        let mut store = self.0.borrow_mut();
+        uris.iter().map(|uri| store.map_uri(uri)).collect()
+    }
+}
+
+impl Unmap for LocalURIDMapper {
+    fn unmap<T: ?Sized>(&self, urid: URID<T>) -> Option<&Uri> {
+        self.0.borrow().unmap(urid.get())
+    }
+}
+
+impl LocalURIDMapper {
+    /// Create a new URID map store.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+/// A mapper for a fixed set of URIs, known at compile time.
+///
+/// The mapper is constructed from a static list of URI-URID pairs and only knows these pairs: Mapping any other URI or unmapping any other URID returns `None`. This also means that it violates the recommendation that [`map_uri`](trait.Map.html#tymethod.map_uri) should be dynamic; It's meant for plugins and tests with a small set of URIs known in advance.
+///
+/// The URIs are looked up with a linear search, without any locking or allocation. Therefore, the mapper can be used in a realtime context.
+///
+/// # Example
+///
+///     use urid::*;
+///
+///     static ENTRIES: [(&Uri, URID); 2] = unsafe {
+///         [
+///             (Uri::from_bytes_with_nul_unchecked(b"urn:my-type-a\0"), URID::new_unchecked(1)),
+///             (Uri::from_bytes_with_nul_unchecked(b"urn:my-type-b\0"), URID::new_unchecked(2)),
+///         ]
+///     };
+///
+///     let map = StaticMap::new(&ENTRIES);
+///     assert_eq!(2, map.map_str("urn:my-type-b").unwrap());
+///     assert!(map.map_str("urn:my-type-c").is_none());
+#[derive(Clone, Copy)]
+pub struct StaticMap {
+    entries: &'static [(&'static Uri, URID)],
+}
+
+impl StaticMap {
+    /// Create a new mapper from the given URI-URID pairs.
+    ///
+    /// Every URI and every URID should only appear once in the list. Otherwise, only the first matching pair is used.
+    pub const fn new(entries: &'static [(&'static Uri, URID)]) -> Self {
+        Self { entries }
+    }
+
+    /// Return the URI-URID pairs of the mapper.
+    pub fn entries(&self) -> &'static [(&'static Uri, URID)] {
+        self.entries
+    }
+}
+
+impl Map for StaticMap {
+    fn map_uri(&self, uri: &Uri) -> Option<URID> {
+        self.entries
+            .iter()
+            .find(|(entry_uri, _)| *entry_uri == uri)
+            .map(|(_, urid)| *urid)
+    }
+}
+
+impl Unmap for StaticMap {
+    fn unmap<T: ?Sized>(&self, urid: URID<T>) -> Option<&Uri> {
+        self.entries
+            .iter()
+            .find(|(_, entry_urid)| *entry_urid == urid)
+            .map(|(uri, _)| *uri)
+    }
+}
+
+/// A [`Map`](trait.Map.html) wrapper that memoizes the results of [`map_type`](trait.Map.html#method.map_type).
+///
+/// Every call to `map_type` on a regular mapper has to map the type's URI again, which usually involves hashing the URI and locking a mutex. This wrapper remembers the URID of every type it has mapped, so that repeated lookups of the same type don't reach the underlying mapper at all. Calls to [`map_uri`](trait.Map.html#tymethod.map_uri) are always forwarded.
+///
+/// Since `map_type` also accepts types that aren't `'static`, the cache can't be keyed by `TypeId`. Instead, it is keyed by the address of the type's static URI.
+///
+/// # Realtime usage
+/// The cache is backed by a `RefCell` and a `HashMap`, which may allocate when a type is mapped for the first time. This wrapper is therefore only a convenience for non-realtime code. In a realtime context, you should still use a [`URIDCollection`](trait.URIDCollection.html).
+pub struct CachingMap<M: Map> {
+    map: M,
+    cache: RefCell<HashMap<(usize, usize), URID>>,
+}
+
+impl<M: Map> CachingMap<M> {
+    /// Wrap the given mapper.
+    pub fn new(map: M) -> Self {
+        Self {
+            map,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Return a reference to the wrapped mapper.
+    pub fn inner(&self) -> &M {
+        &self.map
+    }
+
+    /// Unwrap the mapper and discard the cache.
+    pub fn into_inner(self) -> M {
+        self.map
+    }
+}
+
+impl<M: Map> Map for CachingMap<M> {
+    fn map_uri(&self, uri: &Uri) -> Option<URID> {
+        self.map.map_uri(uri)
+    }
+
+    fn map_all(&self, uris: &[&Uri]) -> Option<Vec<URID>> {
+        self.map.map_all(uris)
+    }
+
+    fn map_type<T: UriBound + ?Sized>(&self) -> Option<URID<T>> {
+        let key = (T::URI.as_ptr() as usize, T::URI.len());
+        if let Some(urid) = self.cache.borrow().get(&key) {
+            return Some(unsafe { URID::new_unchecked(urid.get()) });
+        }
+        let urid = self.map.map_type::<T>()?;
+        self.cache.borrow_mut().insert(key, urid.into_general());
+        Some(urid)
+    }
+}
+
+impl<M: Map + Unmap> Unmap for CachingMap<M> {
+    fn unmap<T: ?Sized>(&self, urid: URID<T>) -> Option<&Uri> {
+        self.map.unmap(urid)
+    }
 }
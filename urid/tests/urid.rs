@@ -33,6 +33,19 @@ fn test_unmap() {
 
     assert_eq!(MyTypeA::uri(), map.unmap(type_a).unwrap());
     assert_eq!(MyTypeB::uri(), map.unmap(type_b).unwrap());
+    assert!(map.unmap(URID::new(3).unwrap()).is_none());
+}
+
+#[test]
+fn test_unmap_many() {
+    let map = HashURIDMapper::new();
+
+    let uris: Vec<String> = (0..1000).map(|i| format!("urn:my-type-{}", i)).collect();
+    let urids: Vec<URID> = uris.iter().map(|uri| map.map_str(uri).unwrap()).collect();
+
+    for (uri, urid) in uris.iter().zip(urids.iter()) {
+        assert_eq!(uri.as_str(), map.unmap(*urid).unwrap().to_str().unwrap());
+    }
 }
 
 #[derive(URIDCollection)]
@@ -49,3 +62,247 @@ fn test_collection() {
     assert_eq!(1, collection.type_a);
     assert_eq!(2, collection.type_b);
 }
+
+#[test]
+fn test_map_all() {
+    let map = HashURIDMapper::new();
+    assert_eq!(1, map.map_type::<MyTypeB>().unwrap());
+
+    let urids = map
+        .map_all(&[MyTypeA::uri(), MyTypeB::uri(), MyTypeA::uri()])
+        .unwrap();
+    assert_eq!(
+        vec![2, 1, 2],
+        urids.iter().map(|urid| urid.get()).collect::<Vec<u32>>()
+    );
+    assert!(map.map_all(&[]).unwrap().is_empty());
+
+    // The default implementation maps the URIs one after another.
+    let counting = CountingMapper::default();
+    let urids = counting.map_all(&[MyTypeA::uri(), MyTypeB::uri()]).unwrap();
+    assert_eq!(2, counting.count.get());
+    assert_eq!(MyTypeA::uri(), counting.map.unmap(urids[0]).unwrap());
+    assert_eq!(MyTypeB::uri(), counting.map.unmap(urids[1]).unwrap());
+}
+
+#[test]
+fn test_local_mapper() {
+    let map = LocalURIDMapper::new();
+
+    assert_eq!(1, map.map_type::<MyTypeA>().unwrap());
+    assert_eq!(2, map.map_uri(MyTypeB::uri()).unwrap());
+    assert_eq!(1, map.map_uri(MyTypeA::uri()).unwrap());
+
+    let type_a: URID<MyTypeA> = map.map_type().unwrap();
+    let type_b: URID<MyTypeB> = map.map_type().unwrap();
+    assert_eq!(MyTypeA::uri(), map.unmap(type_a).unwrap());
+    assert_eq!(MyTypeB::uri(), map.unmap(type_b).unwrap());
+    assert!(map.unmap(URID::new(3).unwrap()).is_none());
+
+    let collection: MyURIDCollection = map.populate_collection().unwrap();
+    assert_eq!(1, collection.type_a);
+    assert_eq!(2, collection.type_b);
+}
+
+/// A mapper that counts how often it has been asked to map a URI.
+#[derive(Default)]
+struct CountingMapper {
+    map: HashURIDMapper,
+    count: std::cell::Cell<usize>,
+}
+
+impl Map for CountingMapper {
+    fn map_uri(&self, uri: &Uri) -> Option<URID> {
+        self.count.set(self.count.get() + 1);
+        self.map.map_uri(uri)
+    }
+}
+
+#[test]
+fn test_caching_map() {
+    let map = CachingMap::new(CountingMapper::default());
+
+    let type_a = map.map_type::<MyTypeA>().unwrap();
+    assert_eq!(1, map.inner().count.get());
+
+    assert_eq!(type_a, map.map_type::<MyTypeA>().unwrap());
+    assert_eq!(1, map.inner().count.get());
+
+    let type_b = map.map_type::<MyTypeB>().unwrap();
+    assert_ne!(type_a, type_b);
+    assert_eq!(2, map.inner().count.get());
+
+    // Untyped mappings are always forwarded.
+    assert_eq!(type_a, map.map_uri(MyTypeA::uri()).unwrap());
+    assert_eq!(3, map.inner().count.get());
+
+    let collection: MyURIDCollection = map.populate_collection().unwrap();
+    assert_eq!(type_a, collection.type_a);
+    assert_eq!(type_b, collection.type_b);
+    assert_eq!(3, map.inner().count.get());
+}
+
+#[test]
+fn test_dump_and_load() {
+    let map = HashURIDMapper::new();
+    let type_a = map.map_type::<MyTypeA>().unwrap();
+    let type_b = map.map_type::<MyTypeB>().unwrap();
+
+    let mut entries = map.dump();
+    assert_eq!(
+        vec![
+            (MyTypeA::uri().to_owned(), type_a.get()),
+            (MyTypeB::uri().to_owned(), type_b.get())
+        ],
+        entries
+    );
+
+    // The order of the entries doesn't matter.
+    entries.reverse();
+    let map = HashURIDMapper::load(entries).unwrap();
+    assert_eq!(type_a, map.map_type::<MyTypeA>().unwrap());
+    assert_eq!(type_b, map.map_type::<MyTypeB>().unwrap());
+    assert_eq!(MyTypeA::uri(), map.unmap(type_a).unwrap());
+
+    // New URIs are mapped after the highest existing URID.
+    let map = HashURIDMapper::load(vec![(MyTypeA::uri().to_owned(), 7)]).unwrap();
+    assert_eq!(8, map.map_type::<MyTypeB>().unwrap());
+    assert_eq!(MyTypeB::uri(), map.unmap(URID::new(8).unwrap()).unwrap());
+    assert!(map.unmap(URID::new(1).unwrap()).is_none());
+
+    // Zero and duplicate URIDs are rejected.
+    assert!(HashURIDMapper::load(vec![(MyTypeA::uri().to_owned(), 0)]).is_none());
+    assert!(HashURIDMapper::load(vec![
+        (MyTypeA::uri().to_owned(), 1),
+        (MyTypeB::uri().to_owned(), 1)
+    ])
+    .is_none());
+}
+
+#[test]
+fn test_clear() {
+    let mut map = HashURIDMapper::new();
+    assert_eq!(1, map.map_type::<MyTypeA>().unwrap());
+    assert_eq!(2, map.map_type::<MyTypeB>().unwrap());
+
+    map.clear();
+    assert!(map.dump().is_empty());
+    assert!(map.unmap(URID::new(1).unwrap()).is_none());
+
+    // Mapping restarts from the first URID.
+    assert_eq!(1, map.map_type::<MyTypeB>().unwrap());
+    assert_eq!(2, map.map_type::<MyTypeA>().unwrap());
+    assert_eq!(MyTypeB::uri(), map.unmap(URID::new(1).unwrap()).unwrap());
+}
+
+#[test]
+fn test_from_unmap() {
+    let map = HashURIDMapper::new();
+    assert_eq!(1, map.map_type::<MyTypeA>().unwrap());
+
+    assert_eq!(1, URID::from_unmap(1, &map).unwrap());
+    assert!(URID::from_unmap(0, &map).is_none());
+    assert!(URID::from_unmap(2, &map).is_none());
+
+    assert_eq!(2, map.map_type::<MyTypeB>().unwrap());
+    assert_eq!(2, URID::from_unmap(2, &map).unwrap());
+}
+
+#[test]
+fn test_clone_mapper() {
+    let map = HashURIDMapper::new();
+    assert_eq!(1, map.map_type::<MyTypeA>().unwrap());
+
+    let clone = map.clone();
+    assert_eq!(1, clone.map_type::<MyTypeA>().unwrap());
+    assert_eq!(MyTypeA::uri(), clone.unmap(URID::new(1).unwrap()).unwrap());
+
+    // The mappers diverge after cloning.
+    assert_eq!(2, map.map_type::<MyTypeB>().unwrap());
+    assert_eq!(2, clone.map_str("urn:my-type-c").unwrap());
+    assert_eq!(MyTypeB::uri(), map.unmap(URID::new(2).unwrap()).unwrap());
+    assert_eq!(
+        "urn:my-type-c",
+        clone
+            .unmap(URID::new(2).unwrap())
+            .unwrap()
+            .to_str()
+            .unwrap()
+    );
+    assert_eq!(3, clone.map_type::<MyTypeB>().unwrap());
+}
+
+#[derive(URIDCollection)]
+struct MyGenericCollection<T: UriBound> {
+    inner: MyURIDCollection,
+    generic: URID<T>,
+    #[urid(default)]
+    counter: u32,
+    #[urid(default)]
+    phantom: std::marker::PhantomData<T>,
+}
+
+#[test]
+fn test_generic_collection() {
+    let map = HashURIDMapper::new();
+    let collection = MyGenericCollection::<MyTypeB>::from_map(&map).unwrap();
+
+    assert_eq!(1, collection.inner.type_a);
+    assert_eq!(2, collection.inner.type_b);
+    assert_eq!(2, collection.generic);
+    assert_eq!(0, collection.counter);
+}
+
+#[test]
+fn test_map_utf8_str() {
+    let map = HashURIDMapper::new();
+
+    let urid = map.map_str("urn:my-type:größe-µ-音").unwrap();
+    assert_eq!(urid, map.map_str("urn:my-type:größe-µ-音").unwrap());
+    assert_eq!(
+        "urn:my-type:größe-µ-音",
+        map.unmap(urid).unwrap().to_str().unwrap()
+    );
+
+    // Interior null bytes are still rejected.
+    assert!(map.map_str("urn:my-type:\0größe").is_none());
+}
+
+static STATIC_ENTRIES: [(&Uri, URID); 3] = unsafe {
+    [
+        (
+            Uri::from_bytes_with_nul_unchecked(b"urn:my-type-a\0"),
+            URID::new_unchecked(3),
+        ),
+        (
+            Uri::from_bytes_with_nul_unchecked(b"urn:my-type-b\0"),
+            URID::new_unchecked(7),
+        ),
+        (
+            Uri::from_bytes_with_nul_unchecked(b"urn:my-type-c\0"),
+            URID::new_unchecked(11),
+        ),
+    ]
+};
+
+#[test]
+fn test_static_map() {
+    let map = StaticMap::new(&STATIC_ENTRIES);
+
+    assert_eq!(3, map.map_type::<MyTypeA>().unwrap());
+    assert_eq!(7, map.map_type::<MyTypeB>().unwrap());
+    assert_eq!(11, map.map_str("urn:my-type-c").unwrap());
+    assert!(map.map_str("urn:my-type-d").is_none());
+
+    assert_eq!(MyTypeA::uri(), map.unmap(URID::new(3).unwrap()).unwrap());
+    assert_eq!(MyTypeB::uri(), map.unmap(URID::new(7).unwrap()).unwrap());
+    assert_eq!(
+        "urn:my-type-c",
+        map.unmap(URID::new(11).unwrap()).unwrap().to_str().unwrap()
+    );
+    assert!(map.unmap(URID::new(1).unwrap()).is_none());
+
+    let collection = MyURIDCollection::from_map(&map).unwrap();
+    assert_eq!(3, collection.type_a);
+    assert_eq!(7, collection.type_b);
+}
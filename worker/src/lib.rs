@@ -158,6 +158,8 @@ impl<T> fmt::Display for ScheduleError<T> {
 }
 
 /// Host feature providing data to build a ScheduleHandler.
+///
+/// Work is scheduled in the `run` method of the plugin, so this feature is only allowed in the `Audio` threading class. Requesting it in any other class, for example as a part of the `InitFeatures`, is a usage error and panics.
 #[repr(transparent)]
 pub struct Schedule<'a, P> {
     internal: &'a lv2_sys::LV2_Worker_Schedule,
@@ -208,6 +210,9 @@ impl<'a, P: Worker> Schedule<'a, P> {
     where
         P::WorkData: 'static + Send,
     {
+        if P::RAW_WORK {
+            return Err(ScheduleError::Unknown(worker_data));
+        }
         let worker_data = ManuallyDrop::new(worker_data);
         let size = mem::size_of_val(&worker_data) as u32;
         let ptr = &worker_data as *const _ as *const c_void;
@@ -231,6 +236,39 @@ impl<'a, P: Worker> Schedule<'a, P> {
             ))),
         }
     }
+
+    /// Request the host to call the worker thread with a raw byte buffer.
+    ///
+    /// The bytes are passed to the host as they are and the host hands a copy of them to
+    /// [`Worker::work_bytes`](trait.Worker.html#method.work_bytes). This avoids the generic copy of
+    /// `schedule_work` for plugins that already have a serialized representation of their work, for
+    /// example the path of a sample file. The plugin is responsible for the serialization and
+    /// deserialization of the data in this mode.
+    ///
+    /// Raw work is only accepted by workers that set
+    /// [`Worker::RAW_WORK`](trait.Worker.html#associatedconstant.RAW_WORK). For all other
+    /// workers, this method returns an `Unknown` error.
+    pub fn schedule_work_bytes(&self, data: &[u8]) -> Result<(), ScheduleError<()>> {
+        if !P::RAW_WORK {
+            return Err(ScheduleError::Unknown(()));
+        }
+        let schedule_work = if let Some(schedule_work) = self.internal.schedule_work {
+            schedule_work
+        } else {
+            return Err(ScheduleError::NoCallback(()));
+        };
+        match unsafe {
+            (schedule_work)(
+                self.internal.handle,
+                data.len() as u32,
+                data.as_ptr() as *const c_void,
+            )
+        } {
+            lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS => Ok(()),
+            lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE => Err(ScheduleError::NoSpace(())),
+            _ => Err(ScheduleError::Unknown(())),
+        }
+    }
 }
 
 /// Errors potentially generated by the
@@ -317,6 +355,79 @@ impl<P: Worker> ResponseHandler<P> {
             ))),
         }
     }
+
+    /// Send several responses to the `run` context at once.
+    ///
+    /// The items are transmitted in one message and the host calls `work_response` once per item, in
+    /// the order they were given. Sending an empty batch does nothing.
+    ///
+    /// If this method fails, the items are considered as untransmitted and are returned to the
+    /// caller.
+    pub fn respond_batch(
+        &self,
+        items: impl IntoIterator<Item = P::ResponseData>,
+    ) -> Result<(), RespondError<Vec<P::ResponseData>>> {
+        let mut items: Vec<P::ResponseData> = items.into_iter().collect();
+        if items.is_empty() {
+            return Ok(());
+        }
+        let response_function = if let Some(response_function) = self.response_function {
+            response_function
+        } else {
+            return Err(RespondError::NoCallback(items));
+        };
+
+        let header = BatchHeader {
+            magic: BatchHeader::MAGIC,
+            count: items.len() as u32,
+        };
+        let items_size = items.len() * mem::size_of::<P::ResponseData>();
+        let buffer_size = mem::size_of::<BatchHeader>() + items_size;
+        let mut buffer: Vec<u8> = Vec::with_capacity(buffer_size);
+        unsafe {
+            ptr::copy_nonoverlapping(
+                &header as *const BatchHeader as *const u8,
+                buffer.as_mut_ptr(),
+                mem::size_of::<BatchHeader>(),
+            );
+            ptr::copy_nonoverlapping(
+                items.as_ptr() as *const u8,
+                buffer.as_mut_ptr().add(mem::size_of::<BatchHeader>()),
+                items_size,
+            );
+            buffer.set_len(buffer_size);
+        }
+
+        match unsafe {
+            (response_function)(
+                self.respond_handle,
+                buffer.len() as u32,
+                buffer.as_ptr() as *const c_void,
+            )
+        } {
+            lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS => {
+                // The items now belong to the copy of the host.
+                unsafe { items.set_len(0) };
+                Ok(())
+            }
+            lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE => Err(RespondError::NoSpace(items)),
+            _ => Err(RespondError::Unknown(items)),
+        }
+    }
+}
+
+/// Header of a response batch sent by [`ResponseHandler::respond_batch`](struct.ResponseHandler.html#method.respond_batch).
+///
+/// It is followed by `count` unaligned values of the response data type.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct BatchHeader {
+    magic: u32,
+    count: u32,
+}
+
+impl BatchHeader {
+    const MAGIC: u32 = u32::from_le_bytes(*b"LV2B");
 }
 
 /// Errors potentially generated by [`Worker`](trait.Worker.html) methods
@@ -326,6 +437,21 @@ pub enum WorkerError {
     Unknown,
     /// Failure due to a lack of space
     NoSpace,
+    /// Work couldn't be scheduled since the host didn't provide a `schedule_work` callback
+    ///
+    /// Contrary to the other variants, this isn't a transient error: The plugin may fall back to
+    /// doing the work synchronously.
+    NoSchedule,
+}
+
+impl<T> From<ScheduleError<T>> for WorkerError {
+    fn from(error: ScheduleError<T>) -> Self {
+        match error {
+            ScheduleError::Unknown(..) => WorkerError::Unknown,
+            ScheduleError::NoSpace(..) => WorkerError::NoSpace,
+            ScheduleError::NoCallback(..) => WorkerError::NoSchedule,
+        }
+    }
 }
 
 /// The non-realtime working extension for plugins.
@@ -341,6 +467,15 @@ pub trait Worker: Plugin {
     type WorkData: 'static + Send;
     /// Type of data sent to `work_response` by the response handler.
     type ResponseData: 'static + Send;
+    /// Whether the work of this plugin is scheduled as raw bytes.
+    ///
+    /// If `true`, work is scheduled with
+    /// [`Schedule::schedule_work_bytes`](struct.Schedule.html#method.schedule_work_bytes) and
+    /// handled by `work_bytes`. Otherwise, work is scheduled with
+    /// [`Schedule::schedule_work`](struct.Schedule.html#method.schedule_work) and handled by
+    /// `work`. Since all work of a plugin has the same form, it's passed to the host without any
+    /// framing.
+    const RAW_WORK: bool = false;
     /// The work to do in a non-real-time context,
     ///
     /// This is called by the host in a non-realtime context as requested, probably in a separate
@@ -356,6 +491,19 @@ pub trait Worker: Plugin {
         data: Self::WorkData,
     ) -> Result<(), WorkerError>;
 
+    /// The work to do in a non-real-time context, for data scheduled with
+    /// [`Schedule::schedule_work_bytes`](struct.Schedule.html#method.schedule_work_bytes).
+    ///
+    /// This method is only called if `RAW_WORK` is `true`. The same rules as for `work` apply.
+    /// `data` is the copy of the scheduled bytes provided by the host and it's up to the plugin to
+    /// deserialize it. By default, raw work is rejected with an `Unknown` error.
+    fn work_bytes(
+        _response_handler: &ResponseHandler<Self>,
+        _data: &[u8],
+    ) -> Result<(), WorkerError> {
+        Err(WorkerError::Unknown)
+    }
+
     /// Handle a response from the worker.
     ///
     /// This is called by the host in the `run()` context when a response from the worker is ready.
@@ -402,17 +550,30 @@ impl<P: Worker> WorkerDescriptor<P> {
             respond_handle,
             phantom: PhantomData::<P>,
         };
-        //build ref to worker data from raw pointer
-        let worker_data =
-            ptr::read_unaligned(data as *const mem::ManuallyDrop<<P as Worker>::WorkData>);
-        let worker_data = mem::ManuallyDrop::into_inner(worker_data);
-        if size as usize != mem::size_of_val(&worker_data) {
-            return lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
-        }
-        match P::work(&response_handler, worker_data) {
+        let result = if P::RAW_WORK {
+            let data = if size == 0 {
+                &[]
+            } else if data.is_null() {
+                return lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
+            } else {
+                std::slice::from_raw_parts(data as *const u8, size as usize)
+            };
+            P::work_bytes(&response_handler, data)
+        } else {
+            if size as usize != mem::size_of::<<P as Worker>::WorkData>() {
+                return lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
+            }
+            //build ref to worker data from raw pointer
+            let worker_data =
+                ptr::read_unaligned(data as *const mem::ManuallyDrop<<P as Worker>::WorkData>);
+            let worker_data = mem::ManuallyDrop::into_inner(worker_data);
+            P::work(&response_handler, worker_data)
+        };
+        match result {
             Ok(()) => lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS,
             Err(WorkerError::Unknown) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
             Err(WorkerError::NoSpace) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+            Err(WorkerError::NoSchedule) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
         }
     }
 
@@ -429,20 +590,49 @@ impl<P: Worker> WorkerDescriptor<P> {
             } else {
                 return lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
             };
-        //build ref to response data from raw pointer
-        let response_data =
-            ptr::read_unaligned(body as *const mem::ManuallyDrop<<P as Worker>::ResponseData>);
-        let response_data = mem::ManuallyDrop::into_inner(response_data);
-        if size as usize != mem::size_of_val(&response_data) {
-            return lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
+        let (instance, features) = plugin_instance.audio_class_handle();
+
+        let item_size = mem::size_of::<<P as Worker>::ResponseData>();
+        if size as usize == item_size {
+            //build ref to response data from raw pointer
+            let response_data =
+                ptr::read_unaligned(body as *const mem::ManuallyDrop<<P as Worker>::ResponseData>);
+            let response_data = mem::ManuallyDrop::into_inner(response_data);
+            return match instance.work_response(response_data, features) {
+                Ok(()) => lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS,
+                Err(WorkerError::Unknown) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
+                Err(WorkerError::NoSpace) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+                Err(WorkerError::NoSchedule) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
+            };
         }
 
-        let (instance, features) = plugin_instance.audio_class_handle();
-        match instance.work_response(response_data, features) {
-            Ok(()) => lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS,
-            Err(WorkerError::Unknown) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
-            Err(WorkerError::NoSpace) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+        //anything else has to be a batch of responses
+        if (size as usize) < mem::size_of::<BatchHeader>() {
+            return lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
         }
+        let header = ptr::read_unaligned(body as *const BatchHeader);
+        if header.magic != BatchHeader::MAGIC
+            || size as usize != mem::size_of::<BatchHeader>() + header.count as usize * item_size
+        {
+            return lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
+        }
+        let items = (body as *const u8).add(mem::size_of::<BatchHeader>());
+        let mut status = lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS;
+        for i in 0..header.count as usize {
+            let response_data =
+                ptr::read_unaligned(items.add(i * item_size) as *const <P as Worker>::ResponseData);
+            // After a failure, the remaining items are only dropped.
+            if status != lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS {
+                continue;
+            }
+            status = match instance.work_response(response_data, features) {
+                Ok(()) => lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS,
+                Err(WorkerError::Unknown) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
+                Err(WorkerError::NoSpace) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+                Err(WorkerError::NoSchedule) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
+            };
+        }
+        status
     }
 
     /// Extern unsafe version of `end_run` method actually called by the host
@@ -453,6 +643,7 @@ impl<P: Worker> WorkerDescriptor<P> {
                 Ok(()) => lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS,
                 Err(WorkerError::Unknown) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
                 Err(WorkerError::NoSpace) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+                Err(WorkerError::NoSchedule) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
             }
         } else {
             lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN
@@ -532,6 +723,10 @@ mod tests {
         }
 
         fn run(&mut self, _ports: &mut Ports, _features: &mut Self::InitFeatures, _: u32) {}
+
+        fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+            match_extensions![uri, WorkerDescriptor<Self>]
+        }
     }
 
     impl Worker for TestDropWorker {
@@ -586,6 +781,166 @@ mod tests {
         LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN
     }
 
+    struct TestBatchWorker;
+
+    unsafe impl UriBound for TestBatchWorker {
+        const URI: &'static [u8] = b"not relevant\0";
+    }
+
+    impl Plugin for TestBatchWorker {
+        type Ports = Ports;
+        type InitFeatures = ();
+        type AudioFeatures = ();
+
+        fn new(_plugin_info: &PluginInfo, _features: &mut Self::InitFeatures) -> Option<Self> {
+            Some(Self {})
+        }
+
+        fn run(&mut self, _ports: &mut Ports, _features: &mut Self::InitFeatures, _: u32) {}
+    }
+
+    thread_local! {
+        static BATCH_MESSAGE: std::cell::RefCell<Vec<u8>> = const { std::cell::RefCell::new(Vec::new()) };
+        static BATCH_RESPONSES: std::cell::RefCell<Vec<u32>> = const { std::cell::RefCell::new(Vec::new()) };
+        static WORK_BYTES: std::cell::RefCell<Vec<u8>> = const { std::cell::RefCell::new(Vec::new()) };
+    }
+
+    impl Worker for TestBatchWorker {
+        type WorkData = ();
+        type ResponseData = u32;
+        const RAW_WORK: bool = true;
+
+        fn work(_response_handler: &ResponseHandler<Self>, _data: ()) -> Result<(), WorkerError> {
+            Ok(())
+        }
+
+        fn work_bytes(
+            _response_handler: &ResponseHandler<Self>,
+            data: &[u8],
+        ) -> Result<(), WorkerError> {
+            WORK_BYTES.with(|bytes| *bytes.borrow_mut() = data.to_vec());
+            Ok(())
+        }
+
+        fn work_response(
+            &mut self,
+            data: u32,
+            _features: &mut Self::AudioFeatures,
+        ) -> Result<(), WorkerError> {
+            BATCH_RESPONSES.with(|responses| responses.borrow_mut().push(data));
+            Ok(())
+        }
+    }
+
+    extern "C" fn recording_respond(
+        _handle: LV2_Worker_Respond_Handle,
+        size: u32,
+        data: *const c_void,
+    ) -> LV2_Worker_Status {
+        let data = unsafe { std::slice::from_raw_parts(data as *const u8, size as usize) };
+        BATCH_MESSAGE.with(|message| *message.borrow_mut() = data.to_vec());
+        LV2_Worker_Status_LV2_WORKER_SUCCESS
+    }
+
+    #[test]
+    fn respond_batch_calls_work_response_per_item() {
+        let respond = ResponseHandler {
+            response_function: Some(recording_respond),
+            respond_handle: ptr::null_mut(),
+            phantom: PhantomData::<TestBatchWorker>,
+        };
+        let mut instance = PluginInstance::<TestBatchWorker>::test_builder()
+            .build()
+            .unwrap();
+        let ptr_tbw = instance.handle();
+
+        respond.respond_batch(vec![1, 2, 3]).unwrap();
+        let message = BATCH_MESSAGE.with(|message| message.borrow().clone());
+        assert_eq!(message.len(), 8 + 3 * mem::size_of::<u32>());
+        let status = unsafe {
+            WorkerDescriptor::<TestBatchWorker>::extern_work_response(
+                ptr_tbw,
+                message.len() as u32,
+                message.as_ptr() as *const c_void,
+            )
+        };
+        assert_eq!(status, LV2_Worker_Status_LV2_WORKER_SUCCESS);
+        assert_eq!(
+            BATCH_RESPONSES.with(|responses| responses.borrow().clone()),
+            vec![1, 2, 3]
+        );
+
+        // Single responses are still delivered.
+        respond.respond(4).unwrap();
+        let message = BATCH_MESSAGE.with(|message| message.borrow().clone());
+        let status = unsafe {
+            WorkerDescriptor::<TestBatchWorker>::extern_work_response(
+                ptr_tbw,
+                message.len() as u32,
+                message.as_ptr() as *const c_void,
+            )
+        };
+        assert_eq!(status, LV2_Worker_Status_LV2_WORKER_SUCCESS);
+        assert_eq!(
+            BATCH_RESPONSES.with(|responses| responses.borrow().clone()),
+            vec![1, 2, 3, 4]
+        );
+
+        // A truncated batch is rejected.
+        respond.respond_batch(vec![5, 6]).unwrap();
+        let message = BATCH_MESSAGE.with(|message| message.borrow().clone());
+        let status = unsafe {
+            WorkerDescriptor::<TestBatchWorker>::extern_work_response(
+                ptr_tbw,
+                message.len() as u32 - 1,
+                message.as_ptr() as *const c_void,
+            )
+        };
+        assert_eq!(status, LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN);
+        assert_eq!(
+            BATCH_RESPONSES.with(|responses| responses.borrow().len()),
+            4
+        );
+    }
+
+    #[test]
+    fn respond_batch_returns_items_on_error() {
+        let respond = ResponseHandler {
+            response_function: Some(faulty_respond),
+            respond_handle: ptr::null_mut(),
+            phantom: PhantomData::<TestBatchWorker>,
+        };
+        assert_eq!(
+            respond.respond_batch(vec![1, 2]),
+            Err(RespondError::Unknown(vec![1, 2]))
+        );
+        // Empty batches aren't sent at all.
+        assert_eq!(respond.respond_batch(Vec::new()), Ok(()));
+    }
+
+    #[test]
+    fn extension_data_is_worker_interface() {
+        use lv2_core::extension::downcast_extension;
+
+        let interface = TestDropWorker::extension_data(WorkerDescriptor::<TestDropWorker>::uri())
+            .and_then(downcast_extension::<LV2_Worker_Interface>)
+            .unwrap();
+        assert!(ptr::eq(
+            interface,
+            <WorkerDescriptor<TestDropWorker> as ExtensionDescriptor>::INTERFACE
+        ));
+        assert!(interface.work.is_some());
+        assert!(interface.work_response.is_some());
+        assert!(interface.end_run.is_some());
+
+        // Asking for another interface type fails.
+        assert!(
+            TestDropWorker::extension_data(WorkerDescriptor::<TestDropWorker>::uri())
+                .and_then(downcast_extension::<LV2_Worker_Schedule>)
+                .is_none()
+        );
+    }
+
     #[test]
     fn schedule_must_not_drop() {
         let hd = HasDrop::new(0);
@@ -615,6 +970,117 @@ mod tests {
         let _ = schedule.schedule_work(hd);
     }
 
+    extern "C" fn recording_schedule(
+        _handle: LV2_Worker_Schedule_Handle,
+        size: u32,
+        data: *const c_void,
+    ) -> LV2_Worker_Status {
+        let data = unsafe { std::slice::from_raw_parts(data as *const u8, size as usize) };
+        BATCH_MESSAGE.with(|message| *message.borrow_mut() = data.to_vec());
+        LV2_Worker_Status_LV2_WORKER_SUCCESS
+    }
+
+    #[test]
+    fn schedule_work_bytes_calls_work_bytes() {
+        let internal = lv2_sys::LV2_Worker_Schedule {
+            handle: ptr::null_mut(),
+            schedule_work: Some(recording_schedule),
+        };
+        let schedule = Schedule {
+            internal: &internal,
+            phantom: PhantomData::<*const TestBatchWorker>,
+        };
+        let mut instance = PluginInstance::<TestBatchWorker>::test_builder()
+            .build()
+            .unwrap();
+        let ptr_tbw = instance.handle();
+
+        // The bytes are passed to the host as they are.
+        schedule.schedule_work_bytes(b"samples/kick.wav").unwrap();
+        let message = BATCH_MESSAGE.with(|message| message.borrow().clone());
+        assert_eq!(message.as_slice(), b"samples/kick.wav");
+        let status = unsafe {
+            WorkerDescriptor::<TestBatchWorker>::extern_work(
+                ptr_tbw,
+                Some(extern_respond),
+                ptr::null_mut(),
+                message.len() as u32,
+                message.as_ptr() as *const c_void,
+            )
+        };
+        assert_eq!(status, LV2_Worker_Status_LV2_WORKER_SUCCESS);
+        assert_eq!(
+            WORK_BYTES.with(|bytes| bytes.borrow().clone()).as_slice(),
+            b"samples/kick.wav"
+        );
+
+        // An empty buffer is as long as `WorkData`, but still delivered as raw bytes.
+        schedule.schedule_work_bytes(&[]).unwrap();
+        let status = unsafe {
+            WorkerDescriptor::<TestBatchWorker>::extern_work(
+                ptr_tbw,
+                Some(extern_respond),
+                ptr::null_mut(),
+                0,
+                ptr::null(),
+            )
+        };
+        assert_eq!(status, LV2_Worker_Status_LV2_WORKER_SUCCESS);
+        assert!(WORK_BYTES.with(|bytes| bytes.borrow().is_empty()));
+
+        // Workers with raw work don't accept typed work.
+        assert_eq!(schedule.schedule_work(()), Err(ScheduleError::Unknown(())));
+
+        // Workers with typed work don't accept raw work.
+        let schedule = Schedule {
+            internal: &internal,
+            phantom: PhantomData::<*const TestDropWorker>,
+        };
+        assert_eq!(
+            schedule.schedule_work_bytes(&message),
+            Err(ScheduleError::Unknown(()))
+        );
+        let status = unsafe {
+            WorkerDescriptor::<TestDropWorker>::extern_work(
+                ptr_tbw,
+                Some(extern_respond),
+                ptr::null_mut(),
+                message.len() as u32,
+                message.as_ptr() as *const c_void,
+            )
+        };
+        assert_eq!(status, LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN);
+    }
+
+    #[test]
+    fn schedule_error_converts_to_worker_error() {
+        let internal = lv2_sys::LV2_Worker_Schedule {
+            handle: ptr::null_mut(),
+            schedule_work: None,
+        };
+        let schedule = Schedule {
+            internal: &internal,
+            phantom: PhantomData::<*const TestBatchWorker>,
+        };
+        let error = schedule.schedule_work_bytes(&[]).unwrap_err();
+        assert_eq!(WorkerError::from(error), WorkerError::NoSchedule);
+
+        let internal = lv2_sys::LV2_Worker_Schedule {
+            handle: ptr::null_mut(),
+            schedule_work: Some(faulty_schedule),
+        };
+        let schedule = Schedule {
+            internal: &internal,
+            phantom: PhantomData::<*const TestBatchWorker>,
+        };
+        let error = schedule.schedule_work_bytes(&[]).unwrap_err();
+        assert_eq!(WorkerError::from(error), WorkerError::Unknown);
+        assert_eq!(
+            WorkerError::from(ScheduleError::NoSpace(())),
+            WorkerError::NoSpace
+        );
+    }
+
     #[test]
     fn respond_must_not_drop() {
         let hd = HasDrop::new(0);
@@ -644,10 +1110,10 @@ mod tests {
         let hd = mem::ManuallyDrop::new(HasDrop::new(0));
         let ptr_hd = &hd as *const _ as *const c_void;
         let size = mem::size_of_val(&hd) as u32;
-        let mut tdw = TestDropWorker {};
-
-        let ptr_tdw = &mut tdw as *mut _ as *mut c_void;
-        //trash trick i use Plugin ptr insteas of Pluginstance ptr
+        let mut instance = PluginInstance::<TestDropWorker>::test_builder()
+            .build()
+            .unwrap();
+        let ptr_tdw = instance.handle();
         unsafe {
             WorkerDescriptor::<TestDropWorker>::extern_work(
                 ptr_tdw,
@@ -664,10 +1130,10 @@ mod tests {
         let hd = mem::ManuallyDrop::new(HasDrop::new(1));
         let ptr_hd = &hd as *const _ as *const c_void;
         let size = mem::size_of_val(&hd) as u32;
-        let mut tdw = TestDropWorker {};
-
-        let ptr_tdw = &mut tdw as *mut _ as *mut c_void;
-        //trash trick i use Plugin ptr insteas of Pluginstance ptr
+        let mut instance = PluginInstance::<TestDropWorker>::test_builder()
+            .build()
+            .unwrap();
+        let ptr_tdw = instance.handle();
         unsafe {
             WorkerDescriptor::<TestDropWorker>::extern_work(
                 ptr_tdw,
@@ -685,10 +1151,10 @@ mod tests {
         let hd = mem::ManuallyDrop::new(HasDrop::new(0));
         let ptr_hd = &hd as *const _ as *const c_void;
         let size = mem::size_of_val(&hd) as u32;
-        let mut tdw = TestDropWorker {};
-
-        let ptr_tdw = &mut tdw as *mut _ as *mut c_void;
-        //trash trick i use Plugin ptr insteas of Pluginstance ptr
+        let mut instance = PluginInstance::<TestDropWorker>::test_builder()
+            .build()
+            .unwrap();
+        let ptr_tdw = instance.handle();
         unsafe {
             WorkerDescriptor::<TestDropWorker>::extern_work_response(ptr_tdw, size, ptr_hd);
         }
@@ -699,12 +1165,42 @@ mod tests {
         let hd = mem::ManuallyDrop::new(HasDrop::new(1));
         let ptr_hd = &hd as *const _ as *const c_void;
         let size = mem::size_of_val(&hd) as u32;
-        let mut tdw = TestDropWorker {};
-
-        let ptr_tdw = &mut tdw as *mut _ as *mut c_void;
-        //trash trick i use Plugin ptr insteas of Pluginstance ptr
+        let mut instance = PluginInstance::<TestDropWorker>::test_builder()
+            .build()
+            .unwrap();
+        let ptr_tdw = instance.handle();
         unsafe {
             WorkerDescriptor::<TestDropWorker>::extern_work_response(ptr_tdw, size, ptr_hd);
         }
     }
+
+    #[test]
+    fn schedule_is_allowed_in_audio_class() {
+        let schedule = LV2_Worker_Schedule {
+            handle: ptr::null_mut(),
+            schedule_work: Some(extern_schedule),
+        };
+        let feature = unsafe {
+            Schedule::<TestDropWorker>::from_feature_ptr(
+                &schedule as *const _ as *const c_void,
+                ThreadingClass::Audio,
+            )
+        };
+        assert!(feature.is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "only allowed in the audio threading class")]
+    fn schedule_panics_in_instantiation_class() {
+        let schedule = LV2_Worker_Schedule {
+            handle: ptr::null_mut(),
+            schedule_work: Some(extern_schedule),
+        };
+        unsafe {
+            Schedule::<TestDropWorker>::from_feature_ptr(
+                &schedule as *const _ as *const c_void,
+                ThreadingClass::Instantiation,
+            );
+        }
+    }
 }